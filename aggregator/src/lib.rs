@@ -6,6 +6,7 @@ use aleph_bft_types::Recipient;
 const LOG_TARGET: &str = "aleph-aggregator";
 
 mod aggregator;
+pub mod mock;
 
 pub use crate::aggregator::{HashSignatureAggregator, IO};
 
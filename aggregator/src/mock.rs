@@ -0,0 +1,357 @@
+//! Utilities for testing code built on top of the aggregator without a real network or real
+//! cryptography.
+
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Display, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use aleph_bft_types::{
+    Index, Keychain, MultiKeychain, NodeCount, NodeIndex, PartialMultisignature, Recipient,
+    SignatureSet,
+};
+use parity_scale_codec::{Decode, Encode};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::{NetworkError, ProtocolSink};
+
+/// A hash with a predictable value, for use in tests.
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Encode, Decode, Debug)]
+pub struct MockHash(pub [u8; 32]);
+
+impl MockHash {
+    /// Creates a hash deterministically derived from the seed.
+    pub fn from_seed(seed: u64) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&seed.to_le_bytes());
+        MockHash(bytes)
+    }
+}
+
+impl AsRef<[u8]> for MockHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for MockHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+/// Generates a reproducible sequence of distinct hashes.
+#[derive(Default, Debug, Clone)]
+pub struct HashGenerator {
+    next_seed: u64,
+}
+
+impl HashGenerator {
+    /// Creates a generator starting from the given seed.
+    pub fn new(seed: u64) -> Self {
+        HashGenerator { next_seed: seed }
+    }
+
+    /// Returns the next hash in the sequence.
+    pub fn next_hash(&mut self) -> MockHash {
+        let hash = MockHash::from_seed(self.next_seed);
+        self.next_seed += 1;
+        hash
+    }
+
+    /// Returns the next `count` hashes in the sequence.
+    pub fn next_hashes(&mut self, count: usize) -> Vec<MockHash> {
+        (0..count).map(|_| self.next_hash()).collect()
+    }
+}
+
+/// A protocol sink connected in memory to the sinks of other nodes.
+pub struct ChannelSink<D> {
+    index: NodeIndex,
+    peers: Vec<UnboundedSender<D>>,
+    receiver: UnboundedReceiver<D>,
+}
+
+impl<D> ChannelSink<D> {
+    pub fn index(&self) -> NodeIndex {
+        self.index
+    }
+}
+
+/// Creates `node_count` fully connected sinks, the sink at position `i` belongs to the node
+/// with index `i`.
+pub fn network<D: Clone + Send>(node_count: usize) -> Vec<ChannelSink<D>> {
+    let (senders, receivers): (Vec<_>, Vec<_>) =
+        (0..node_count).map(|_| unbounded_channel()).unzip();
+    receivers
+        .into_iter()
+        .enumerate()
+        .map(|(index, receiver)| ChannelSink {
+            index: NodeIndex(index),
+            peers: senders.clone(),
+            receiver,
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl<D: Clone + Send> ProtocolSink<D> for ChannelSink<D> {
+    async fn next(&mut self) -> Option<D> {
+        self.receiver.recv().await
+    }
+
+    fn send(&self, data: D, recipient: Recipient) -> Result<(), NetworkError> {
+        match recipient {
+            Recipient::Everyone => {
+                for (index, peer) in self.peers.iter().enumerate() {
+                    if index != self.index.0 {
+                        peer.send(data.clone())
+                            .map_err(|_| NetworkError::SendFail)?;
+                    }
+                }
+                Ok(())
+            }
+            Recipient::Node(NodeIndex(index)) => self
+                .peers
+                .get(index)
+                .ok_or(NetworkError::SendFail)?
+                .send(data)
+                .map_err(|_| NetworkError::SendFail),
+        }
+    }
+}
+
+/// A protocol sink that returns a scripted sequence of incoming messages and records everything
+/// sent through it.
+pub struct RecordingSink<D> {
+    incoming: VecDeque<D>,
+    sent: Arc<Mutex<Vec<(D, Recipient)>>>,
+    fail_sends: bool,
+}
+
+impl<D> RecordingSink<D> {
+    /// Creates a sink which will return the given messages in order and then report a closed
+    /// network.
+    pub fn new(incoming: Vec<D>) -> Self {
+        RecordingSink {
+            incoming: incoming.into(),
+            sent: Arc::new(Mutex::new(Vec::new())),
+            fail_sends: false,
+        }
+    }
+
+    /// Makes every send through this sink fail.
+    pub fn failing_sends(mut self) -> Self {
+        self.fail_sends = true;
+        self
+    }
+
+    /// A handle to the messages sent so far, usable after the sink has been moved.
+    pub fn sent(&self) -> Arc<Mutex<Vec<(D, Recipient)>>> {
+        self.sent.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl<D: Send> ProtocolSink<D> for RecordingSink<D> {
+    async fn next(&mut self) -> Option<D> {
+        self.incoming.pop_front()
+    }
+
+    fn send(&self, data: D, recipient: Recipient) -> Result<(), NetworkError> {
+        if self.fail_sends {
+            return Err(NetworkError::SendFail);
+        }
+        self.sent
+            .lock()
+            .expect("mutex is never poisoned")
+            .push((data, recipient));
+        Ok(())
+    }
+}
+
+/// A fake signature, valid only for the exact message and signer it was created with.
+#[derive(Hash, PartialEq, Eq, Clone, Encode, Decode, Debug)]
+pub struct MockSignature {
+    message: Vec<u8>,
+    signer: u64,
+}
+
+/// A multikeychain with fake signatures and a configurable completeness threshold.
+#[derive(Clone, Debug)]
+pub struct MockKeychain {
+    index: NodeIndex,
+    node_count: NodeCount,
+    threshold: usize,
+    honest: bool,
+}
+
+impl MockKeychain {
+    /// Creates a keychain for the node with the given index, requiring the usual 2/3 + 1
+    /// signatures for a complete multisignature.
+    pub fn new(index: NodeIndex, node_count: NodeCount) -> Self {
+        MockKeychain {
+            index,
+            node_count,
+            threshold: 2 * node_count.0 / 3 + 1,
+            honest: true,
+        }
+    }
+
+    /// Creates keychains for all the nodes in a committee of the given size.
+    pub fn new_set(node_count: usize) -> Vec<Self> {
+        (0..node_count)
+            .map(|index| MockKeychain::new(NodeIndex(index), NodeCount(node_count)))
+            .collect()
+    }
+
+    /// Sets the number of correct signatures required for a complete multisignature.
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Makes the keychain produce signatures that never verify.
+    pub fn dishonest(mut self) -> Self {
+        self.honest = false;
+        self
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl Index for MockKeychain {
+    fn index(&self) -> NodeIndex {
+        self.index
+    }
+}
+
+impl Keychain for MockKeychain {
+    type Signature = MockSignature;
+
+    fn node_count(&self) -> NodeCount {
+        self.node_count
+    }
+
+    fn sign(&self, msg: &[u8]) -> MockSignature {
+        let message = match self.honest {
+            true => msg.to_vec(),
+            false => Vec::new(),
+        };
+        MockSignature {
+            message,
+            signer: self.index.0 as u64,
+        }
+    }
+
+    fn verify(&self, msg: &[u8], sgn: &MockSignature, index: NodeIndex) -> bool {
+        sgn.message == msg && sgn.signer == index.0 as u64
+    }
+}
+
+impl MultiKeychain for MockKeychain {
+    type PartialMultisignature = SignatureSet<MockSignature>;
+
+    fn bootstrap_multi(
+        &self,
+        signature: &MockSignature,
+        index: NodeIndex,
+    ) -> Self::PartialMultisignature {
+        SignatureSet::with_size(self.node_count).add_signature(signature, index)
+    }
+
+    fn is_complete(&self, msg: &[u8], partial: &Self::PartialMultisignature) -> bool {
+        partial
+            .iter()
+            .filter(|(index, signature)| self.verify(msg, signature, *index))
+            .count()
+            >= self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aleph_bft_rmc::{DoublingDelayScheduler, Handler, MultiKeychain, Service};
+    use aleph_bft_types::{NodeIndex, Recipient};
+    use tokio::{sync::mpsc::unbounded_channel, time::timeout};
+
+    use crate::{
+        mock::{network, HashGenerator, MockKeychain, RecordingSink},
+        HashSignatureAggregator, ProtocolSink, IO,
+    };
+
+    #[test]
+    fn hash_generator_is_deterministic() {
+        let hashes = HashGenerator::new(7).next_hashes(10);
+        assert_eq!(hashes, HashGenerator::new(7).next_hashes(10));
+        for (i, hash) in hashes.iter().enumerate() {
+            assert!(!hashes[i + 1..].contains(hash));
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_sinks_deliver_to_recipients() {
+        let mut sinks = network::<u32>(3);
+        sinks[0].send(7, Recipient::Everyone).unwrap();
+        sinks[1].send(8, Recipient::Node(NodeIndex(2))).unwrap();
+
+        assert_eq!(sinks[1].next().await, Some(7));
+        assert_eq!(sinks[2].next().await, Some(7));
+        assert_eq!(sinks[2].next().await, Some(8));
+        assert!(sinks[1].send(9, Recipient::Node(NodeIndex(3))).is_err());
+    }
+
+    #[tokio::test]
+    async fn recording_sink_records_and_replays() {
+        let mut sink = RecordingSink::new(vec![1u32, 2]);
+        let sent = sink.sent();
+        sink.send(3, Recipient::Everyone).unwrap();
+
+        assert_eq!(sink.next().await, Some(1));
+        assert_eq!(sink.next().await, Some(2));
+        assert_eq!(sink.next().await, None);
+        assert_eq!(*sent.lock().unwrap(), vec![(3, Recipient::Everyone)]);
+        assert!(RecordingSink::<u32>::new(vec![])
+            .failing_sends()
+            .send(3, Recipient::Everyone)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn aggregates_over_in_memory_network() {
+        let node_count = 4;
+        let hash = HashGenerator::default().next_hash();
+        let keychains = MockKeychain::new_set(node_count);
+        let (results_for_test, mut results) = unbounded_channel();
+        for (keychain, sink) in keychains.iter().cloned().zip(network(node_count)) {
+            let results_for_test = results_for_test.clone();
+            tokio::spawn(async move {
+                let scheduler = DoublingDelayScheduler::new(Duration::from_millis(50));
+                let rmc_service = Service::new(scheduler, Handler::new(keychain));
+                let mut io = IO::new(sink, rmc_service, HashSignatureAggregator::new());
+                io.start_aggregation(hash).await;
+                if let Some(result) = io.next_multisigned_hash().await {
+                    results_for_test
+                        .send(result)
+                        .expect("test should be running");
+                }
+                // Keep serving the other nodes.
+                io.next_multisigned_hash().await;
+            });
+        }
+
+        for _ in 0..node_count {
+            let (multisigned_hash, signature) = timeout(Duration::from_secs(5), results.recv())
+                .await
+                .expect("aggregation should finish")
+                .expect("tasks should be running");
+            assert_eq!(multisigned_hash, hash);
+            assert!(keychains[0].is_complete(hash.as_ref(), &signature));
+        }
+    }
+}
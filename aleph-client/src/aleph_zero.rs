@@ -9412,7 +9412,11 @@ pub mod api {
                 #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
                 #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
                 pub struct SetEmergencyFinalizer {
-                    pub emergency_finalizer: runtime_types::primitives::app::Public,
+                    pub emergency_finalizers:
+                        runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                            runtime_types::primitives::app::Public,
+                        >,
+                    pub threshold: ::core::primitive::u32,
                 }
                 impl ::subxt::blocks::StaticExtrinsic for SetEmergencyFinalizer {
                     const PALLET: &'static str = "Aleph";
@@ -9489,13 +9493,15 @@ pub mod api {
                 #[doc = "See [`Pallet::set_emergency_finalizer`]."]
                 pub fn set_emergency_finalizer(
                     &self,
-                    emergency_finalizer: runtime_types::primitives::app::Public,
+                    emergency_finalizers: runtime_types::bounded_collections::bounded_vec::BoundedVec<runtime_types::primitives::app::Public>,
+                    threshold: ::core::primitive::u32,
                 ) -> ::subxt::tx::Payload<types::SetEmergencyFinalizer> {
                     ::subxt::tx::Payload::new_static(
                         "Aleph",
                         "set_emergency_finalizer",
                         types::SetEmergencyFinalizer {
-                            emergency_finalizer,
+                            emergency_finalizers,
+                            threshold,
                         },
                         [
                             38u8, 226u8, 0u8, 105u8, 84u8, 48u8, 81u8, 59u8, 52u8, 230u8, 94u8,
@@ -9583,7 +9589,11 @@ pub mod api {
             # [codec (crate = :: subxt :: ext :: codec)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
-            pub struct ChangeEmergencyFinalizer(pub runtime_types::primitives::app::Public);
+            pub struct ChangeEmergencyFinalizer(
+                pub  runtime_types::primitives::EmergencyFinalizerSet<
+                    runtime_types::primitives::app::Public,
+                >,
+            );
             impl ::subxt::events::StaticEvent for ChangeEmergencyFinalizer {
                 const PALLET: &'static str = "Aleph";
                 const EVENT: &'static str = "ChangeEmergencyFinalizer";
@@ -9761,7 +9771,9 @@ pub mod api {
                     &self,
                 ) -> ::subxt::storage::address::Address<
                     ::subxt::storage::address::StaticStorageMapKey,
-                    runtime_types::primitives::app::Public,
+                    runtime_types::primitives::EmergencyFinalizerSet<
+                        runtime_types::primitives::app::Public,
+                    >,
                     ::subxt::storage::address::Yes,
                     (),
                     (),
@@ -9782,7 +9794,9 @@ pub mod api {
                     &self,
                 ) -> ::subxt::storage::address::Address<
                     ::subxt::storage::address::StaticStorageMapKey,
-                    runtime_types::primitives::app::Public,
+                    runtime_types::primitives::EmergencyFinalizerSet<
+                        runtime_types::primitives::app::Public,
+                    >,
                     ::subxt::storage::address::Yes,
                     (),
                     (),
@@ -9802,7 +9816,9 @@ pub mod api {
                     &self,
                 ) -> ::subxt::storage::address::Address<
                     ::subxt::storage::address::StaticStorageMapKey,
-                    runtime_types::primitives::app::Public,
+                    runtime_types::primitives::EmergencyFinalizerSet<
+                        runtime_types::primitives::app::Public,
+                    >,
                     ::subxt::storage::address::Yes,
                     (),
                     (),
@@ -9990,6 +10006,42 @@ pub mod api {
                     const PALLET: &'static str = "Elections";
                     const CALL: &'static str = "set_elections_openness";
                 }
+                #[derive(
+                    :: subxt :: ext :: codec :: Decode,
+                    :: subxt :: ext :: codec :: Encode,
+                    :: subxt :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: scale_encode :: EncodeAsType,
+                    Clone,
+                    Debug,
+                    Eq,
+                    PartialEq,
+                )]
+                # [codec (crate = :: subxt :: ext :: codec)]
+                #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+                #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+                pub struct Chill;
+                impl ::subxt::blocks::StaticExtrinsic for Chill {
+                    const PALLET: &'static str = "Elections";
+                    const CALL: &'static str = "chill";
+                }
+                #[derive(
+                    :: subxt :: ext :: codec :: Decode,
+                    :: subxt :: ext :: codec :: Encode,
+                    :: subxt :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: scale_encode :: EncodeAsType,
+                    Clone,
+                    Debug,
+                    Eq,
+                    PartialEq,
+                )]
+                # [codec (crate = :: subxt :: ext :: codec)]
+                #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+                #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+                pub struct EnqueueCandidate;
+                impl ::subxt::blocks::StaticExtrinsic for EnqueueCandidate {
+                    const PALLET: &'static str = "Elections";
+                    const CALL: &'static str = "enqueue_candidate";
+                }
             }
             pub struct TransactionApi;
             impl TransactionApi {
@@ -10042,6 +10094,18 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = "See [`Pallet::chill`]."]
+                pub fn chill(&self) -> ::subxt::tx::Payload<types::Chill> {
+                    ::subxt::tx::Payload::new("Elections", "chill", types::Chill {})
+                }
+                #[doc = "See [`Pallet::enqueue_candidate`]."]
+                pub fn enqueue_candidate(&self) -> ::subxt::tx::Payload<types::EnqueueCandidate> {
+                    ::subxt::tx::Payload::new(
+                        "Elections",
+                        "enqueue_candidate",
+                        types::EnqueueCandidate {},
+                    )
+                }
             }
         }
         #[doc = "The `Event` enum of this pallet"]
@@ -10075,6 +10139,25 @@ pub mod api {
                 const PALLET: &'static str = "Elections";
                 const EVENT: &'static str = "ChangeValidators";
             }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Elections mode has changed, it is used from the next elections onwards"]
+            pub struct ChangeElectionMode(pub runtime_types::primitives::ElectionMode);
+            impl ::subxt::events::StaticEvent for ChangeElectionMode {
+                const PALLET: &'static str = "Elections";
+                const EVENT: &'static str = "ChangeElectionMode";
+            }
         }
         pub mod storage {
             use super::runtime_types;
@@ -18622,6 +18705,29 @@ pub mod api {
             # [codec (crate = :: subxt :: ext :: codec)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Validators' bans have expired or have been cancelled"]
+            pub struct UnbanValidators(
+                pub  ::std::vec::Vec<
+                    ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                >,
+            );
+            impl ::subxt::events::StaticEvent for UnbanValidators {
+                const PALLET: &'static str = "CommitteeManagement";
+                const EVENT: &'static str = "UnbanValidators";
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
             #[doc = "Validator is underperforimg in finality committee"]
             pub struct ValidatorUnderperforming(
                 pub ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
@@ -18630,6 +18736,95 @@ pub mod api {
                 const PALLET: &'static str = "CommitteeManagement";
                 const EVENT: &'static str = "ValidatorUnderperforming";
             }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Block production performance threshold of a validator has been overridden, or the"]
+            #[doc = "override has been removed if `None`"]
+            pub struct SetValidatorMinimalExpectedPerformance(
+                pub ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                pub ::core::option::Option<runtime_types::sp_arithmetic::per_things::Perbill>,
+            );
+            impl ::subxt::events::StaticEvent for SetValidatorMinimalExpectedPerformance {
+                const PALLET: &'static str = "CommitteeManagement";
+                const EVENT: &'static str = "SetValidatorMinimalExpectedPerformance";
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Validator underperformed in block production in a session, with the number of such"]
+            #[doc = "sessions counted so far"]
+            pub struct ValidatorUnderperformedSession(
+                pub ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                pub ::core::primitive::u32,
+            );
+            impl ::subxt::events::StaticEvent for ValidatorUnderperformedSession {
+                const PALLET: &'static str = "CommitteeManagement";
+                const EVENT: &'static str = "ValidatorUnderperformedSession";
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Underperformance counters of a validator have been cleared"]
+            pub struct UnderperformanceCountersCleared(
+                pub ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+            );
+            impl ::subxt::events::StaticEvent for UnderperformanceCountersCleared {
+                const PALLET: &'static str = "CommitteeManagement";
+                const EVENT: &'static str = "UnderperformanceCountersCleared";
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Ban of a validator has been cancelled on appeal before taking effect"]
+            pub struct BanAppealed(
+                pub ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                pub runtime_types::primitives::BanInfo,
+            );
+            impl ::subxt::events::StaticEvent for BanAppealed {
+                const PALLET: &'static str = "CommitteeManagement";
+                const EVENT: &'static str = "BanAppealed";
+            }
         }
         pub mod storage {
             use super::runtime_types;
@@ -20774,6 +20969,68 @@ pub mod api {
                     const PALLET: &'static str = "Operations";
                     const CALL: &'static str = "fix_accounts_consumers_counter";
                 }
+                #[derive(
+                    :: subxt :: ext :: codec :: Decode,
+                    :: subxt :: ext :: codec :: Encode,
+                    :: subxt :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: scale_encode :: EncodeAsType,
+                    Clone,
+                    Debug,
+                    Eq,
+                    PartialEq,
+                )]
+                # [codec (crate = :: subxt :: ext :: codec)]
+                #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+                #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+                pub struct RemoveOrphanedNextKeys {
+                    pub who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                }
+                impl ::subxt::blocks::StaticExtrinsic for RemoveOrphanedNextKeys {
+                    const PALLET: &'static str = "Operations";
+                    const CALL: &'static str = "remove_orphaned_next_keys";
+                }
+                #[derive(
+                    :: subxt :: ext :: codec :: Decode,
+                    :: subxt :: ext :: codec :: Encode,
+                    :: subxt :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: scale_encode :: EncodeAsType,
+                    Clone,
+                    Debug,
+                    Eq,
+                    PartialEq,
+                )]
+                # [codec (crate = :: subxt :: ext :: codec)]
+                #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+                #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+                pub struct FixAccountsConsumersOverflow {
+                    pub who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                }
+                impl ::subxt::blocks::StaticExtrinsic for FixAccountsConsumersOverflow {
+                    const PALLET: &'static str = "Operations";
+                    const CALL: &'static str = "fix_accounts_consumers_overflow";
+                }
+                #[derive(
+                    :: subxt :: ext :: codec :: Decode,
+                    :: subxt :: ext :: codec :: Encode,
+                    :: subxt :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: scale_encode :: EncodeAsType,
+                    Clone,
+                    Debug,
+                    Eq,
+                    PartialEq,
+                )]
+                # [codec (crate = :: subxt :: ext :: codec)]
+                #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+                #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+                pub struct FixAccountsConsumersCounterBatch {
+                    pub accounts: ::std::vec::Vec<
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    >,
+                }
+                impl ::subxt::blocks::StaticExtrinsic for FixAccountsConsumersCounterBatch {
+                    const PALLET: &'static str = "Operations";
+                    const CALL: &'static str = "fix_accounts_consumers_counter_batch";
+                }
             }
             pub struct TransactionApi;
             impl TransactionApi {
@@ -20794,6 +21051,41 @@ pub mod api {
                         ],
                     )
                 }
+                #[doc = "See [`Pallet::remove_orphaned_next_keys`]."]
+                pub fn remove_orphaned_next_keys(
+                    &self,
+                    who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                ) -> ::subxt::tx::Payload<types::RemoveOrphanedNextKeys> {
+                    ::subxt::tx::Payload::new(
+                        "Operations",
+                        "remove_orphaned_next_keys",
+                        types::RemoveOrphanedNextKeys { who },
+                    )
+                }
+                #[doc = "See [`Pallet::fix_accounts_consumers_overflow`]."]
+                pub fn fix_accounts_consumers_overflow(
+                    &self,
+                    who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                ) -> ::subxt::tx::Payload<types::FixAccountsConsumersOverflow> {
+                    ::subxt::tx::Payload::new(
+                        "Operations",
+                        "fix_accounts_consumers_overflow",
+                        types::FixAccountsConsumersOverflow { who },
+                    )
+                }
+                #[doc = "See [`Pallet::fix_accounts_consumers_counter_batch`]."]
+                pub fn fix_accounts_consumers_counter_batch(
+                    &self,
+                    accounts: ::std::vec::Vec<
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    >,
+                ) -> ::subxt::tx::Payload<types::FixAccountsConsumersCounterBatch> {
+                    ::subxt::tx::Payload::new(
+                        "Operations",
+                        "fix_accounts_consumers_counter_batch",
+                        types::FixAccountsConsumersCounterBatch { accounts },
+                    )
+                }
             }
         }
         #[doc = "The `Event` enum of this pallet"]
@@ -20842,6 +21134,71 @@ pub mod api {
                 const PALLET: &'static str = "Operations";
                 const EVENT: &'static str = "ConsumersCounterDecremented";
             }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Next session keys of an account that is not bonded were removed"]
+            pub struct OrphanedNextKeysRemoved {
+                pub who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+            }
+            impl ::subxt::events::StaticEvent for OrphanedNextKeysRemoved {
+                const PALLET: &'static str = "Operations";
+                const EVENT: &'static str = "OrphanedNextKeysRemoved";
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "A consumers counter of an account was decremented by `delta` to fix its overflow"]
+            pub struct ConsumersCounterOverflowFixed {
+                pub who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                pub delta: ::core::primitive::u32,
+            }
+            impl ::subxt::events::StaticEvent for ConsumersCounterOverflowFixed {
+                const PALLET: &'static str = "Operations";
+                const EVENT: &'static str = "ConsumersCounterOverflowFixed";
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            #[doc = "Fixing a consumers counter of an account in a batch failed"]
+            pub struct ConsumersCounterFixFailed {
+                pub who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                pub error: runtime_types::sp_runtime::DispatchError,
+            }
+            impl ::subxt::events::StaticEvent for ConsumersCounterFixFailed {
+                const PALLET: &'static str = "Operations";
+                const EVENT: &'static str = "ConsumersCounterFixFailed";
+            }
         }
     }
     pub mod runtime_types {
@@ -21015,6 +21372,8 @@ pub mod api {
                 SafeMode(runtime_types::pallet_safe_mode::pallet::Error),
                 #[codec(index = 24)]
                 TxPause(runtime_types::pallet_tx_pause::pallet::Error),
+                #[codec(index = 255)]
+                Operations(runtime_types::pallet_operations::pallet::Error),
             }
             #[derive(
                 :: subxt :: ext :: codec :: Decode,
@@ -21815,7 +22174,11 @@ pub mod api {
                     #[codec(index = 0)]
                     #[doc = "See [`Pallet::set_emergency_finalizer`]."]
                     set_emergency_finalizer {
-                        emergency_finalizer: runtime_types::primitives::app::Public,
+                        emergency_finalizers:
+                            runtime_types::bounded_collections::bounded_vec::BoundedVec<
+                                runtime_types::primitives::app::Public,
+                            >,
+                        threshold: ::core::primitive::u32,
                     },
                     #[codec(index = 1)]
                     #[doc = "See [`Pallet::schedule_finality_version_change`]."]
@@ -21854,7 +22217,11 @@ pub mod api {
                 #[doc = "The `Event` enum of this pallet"]
                 pub enum Event {
                     #[codec(index = 0)]
-                    ChangeEmergencyFinalizer(runtime_types::primitives::app::Public),
+                    ChangeEmergencyFinalizer(
+                        runtime_types::primitives::EmergencyFinalizerSet<
+                            runtime_types::primitives::app::Public,
+                        >,
+                    ),
                     #[codec(index = 1)]
                     ScheduleFinalityVersionChange(runtime_types::primitives::VersionChange),
                     #[codec(index = 2)]
@@ -22305,6 +22672,24 @@ pub mod api {
                             ::core::option::Option<::core::primitive::u32>,
                         ban_period: ::core::option::Option<::core::primitive::u32>,
                     },
+                    #[codec(index = 6)]
+                    #[doc = "See [`Pallet::set_validator_minimal_expected_performance`]."]
+                    set_validator_minimal_expected_performance {
+                        validator:
+                            ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        minimal_expected_performance: ::core::option::Option<::core::primitive::u8>,
+                    },
+                    #[codec(index = 7)]
+                    #[doc = "See [`Pallet::clear_underperformance_counters`]."]
+                    clear_underperformance_counters {
+                        validator:
+                            ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    },
+                    #[codec(index = 8)]
+                    #[doc = "See [`Pallet::appeal_ban`]."]
+                    appeal_ban {
+                        banned: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    },
                 }
                 #[derive(
                     :: subxt :: ext :: codec :: Decode,
@@ -22334,6 +22719,12 @@ pub mod api {
                     #[codec(index = 2)]
                     #[doc = "Lenient threshold not in [0-100] range"]
                     InvalidLenientThreshold,
+                    #[codec(index = 3)]
+                    #[doc = "Validator is not banned, or its ban has already taken effect"]
+                    NoPendingBan,
+                    #[codec(index = 4)]
+                    #[doc = "Minimal expected performance has to be at most 100 percent"]
+                    InvalidPerformanceThreshold,
                 }
                 #[derive(
                     :: subxt :: ext :: codec :: Decode,
@@ -22365,10 +22756,42 @@ pub mod api {
                         )>,
                     ),
                     #[codec(index = 3)]
+                    #[doc = "Validators' bans have expired or have been cancelled"]
+                    UnbanValidators(
+                        ::std::vec::Vec<
+                            ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        >,
+                    ),
+                    #[codec(index = 4)]
                     #[doc = "Validator is underperforimg in finality committee"]
                     ValidatorUnderperforming(
                         ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
                     ),
+                    #[codec(index = 5)]
+                    #[doc = "Block production performance threshold of a validator has been overridden, or the"]
+                    #[doc = "override has been removed if `None`"]
+                    SetValidatorMinimalExpectedPerformance(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        ::core::option::Option<runtime_types::sp_arithmetic::per_things::Perbill>,
+                    ),
+                    #[codec(index = 6)]
+                    #[doc = "Validator underperformed in block production in a session, with the number of such"]
+                    #[doc = "sessions counted so far"]
+                    ValidatorUnderperformedSession(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        ::core::primitive::u32,
+                    ),
+                    #[codec(index = 7)]
+                    #[doc = "Underperformance counters of a validator have been cleared"]
+                    UnderperformanceCountersCleared(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    ),
+                    #[codec(index = 8)]
+                    #[doc = "Ban of a validator has been cancelled on appeal before taking effect"]
+                    BanAppealed(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        runtime_types::primitives::BanInfo,
+                    ),
                 }
             }
             #[derive(
@@ -23304,6 +23727,29 @@ pub mod api {
                     set_elections_openness {
                         openness: runtime_types::primitives::ElectionOpenness,
                     },
+                    #[codec(index = 5)]
+                    #[doc = "See [`Pallet::set_election_mode`]."]
+                    set_election_mode {
+                        mode: runtime_types::primitives::ElectionMode,
+                    },
+                    #[codec(index = 6)]
+                    #[doc = "See [`Pallet::chill`]."]
+                    chill,
+                    #[codec(index = 7)]
+                    #[doc = "See [`Pallet::enqueue_candidate`]."]
+                    enqueue_candidate,
+                    #[codec(index = 8)]
+                    #[doc = "See [`Pallet::approve_candidate`]."]
+                    approve_candidate {
+                        candidate:
+                            ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    },
+                    #[codec(index = 9)]
+                    #[doc = "See [`Pallet::deny_candidate`]."]
+                    deny_candidate {
+                        candidate:
+                            ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    },
                 }
                 #[derive(
                     :: subxt :: ext :: codec :: Decode,
@@ -23330,6 +23776,24 @@ pub mod api {
                     NonUniqueListOfValidators,
                     #[codec(index = 4)]
                     NonReservedFinalitySeatsLargerThanNonReservedSeats,
+                    #[codec(index = 5)]
+                    NotNonReservedValidator,
+                    #[codec(index = 6)]
+                    AlreadyValidator,
+                    #[codec(index = 7)]
+                    AlreadyCandidate,
+                    #[codec(index = 8)]
+                    NotCandidate,
+                    #[codec(index = 9)]
+                    InsufficientCandidateBond,
+                    #[codec(index = 10)]
+                    CandidateSessionKeysNotSet,
+                    #[codec(index = 11)]
+                    TooManyValidators,
+                    #[codec(index = 12)]
+                    TooManyCandidates,
+                    #[codec(index = 13)]
+                    ChillInPermissionlessElections,
                 }
                 #[derive(
                     :: subxt :: ext :: codec :: Decode,
@@ -23357,6 +23821,33 @@ pub mod api {
                         >,
                         runtime_types::primitives::CommitteeSeats,
                     ),
+                    #[codec(index = 1)]
+                    #[doc = "Elections mode has changed, it is used from the next elections onwards"]
+                    ChangeElectionMode(runtime_types::primitives::ElectionMode),
+                    #[codec(index = 2)]
+                    #[doc = "Non reserved validator has removed itself from the next era's validators"]
+                    ValidatorChilled(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    ),
+                    #[codec(index = 3)]
+                    #[doc = "Account has enqueued itself as a candidate for a non reserved validator"]
+                    CandidateEnqueued(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    ),
+                    #[codec(index = 4)]
+                    #[doc = "Candidate has been approved and added to the next era's non reserved validators"]
+                    CandidateApproved(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    ),
+                    #[codec(index = 5)]
+                    #[doc = "Candidate has been denied and removed from the queue"]
+                    CandidateDenied(
+                        ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    ),
+                    #[codec(index = 6)]
+                    #[doc = "Number of eligible non reserved candidates left out of the elections, as there were"]
+                    #[doc = "more of them than [`Config::MaxValidators`]"]
+                    NonReservedCandidatesDropped(::core::primitive::u32),
                 }
             }
         }
@@ -25050,6 +25541,49 @@ pub mod api {
                     fix_accounts_consumers_counter {
                         who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
                     },
+                    #[codec(index = 1)]
+                    #[doc = "See [`Pallet::remove_orphaned_next_keys`]."]
+                    remove_orphaned_next_keys {
+                        who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    },
+                    #[codec(index = 2)]
+                    #[doc = "See [`Pallet::fix_accounts_consumers_overflow`]."]
+                    fix_accounts_consumers_overflow {
+                        who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    },
+                    #[codec(index = 3)]
+                    #[doc = "See [`Pallet::fix_accounts_consumers_counter_batch`]."]
+                    fix_accounts_consumers_counter_batch {
+                        accounts: ::std::vec::Vec<
+                            ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        >,
+                    },
+                }
+                #[derive(
+                    :: subxt :: ext :: codec :: Decode,
+                    :: subxt :: ext :: codec :: Encode,
+                    :: subxt :: ext :: scale_decode :: DecodeAsType,
+                    :: subxt :: ext :: scale_encode :: EncodeAsType,
+                    Clone,
+                    Debug,
+                    Eq,
+                    PartialEq,
+                )]
+                # [codec (crate = :: subxt :: ext :: codec)]
+                #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+                #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+                #[doc = "The `Error` enum of this pallet."]
+                pub enum Error {
+                    #[codec(index = 0)]
+                    #[doc = "Account has no next session keys, or is bonded so its keys can be purged in pallet"]
+                    #[doc = "session"]
+                    NotOrphanedNextKeys,
+                    #[codec(index = 1)]
+                    #[doc = "Account's consumers counter is not greater than the expected one"]
+                    NoConsumersOverflow,
+                    #[codec(index = 2)]
+                    #[doc = "More than [`MAX_ACCOUNTS_IN_BATCH`] accounts were given"]
+                    TooManyAccounts,
                 }
                 #[derive(
                     :: subxt :: ext :: codec :: Decode,
@@ -25076,6 +25610,23 @@ pub mod api {
                     ConsumersCounterDecremented {
                         who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
                     },
+                    #[codec(index = 2)]
+                    #[doc = "Next session keys of an account that is not bonded were removed"]
+                    OrphanedNextKeysRemoved {
+                        who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                    },
+                    #[codec(index = 3)]
+                    #[doc = "A consumers counter of an account was decremented by `delta` to fix its overflow"]
+                    ConsumersCounterOverflowFixed {
+                        who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        delta: ::core::primitive::u32,
+                    },
+                    #[codec(index = 4)]
+                    #[doc = "Fixing a consumers counter of an account in a batch failed"]
+                    ConsumersCounterFixFailed {
+                        who: ::subxt::utils::Static<::subxt::ext::sp_core::crypto::AccountId32>,
+                        error: runtime_types::sp_runtime::DispatchError,
+                    },
                 }
             }
         }
@@ -27636,6 +28187,25 @@ pub mod api {
             # [codec (crate = :: subxt :: ext :: codec)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            pub enum ElectionMode {
+                #[codec(index = 0)]
+                ProofOfAuthority,
+                #[codec(index = 1)]
+                DelegatedProofOfStake,
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
             pub struct EraValidators<_0> {
                 pub reserved: ::std::vec::Vec<_0>,
                 pub non_reserved: ::std::vec::Vec<_0>,
@@ -27653,6 +28223,23 @@ pub mod api {
             # [codec (crate = :: subxt :: ext :: codec)]
             #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
             #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
+            pub struct EmergencyFinalizerSet<_0> {
+                pub keys: runtime_types::bounded_collections::bounded_vec::BoundedVec<_0>,
+                pub threshold: ::core::primitive::u32,
+            }
+            #[derive(
+                :: subxt :: ext :: codec :: Decode,
+                :: subxt :: ext :: codec :: Encode,
+                :: subxt :: ext :: scale_decode :: DecodeAsType,
+                :: subxt :: ext :: scale_encode :: EncodeAsType,
+                Clone,
+                Debug,
+                Eq,
+                PartialEq,
+            )]
+            # [codec (crate = :: subxt :: ext :: codec)]
+            #[decode_as_type(crate_path = ":: subxt :: ext :: scale_decode")]
+            #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
             pub struct FinalityBanConfig {
                 pub minimal_expected_performance: ::core::primitive::u16,
                 pub underperformed_session_count_threshold: ::core::primitive::u32,
@@ -27710,8 +28297,11 @@ pub mod api {
             #[encode_as_type(crate_path = ":: subxt :: ext :: scale_encode")]
             pub struct SessionAuthorityData {
                 pub authorities: ::std::vec::Vec<runtime_types::primitives::app::Public>,
-                pub emergency_finalizer:
-                    ::core::option::Option<runtime_types::primitives::app::Public>,
+                pub emergency_finalizers: ::core::option::Option<
+                    runtime_types::primitives::EmergencyFinalizerSet<
+                        runtime_types::primitives::app::Public,
+                    >,
+                >,
             }
             #[derive(
                 :: subxt :: ext :: codec :: Decode,
@@ -4,6 +4,7 @@ use subxt::rpc_params;
 use crate::{
     api,
     api::runtime_types::{
+        bounded_collections::bounded_vec::BoundedVec,
         pallet_aleph::pallet::Call::set_emergency_finalizer, primitives::app::Public,
         sp_core::ed25519::Public as EdPublic,
     },
@@ -23,21 +24,24 @@ pub trait AlephApi {
     async fn finality_version(&self, at: Option<BlockHash>) -> Version;
     /// Gets the finality version for the next session.
     async fn next_session_finality_version(&self, at: Option<BlockHash>) -> Version;
-    /// Gets the emergency finalizer
-    async fn emergency_finalizer(&self, at: Option<BlockHash>) -> Option<[u8; 32]>;
+    /// Gets the emergency finalizer keys together with the number of them required to finalize
+    /// a block.
+    async fn emergency_finalizer(&self, at: Option<BlockHash>) -> Option<(Vec<[u8; 32]>, u32)>;
 }
 
 /// Pallet aleph API that requires sudo.
 #[async_trait::async_trait]
 pub trait AlephSudoApi {
-    /// Sets the emergency finalization key.
-    /// * `finalizer` - a new finalizer key
+    /// Sets the emergency finalization keys.
+    /// * `finalizers` - new finalizer keys
+    /// * `threshold` - how many of the keys have to sign a block to finalize it
     /// * `status` - a [`TxStatus`] of a tx to wait for
     /// # Returns
     /// Block hash of block where transaction was put or error
     async fn set_emergency_finalizer(
         &self,
-        finalizer: AccountId,
+        finalizers: Vec<AccountId>,
+        threshold: u32,
         status: TxStatus,
     ) -> anyhow::Result<TxInfo>;
 
@@ -85,12 +89,15 @@ impl<C: ConnectionApi> AlephApi for C {
         self.rpc_call(method.to_string(), params).await.unwrap()
     }
 
-    async fn emergency_finalizer(&self, at: Option<BlockHash>) -> Option<[u8; 32]> {
+    async fn emergency_finalizer(&self, at: Option<BlockHash>) -> Option<(Vec<[u8; 32]>, u32)> {
         let addrs = api::storage().aleph().emergency_finalizer();
 
-        self.get_storage_entry_maybe(&addrs, at)
-            .await
-            .map(|public| public.0 .0)
+        self.get_storage_entry_maybe(&addrs, at).await.map(|set| {
+            (
+                set.keys.into_iter().map(|public| public.0 .0).collect(),
+                set.threshold,
+            )
+        })
     }
 }
 
@@ -98,11 +105,18 @@ impl<C: ConnectionApi> AlephApi for C {
 impl AlephSudoApi for RootConnection {
     async fn set_emergency_finalizer(
         &self,
-        finalizer: AccountId,
+        finalizers: Vec<AccountId>,
+        threshold: u32,
         status: TxStatus,
     ) -> anyhow::Result<TxInfo> {
         let call = Aleph(set_emergency_finalizer {
-            emergency_finalizer: Public(EdPublic(finalizer.into())),
+            emergency_finalizers: BoundedVec(
+                finalizers
+                    .into_iter()
+                    .map(|finalizer| Public(EdPublic(finalizer.into())))
+                    .collect(),
+            ),
+            threshold,
         });
         self.sudo_unchecked(call, status).await
     }
@@ -10,9 +10,10 @@ use crate::{
     aleph_runtime::RuntimeCall::CommitteeManagement,
     api,
     pallet_committee_management::pallet::Call::{
-        ban_from_committee, set_ban_config, set_lenient_threshold,
+        appeal_ban, ban_from_committee, clear_underperformance_counters, set_ban_config,
+        set_lenient_threshold, set_validator_minimal_expected_performance,
     },
-    primitives::{ProductionBanConfig, BanInfo, BanReason},
+    primitives::{BanInfo, BanReason, ProductionBanConfig},
     AccountId, AsConnection, BlockHash, ConnectionApi, EraIndex, RootConnection, SessionCount,
     SessionIndex, SudoCall, TxInfo, TxStatus,
 };
@@ -111,12 +112,40 @@ pub trait CommitteeManagementSudoApi {
         threshold_percent: u8,
         status: TxStatus,
     ) -> anyhow::Result<TxInfo>;
+
+    /// Overrides the minimal expected block production performance of a single validator, or
+    /// removes the override with `None`. It has an immediate effect.
+    /// * `validator` - a validator stash account id
+    /// * `minimal_expected_performance` - performance ratio threshold in a session, in percents
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn set_validator_minimal_expected_performance(
+        &self,
+        validator: AccountId,
+        minimal_expected_performance: Option<u8>,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// Clears block production and finality underperformance counters of a validator.
+    /// * `validator` - a validator stash account id
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn clear_underperformance_counters(
+        &self,
+        validator: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// Cancels a ban of a validator that has not taken effect yet.
+    /// * `banned` - a validator stash account id
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn appeal_ban(&self, banned: AccountId, status: TxStatus) -> anyhow::Result<TxInfo>;
 }
 
 #[async_trait::async_trait]
 impl<C: ConnectionApi + AsConnection> CommitteeManagementApi for C {
     async fn get_ban_config(&self, at: Option<BlockHash>) -> ProductionBanConfig {
-        let addrs = api::storage().committee_management().production_ban_config();
+        let addrs = api::storage()
+            .committee_management()
+            .production_ban_config();
 
         self.get_storage_entry(&addrs, at).await
     }
@@ -243,4 +272,38 @@ impl CommitteeManagementSudoApi for RootConnection {
 
         self.sudo_unchecked(call, status).await
     }
+
+    async fn set_validator_minimal_expected_performance(
+        &self,
+        validator: AccountId,
+        minimal_expected_performance: Option<u8>,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let call = CommitteeManagement(set_validator_minimal_expected_performance {
+            validator: validator.into(),
+            minimal_expected_performance,
+        });
+
+        self.sudo_unchecked(call, status).await
+    }
+
+    async fn clear_underperformance_counters(
+        &self,
+        validator: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let call = CommitteeManagement(clear_underperformance_counters {
+            validator: validator.into(),
+        });
+
+        self.sudo_unchecked(call, status).await
+    }
+
+    async fn appeal_ban(&self, banned: AccountId, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let call = CommitteeManagement(appeal_ban {
+            banned: banned.into(),
+        });
+
+        self.sudo_unchecked(call, status).await
+    }
 }
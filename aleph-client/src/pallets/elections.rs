@@ -4,11 +4,14 @@ use crate::{
     api,
     api::runtime_types::primitives::{CommitteeSeats, EraValidators},
     connections::{AsConnection, TxInfo},
-    pallet_elections::pallet::Call::{change_validators, set_elections_openness},
-    primitives::ElectionOpenness,
+    pallet_elections::pallet::Call::{
+        approve_candidate, change_validators, deny_candidate, set_election_mode,
+        set_elections_openness,
+    },
+    primitives::{ElectionMode, ElectionOpenness},
     AccountId, BlockHash,
     Call::Elections,
-    ConnectionApi, RootConnection, SudoCall, TxStatus,
+    ConnectionApi, RootConnection, SignedConnectionApi, SudoCall, TxStatus,
 };
 
 // TODO once pallet elections docs are published, replace api docs with links to public docs
@@ -36,6 +39,20 @@ pub trait ElectionsApi {
     async fn get_next_era_non_reserved_validators(&self, at: Option<BlockHash>) -> Vec<AccountId>;
 }
 
+/// Pallet elections api for validators.
+#[async_trait::async_trait]
+pub trait ElectionsUserApi {
+    /// Issues `elections.chill` that removes the signer from the next era's non reserved
+    /// validators. Not available in the permissionless elections.
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn chill(&self, status: TxStatus) -> anyhow::Result<TxInfo>;
+
+    /// Issues `elections.enqueue_candidate` that enqueues the signer as a candidate for a non
+    /// reserved validator. The signer has to bond enough and set its session keys first.
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn enqueue_candidate(&self, status: TxStatus) -> anyhow::Result<TxInfo>;
+}
+
 /// any object that implements pallet elections api that requires sudo
 #[async_trait::async_trait]
 pub trait ElectionsSudoApi {
@@ -60,6 +77,33 @@ pub trait ElectionsSudoApi {
         mode: ElectionOpenness,
         status: TxStatus,
     ) -> anyhow::Result<TxInfo>;
+
+    /// Set mode of the elections, used from the next elections onwards.
+    /// * `mode` - new elections mode
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn set_election_mode(
+        &self,
+        mode: ElectionMode,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// Approves a queued candidate, adding it to the next era's non reserved validators.
+    /// * `candidate` - a candidate account id
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn approve_candidate(
+        &self,
+        candidate: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// Denies a queued candidate, removing it from the queue.
+    /// * `candidate` - a candidate account id
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn deny_candidate(
+        &self,
+        candidate: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
 }
 
 #[async_trait::async_trait]
@@ -116,6 +160,21 @@ impl<C: ConnectionApi + AsConnection> ElectionsApi for C {
     }
 }
 
+#[async_trait::async_trait]
+impl<S: SignedConnectionApi> ElectionsUserApi for S {
+    async fn chill(&self, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().elections().chill();
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn enqueue_candidate(&self, status: TxStatus) -> anyhow::Result<TxInfo> {
+        let tx = api::tx().elections().enqueue_candidate();
+
+        self.send_tx(tx, status).await
+    }
+}
+
 #[async_trait::async_trait]
 impl ElectionsSudoApi for RootConnection {
     async fn change_validators(
@@ -145,4 +204,38 @@ impl ElectionsSudoApi for RootConnection {
 
         self.sudo_unchecked(call, status).await
     }
+
+    async fn set_election_mode(
+        &self,
+        mode: ElectionMode,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let call = Elections(set_election_mode { mode });
+
+        self.sudo_unchecked(call, status).await
+    }
+
+    async fn approve_candidate(
+        &self,
+        candidate: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let call = Elections(approve_candidate {
+            candidate: Static(candidate),
+        });
+
+        self.sudo_unchecked(call, status).await
+    }
+
+    async fn deny_candidate(
+        &self,
+        candidate: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let call = Elections(deny_candidate {
+            candidate: Static(candidate),
+        });
+
+        self.sudo_unchecked(call, status).await
+    }
 }
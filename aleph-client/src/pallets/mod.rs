@@ -15,6 +15,8 @@ pub mod elections;
 pub mod fee;
 /// Pallet multisig API
 pub mod multisig;
+/// Pallet operations API
+pub mod operations;
 /// Pallet Proxy API
 pub mod proxy;
 /// Pallet safe-mode API
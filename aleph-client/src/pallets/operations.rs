@@ -0,0 +1,94 @@
+use subxt::utils::Static;
+
+use crate::{api, AccountId, SignedConnectionApi, TxInfo, TxStatus};
+
+/// Pallet operations api.
+#[async_trait::async_trait]
+pub trait OperationsApi {
+    /// API for [`fix_accounts_consumers_counter`](https://github.com/Cardinal-Cryptography/aleph-node/blob/main/pallets/operations/src/lib.rs) call.
+    /// * `who` - an account to be fixed
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn fix_accounts_consumers_counter(
+        &self,
+        who: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`remove_orphaned_next_keys`](https://github.com/Cardinal-Cryptography/aleph-node/blob/main/pallets/operations/src/lib.rs) call.
+    /// * `who` - an account that is not bonded, with next session keys left
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn remove_orphaned_next_keys(
+        &self,
+        who: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`fix_accounts_consumers_overflow`](https://github.com/Cardinal-Cryptography/aleph-node/blob/main/pallets/operations/src/lib.rs) call.
+    /// * `who` - an account with a consumers counter higher than expected
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn fix_accounts_consumers_overflow(
+        &self,
+        who: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+
+    /// API for [`fix_accounts_consumers_counter_batch`](https://github.com/Cardinal-Cryptography/aleph-node/blob/main/pallets/operations/src/lib.rs) call.
+    /// * `accounts` - accounts to be fixed, at most `MAX_ACCOUNTS_IN_BATCH` of them
+    /// * `status` - a [`TxStatus`] for a tx to wait for
+    async fn fix_accounts_consumers_counter_batch(
+        &self,
+        accounts: Vec<AccountId>,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo>;
+}
+
+#[async_trait::async_trait]
+impl<S: SignedConnectionApi> OperationsApi for S {
+    async fn fix_accounts_consumers_counter(
+        &self,
+        who: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx()
+            .operations()
+            .fix_accounts_consumers_counter(Static::from(who));
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn remove_orphaned_next_keys(
+        &self,
+        who: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx()
+            .operations()
+            .remove_orphaned_next_keys(Static::from(who));
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn fix_accounts_consumers_overflow(
+        &self,
+        who: AccountId,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx()
+            .operations()
+            .fix_accounts_consumers_overflow(Static::from(who));
+
+        self.send_tx(tx, status).await
+    }
+
+    async fn fix_accounts_consumers_counter_batch(
+        &self,
+        accounts: Vec<AccountId>,
+        status: TxStatus,
+    ) -> anyhow::Result<TxInfo> {
+        let tx = api::tx()
+            .operations()
+            .fix_accounts_consumers_counter_batch(accounts.into_iter().map(Static::from).collect());
+
+        self.send_tx(tx, status).await
+    }
+}
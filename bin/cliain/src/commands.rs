@@ -174,12 +174,16 @@ pub enum Command {
         finalizer_seed: Option<String>,
     },
 
-    /// Sets seed as the emergency finalizer. Requires sudo.
+    /// Sets seeds as the emergency finalizers. Requires sudo.
     SetEmergencyFinalizer {
-        /// The seed of the key to use as emergency finalizer key.
-        /// If not given, a user is prompted to provide finalizer seed
-        #[clap(long)]
-        finalizer_seed: Option<String>,
+        /// The seeds of the keys to use as emergency finalizer keys, space separated.
+        /// If not given, a user is prompted to provide a single finalizer seed
+        #[clap(long, multiple_values = true)]
+        finalizer_seed: Option<Vec<String>>,
+
+        /// How many of the finalizers have to sign a block to finalize it
+        #[clap(long, default_value = "1")]
+        threshold: u32,
     },
 
     /// Gets next session keys for a validator with specified AccountId
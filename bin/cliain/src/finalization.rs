@@ -8,10 +8,14 @@ use primitives::{BlockHash, BlockNumber};
 
 use crate::RootConnection;
 
-/// Sets the emergency finalized, the provided string should be the seed phrase of the desired finalizer.
-pub async fn set_emergency_finalizer(connection: RootConnection, finalizer: AccountId) {
+/// Sets the emergency finalizers, `threshold` of which have to sign a block to finalize it.
+pub async fn set_emergency_finalizer(
+    connection: RootConnection,
+    finalizers: Vec<AccountId>,
+    threshold: u32,
+) {
     connection
-        .set_emergency_finalizer(finalizer, TxStatus::Finalized)
+        .set_emergency_finalizer(finalizers, threshold, TxStatus::Finalized)
         .await
         .unwrap();
 }
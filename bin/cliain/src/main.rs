@@ -6,9 +6,9 @@ use cliain::{
     bond, call, change_validators, code_info, finalize, force_new_era, instantiate,
     instantiate_with_code, next_session_keys, nominate, prepare_keys, prompt_password_hidden,
     remove_code, rotate_keys, schedule_upgrade, set_emergency_finalizer, set_keys,
-    set_staking_limits, transfer_keep_alive, treasury_approve, treasury_propose,
-    treasury_reject, update_runtime, upload_code, validate, vest, vest_other, vested_transfer,
-    Command, ConnectionConfig,
+    set_staking_limits, transfer_keep_alive, treasury_approve, treasury_propose, treasury_reject,
+    update_runtime, upload_code, validate, vest, vest_other, vested_transfer, Command,
+    ConnectionConfig,
 };
 use log::{error, info};
 
@@ -87,11 +87,19 @@ async fn main() -> anyhow::Result<()> {
             let finalizer = aleph_keypair_from_string(&finalizer_seed);
             finalize(cfg.get_connection().await, block, hash, finalizer).await;
         }
-        Command::SetEmergencyFinalizer { finalizer_seed } => {
-            let finalizer_seed = read_secret(finalizer_seed, "Provide finalizer seed:");
-            let finalizer = aleph_keypair_from_string(&finalizer_seed);
-            let finalizer = account_from_keypair(&finalizer);
-            set_emergency_finalizer(cfg.get_root_connection().await, finalizer).await;
+        Command::SetEmergencyFinalizer {
+            finalizer_seed,
+            threshold,
+        } => {
+            let finalizer_seeds = match finalizer_seed {
+                Some(seeds) => seeds,
+                None => vec![read_secret(None, "Provide finalizer seed:")],
+            };
+            let finalizers = finalizer_seeds
+                .iter()
+                .map(|seed| account_from_keypair(&aleph_keypair_from_string(seed)))
+                .collect();
+            set_emergency_finalizer(cfg.get_root_connection().await, finalizers, threshold).await;
         }
         Command::SetKeys { new_keys } => {
             set_keys(cfg.get_signed_connection().await, new_keys).await
@@ -9,8 +9,10 @@ use pallet_transaction_payment::FeeDetails;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use primitives::{
     crypto::SignatureSet, AccountId, ApiError as AlephApiError, AuraId, AuthorityId as AlephId,
-    AuthoritySignature, Balance, Block, Nonce, Perbill, Score, SessionAuthorityData,
-    SessionCommittee, SessionIndex, SessionValidatorError, Version as FinalityVersion,
+    AuthoritySignature, Balance, BanReason, Block, BlockHash, BlockNumber, EmergencyFinalization,
+    EquivocationProof, EraCommitteeInfo, EraIndex, Nonce, Perbill, Score, SessionAuthorityData,
+    SessionCommittee, SessionIndex, SessionValidatorError, ValidatorEraPerformance,
+    Version as FinalityVersion, VersionChange,
 };
 use sp_consensus_aura::SlotDuration;
 use sp_core::OpaqueMetadata;
@@ -174,6 +176,18 @@ pub mod fake_runtime {
                 unimplemented!()
             }
 
+            fn finality_version_change() -> Option<VersionChange> {
+                unimplemented!()
+            }
+
+            fn finality_version_history() -> Vec<VersionChange> {
+                unimplemented!()
+            }
+
+            fn authority_data_for_session(_session: SessionIndex) -> Option<SessionAuthorityData> {
+                unimplemented!()
+            }
+
             fn predict_session_committee(
                 _session: SessionIndex,
             ) -> Result<SessionCommittee<AccountId>, SessionValidatorError> {
@@ -184,6 +198,18 @@ pub mod fake_runtime {
                 unimplemented!()
             }
 
+            fn era_committee_info() -> EraCommitteeInfo<AccountId> {
+                unimplemented!()
+            }
+
+            fn era_performance_report(_era: EraIndex) -> Vec<(AccountId, ValidatorEraPerformance)> {
+                unimplemented!()
+            }
+
+            fn projected_bans() -> Vec<(AccountId, BanReason)> {
+                unimplemented!()
+            }
+
             fn key_owner(_key: AlephId) -> Option<AccountId> {
                 unimplemented!()
             }
@@ -199,6 +225,26 @@ pub mod fake_runtime {
             fn submit_abft_score(_score: Score, _signature: SignatureSet<AuthoritySignature>) -> Option<()>{
                 unimplemented!()
             }
+
+            fn submit_equivocation_report(_proof: EquivocationProof<AuthoritySignature>) -> Option<()> {
+                unimplemented!()
+            }
+
+            fn emergency_finalizations(_session: SessionIndex) -> Vec<(BlockHash, EmergencyFinalization<BlockNumber, AlephId>)> {
+                unimplemented!()
+            }
+
+            fn submit_emergency_finalization(
+                _hash: BlockHash,
+                _number: BlockNumber,
+                _signatures: SignatureSet<AuthoritySignature>,
+            ) -> Option<()> {
+                unimplemented!()
+            }
+
+            fn next_session_unit_creation_delay() -> Option<u64> {
+                unimplemented!()
+            }
         }
 
         /// There’s an important remark on how this fake runtime must be implemented - it does not need to
@@ -6,8 +6,11 @@ use std::{
 };
 
 use aleph_client::{
-    aleph_keypair_from_string, api, pallets::aleph::AlephRpc, primitives::app::Public,
-    sp_core::H256, AlephKeyPair, BlockNumber, Connection, ConnectionApi, Pair,
+    aleph_keypair_from_string, api,
+    pallets::aleph::AlephRpc,
+    primitives::{app::Public, EmergencyFinalizerSet},
+    sp_core::H256,
+    AlephKeyPair, BlockNumber, Connection, ConnectionApi, Pair,
 };
 use anyhow::Result;
 use dialoguer::Confirm;
@@ -278,16 +281,27 @@ pub async fn try_finalize(
     how_many: BlockNumber,
 ) -> Result<()> {
     let key = read_key_from_file(seed_path)?;
-    let on_chain_pubkey = get_finalizer_pubkey(&connections.primary)
+    let on_chain_finalizers = get_finalizers(&connections.primary)
         .await
-        .ok_or_else(|| anyhow::anyhow!("Failed to get the finalizer PK from chain."))?;
-    if key.public().0 != on_chain_pubkey.0 .0 {
+        .ok_or_else(|| anyhow::anyhow!("Failed to get the finalizer PKs from chain."))?;
+    if !on_chain_finalizers
+        .keys
+        .0
+        .iter()
+        .any(|on_chain_pubkey| key.public().0 == on_chain_pubkey.0 .0)
+    {
         return Err(anyhow::anyhow!(
-            "On chain key does not match the key from file {} != {}",
-            hex::encode(on_chain_pubkey.0 .0),
+            "The key from file {} is not one of the on chain finalizer keys",
             hex::encode(key.public().0),
         ));
     }
+    // Blocks are finalized with the signature of a single key.
+    if on_chain_finalizers.threshold != 1 {
+        return Err(anyhow::anyhow!(
+            "Finalization requires {} signatures, only a single one can be provided",
+            on_chain_finalizers.threshold,
+        ));
+    }
     let plan = pre_sequence_finalization_check(&connections, how_many).await?;
     println!(
         "Sanity check passed. Will proceed to finalizing blocks from {} to {} (last hash {})",
@@ -309,7 +323,7 @@ pub async fn try_finalize(
     Ok(())
 }
 
-async fn get_finalizer_pubkey(connection: &Connection) -> Option<Public> {
+async fn get_finalizers(connection: &Connection) -> Option<EmergencyFinalizerSet<Public>> {
     let addrs = api::storage().aleph().emergency_finalizer();
     connection.get_storage_entry_maybe(&addrs, None).await
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use finality_aleph::UnitCreationDelay;
 use log::warn;
@@ -8,11 +8,14 @@ use sc_cli::clap::{self, ArgGroup, Parser};
 #[derive(Debug, Parser, Clone)]
 #[clap(group(ArgGroup::new("backup")))]
 pub struct AlephCli {
+    /// Delay in milliseconds between creating consecutive AlephBFT units. Overridden by the
+    /// value set on chain, if any.
     #[clap(long, default_value_t = DEFAULT_UNIT_CREATION_DELAY)]
     unit_creation_delay: u64,
 
     /// The addresses at which the node will be externally reachable for validator network
-    /// purposes. Have to be provided for validators.
+    /// purposes. Have to be provided for validators. Can be DNS names, which are resolved anew
+    /// on every connection attempt, so a changing IP address does not require a restart.
     #[clap(long)]
     public_validator_addresses: Option<Vec<String>>,
 
@@ -35,6 +38,23 @@ pub struct AlephCli {
     #[clap(long, value_name = "PATH", group = "backup")]
     backup_path: Option<PathBuf>,
 
+    /// The path to a file containing the key used to encrypt backups.
+    ///
+    /// The file has to contain a 32 byte key, either raw or hex encoded. If provided, new backups
+    /// are encrypted with this key. Encrypted backups can only be loaded with the same key, while
+    /// existing unencrypted backups are still loaded as usual.
+    #[clap(long, value_name = "PATH")]
+    backup_encryption_key_file: Option<PathBuf>,
+
+    /// The number of sessions preceding the current one for which backups are kept.
+    #[clap(long, default_value_t = 0)]
+    backup_sessions_to_keep: u32,
+
+    /// The maximal total size in bytes of all backups. When exceeded, backups of the oldest
+    /// sessions are removed, but the backup of the current session is always kept.
+    #[clap(long, value_name = "BYTES")]
+    backup_max_size: Option<u64>,
+
     /// The maximum number of nonfinalized blocks, after which block production should be locally
     /// stopped. DO NOT CHANGE THIS, PRODUCING MORE OR FEWER BLOCKS MIGHT BE CONSIDERED MALICIOUS
     /// BEHAVIOUR AND PUNISHED ACCORDINGLY!
@@ -51,10 +71,35 @@ pub struct AlephCli {
     #[clap(long, default_value_t = 768 * 1024)]
     alephbft_network_bit_rate: u64,
 
+    /// Maximum bit-rate in bits per second of a single peer of the alephbft validator network.
+    /// By default a single peer can use the whole bit-rate of the alephbft validator network.
+    #[clap(long)]
+    alephbft_per_peer_bit_rate: Option<u64>,
+
+    /// Maximum egress bit-rate in bits per second of the alephbft validator network.
+    /// Unlimited by default.
+    #[clap(long)]
+    alephbft_network_egress_bit_rate: Option<u64>,
+
+    /// Temporarily loosen the alephbft validator network bit-rate limit when finalization falls behind,
+    /// so it never becomes the cause of falling out of consensus. Disabled by default.
+    #[clap(long, default_value_t = false)]
+    adaptive_alephbft_bit_rate: bool,
+
     /// Maximum bit-rate in bits per second of the substrate network.
     #[clap(long, default_value_t = 5*1024*1024)]
     substrate_network_bit_rate: u64,
 
+    /// Maximum egress bit-rate in bits per second of the substrate network.
+    /// Unlimited by default.
+    #[clap(long)]
+    substrate_network_egress_bit_rate: Option<u64>,
+
+    /// The number of seconds without finalization progress, after which the node logs diagnostics
+    /// explaining its state and reports a finality stall in metrics. Set to 0 to disable.
+    #[clap(long, default_value_t = 120)]
+    finality_stall_timeout: u64,
+
     /// Don't spend some extra time to collect more debugging data (e.g. validator network details).
     /// By default collecting is enabled, as the impact on performance is negligible, if any.
     #[clap(long, default_value_t = true)]
@@ -82,10 +127,29 @@ impl AlephCli {
         self.backup_path.clone()
     }
 
+    pub fn backup_encryption_key_file(&self) -> Option<PathBuf> {
+        self.backup_encryption_key_file.clone()
+    }
+
+    pub fn backup_sessions_to_keep(&self) -> u32 {
+        self.backup_sessions_to_keep
+    }
+
+    pub fn backup_max_size(&self) -> Option<u64> {
+        self.backup_max_size
+    }
+
     pub fn no_backup(&self) -> bool {
         self.no_backup
     }
 
+    pub fn finality_stall_timeout(&self) -> Option<Duration> {
+        match self.finality_stall_timeout {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
+
     pub fn max_nonfinalized_blocks(&self) -> u32 {
         if self.max_nonfinalized_blocks != DEFAULT_MAX_NON_FINALIZED_BLOCKS {
             warn!("Running block production with a value of max-nonfinalized-blocks {}, which is not the default of 20. THIS MIGHT BE CONSIDERED MALICIOUS BEHAVIOUR AND RESULT IN PENALTIES!", self.max_nonfinalized_blocks);
@@ -101,10 +165,26 @@ impl AlephCli {
         self.alephbft_network_bit_rate
     }
 
+    pub fn alephbft_per_peer_bit_rate(&self) -> Option<u64> {
+        self.alephbft_per_peer_bit_rate
+    }
+
+    pub fn alephbft_network_egress_bit_rate(&self) -> Option<u64> {
+        self.alephbft_network_egress_bit_rate
+    }
+
+    pub fn alephbft_adaptive_bit_rate(&self) -> bool {
+        self.adaptive_alephbft_bit_rate
+    }
+
     pub fn substrate_network_bit_rate(&self) -> u64 {
         self.substrate_network_bit_rate
     }
 
+    pub fn substrate_network_egress_bit_rate(&self) -> Option<u64> {
+        self.substrate_network_egress_bit_rate
+    }
+
     pub fn collect_validator_network_data(&self) -> bool {
         self.collect_validator_network_data
     }
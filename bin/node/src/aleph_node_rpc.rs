@@ -1,8 +1,8 @@
 use std::{collections::HashMap, sync::Arc};
 
 use finality_aleph::{
-    AlephJustification, BlockId, Justification, JustificationTranslator, ValidatorAddressCache,
-    ValidatorAddressingInfo,
+    AlephJustification, BlockId, CommitteeConnectivity, Justification, JustificationTranslator,
+    ValidatorAddressCache, ValidatorAddressingInfo, ValidatorConnectivity,
 };
 use futures::channel::mpsc;
 use jsonrpsee::{
@@ -10,14 +10,21 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorObject},
 };
-use parity_scale_codec::Decode;
-use primitives::{AccountId, Block, BlockHash, BlockNumber, Signature};
-use sc_client_api::StorageProvider;
+use pallet_aleph_runtime_api::AlephSessionApi;
+use parity_scale_codec::{Decode, Encode};
+use primitives::{
+    AccountId, Block, BlockHash, BlockNumber, EraCommitteeInfo, SessionAuthorityData, SessionIndex,
+    Signature, VersionChange, ALEPH_ENGINE_ID, KEY_TYPE,
+};
+use sc_client_api::{BlockBackend, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_arithmetic::traits::Zero;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
 use sp_consensus_aura::digests::CompatibleDigestItem;
-use sp_core::{twox_128, Bytes};
+use sp_core::{crypto::ByteArray, twox_128, Bytes};
+use sp_keystore::KeystorePtr;
 use sp_runtime::{
     traits::{Block as BlockT, Header as HeaderT},
     DigestItem,
@@ -61,6 +68,15 @@ pub enum Error {
     /// Network info caching is not enabled.
     #[error("Unable to get any data, because network info caching is not enabled.")]
     NetworkInfoCachingNotEnabled,
+    /// Failed to call the runtime API.
+    #[error("Failed to call runtime API {0} at the block {1}: {2}.")]
+    FailedRuntimeApiCall(&'static str, String, String),
+    /// Failed to read justifications of a block.
+    #[error("Failed to read justifications of a block {0}: {1:?}.")]
+    FailedJustificationRead(String, sp_blockchain::Error),
+    /// Failed to read keys from the keystore.
+    #[error("Failed to read keys from the keystore: {0}.")]
+    FailedKeystoreRead(String),
 }
 
 // Base code for all system errors.
@@ -85,6 +101,12 @@ const FAILED_HEADER_DECODING_ERROR: i32 = BASE_ERROR + 8;
 const UNKNOWN_HASH_ERROR: i32 = BASE_ERROR + 9;
 /// Network info caching is not enabled.
 const NETWORK_INFO_CACHING_NOT_ENABLED_ERROR: i32 = BASE_ERROR + 10;
+/// Failed to call the runtime API.
+const FAILED_RUNTIME_API_CALL_ERROR: i32 = BASE_ERROR + 11;
+/// Failed to read justifications of a block.
+const FAILED_JUSTIFICATION_READ_ERROR: i32 = BASE_ERROR + 12;
+/// Failed to read keys from the keystore.
+const FAILED_KEYSTORE_READ_ERROR: i32 = BASE_ERROR + 13;
 
 impl From<Error> for JsonRpseeError {
     fn from(e: Error) -> Self {
@@ -145,11 +167,66 @@ impl From<Error> for JsonRpseeError {
                 "Unable to get any data, because network info caching is not enabled.",
                 None::<()>,
             )),
+            Error::FailedRuntimeApiCall(method, hash, err) => {
+                CallError::Custom(ErrorObject::owned(
+                    FAILED_RUNTIME_API_CALL_ERROR,
+                    format!("Failed to call runtime API {method} at the block {hash}: {err}."),
+                    None::<()>,
+                ))
+            }
+            Error::FailedJustificationRead(hash, err) => CallError::Custom(ErrorObject::owned(
+                FAILED_JUSTIFICATION_READ_ERROR,
+                format!("Failed to read justifications of a block {hash}: {err:?}."),
+                None::<()>,
+            )),
+            Error::FailedKeystoreRead(err) => CallError::Custom(ErrorObject::owned(
+                FAILED_KEYSTORE_READ_ERROR,
+                format!("Failed to read keys from the keystore: {err}."),
+                None::<()>,
+            )),
         }
         .into()
     }
 }
 
+/// Finality versions recorded so far, each with the first session it was used in, together with
+/// the scheduled version change, if any.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalityVersionTimeline {
+    pub history: Vec<VersionChange>,
+    pub scheduled: Option<VersionChange>,
+}
+
+/// Everything needed to verify finality of a block outside of the chain, e.g. by a light client
+/// of a bridge. All the fields are SCALE encoded:
+/// - `header` is the header of the finalized block,
+/// - `justification` is the Aleph justification of the block, as stored by the node, i.e. prefixed
+///   with its version,
+/// - `authority_data` is the `SessionAuthorityData` of the session the block belongs to, used to
+///   verify the justification.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityProof {
+    pub header: Bytes,
+    pub justification: Bytes,
+    pub authority_data: Bytes,
+}
+
+/// Sync state of the node as seen by the node itself, together with its place in the consensus.
+/// The session and committee membership are determined as of the best block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState {
+    pub major_sync: bool,
+    pub offline: bool,
+    pub best_number: BlockNumber,
+    pub best_hash: BlockHash,
+    pub finalized_number: BlockNumber,
+    pub finalized_hash: BlockHash,
+    pub current_session: SessionIndex,
+    pub in_committee: bool,
+}
+
 /// Aleph Node RPC API
 #[rpc(client, server, namespace = "alephNode")]
 pub trait AlephNodeApi<BE> {
@@ -172,6 +249,35 @@ pub trait AlephNodeApi<BE> {
 
     #[method(name = "unstable_validatorNetworkInfo")]
     fn validator_network_info(&self) -> RpcResult<HashMap<AccountId, ValidatorAddressingInfo>>;
+
+    /// Get the committee members of the current session this node has an authenticated connection
+    /// with in the validator network. Returns `None` if the node is not in the committee.
+    #[method(name = "unstable_validatorNetworkConnectivity")]
+    fn validator_network_connectivity(&self) -> RpcResult<Option<CommitteeConnectivity>>;
+
+    /// Get the finality version history and the scheduled version change, as of the block with
+    /// given hash or the best block.
+    #[method(name = "finalityVersionTimeline")]
+    fn finality_version_timeline(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<FinalityVersionTimeline>;
+
+    /// Get validators and committee sizes of the current and the next era, together with ban
+    /// thresholds, as of the block with given hash or the best block.
+    #[method(name = "eraCommittee")]
+    fn era_committee(&self, at: Option<BlockHash>) -> RpcResult<EraCommitteeInfo<AccountId>>;
+
+    /// Get the header of the block with given hash together with its Aleph justification and the
+    /// authority data needed to verify it. Returns `None` if the node has no Aleph justification
+    /// stored for the block.
+    #[method(name = "finalityProof")]
+    fn finality_proof(&self, hash: BlockHash) -> RpcResult<Option<FinalityProof>>;
+
+    /// Get the sync state of the node: whether it is major syncing or offline, the best and
+    /// finalized blocks it knows of, the current session and whether it is in its committee.
+    #[method(name = "syncState")]
+    fn sync_state(&self) -> RpcResult<SyncState>;
 }
 
 /// Aleph Node API implementation
@@ -181,6 +287,8 @@ pub struct AlephNode<Client, SO> {
     client: Arc<Client>,
     sync_oracle: SO,
     validator_address_cache: Option<ValidatorAddressCache>,
+    validator_connectivity: ValidatorConnectivity,
+    keystore: KeystorePtr,
 }
 
 impl<Client, SO> AlephNode<Client, SO>
@@ -193,6 +301,8 @@ where
         client: Arc<Client>,
         sync_oracle: SO,
         validator_address_cache: Option<ValidatorAddressCache>,
+        validator_connectivity: ValidatorConnectivity,
+        keystore: KeystorePtr,
     ) -> Self {
         AlephNode {
             import_justification_tx,
@@ -200,6 +310,8 @@ where
             client,
             sync_oracle,
             validator_address_cache,
+            validator_connectivity,
+            keystore,
         }
     }
 }
@@ -207,7 +319,14 @@ where
 impl<Client, BE, SO> AlephNodeApiServer<BE> for AlephNode<Client, SO>
 where
     BE: sc_client_api::Backend<Block> + 'static,
-    Client: HeaderBackend<Block> + StorageProvider<Block, BE> + 'static,
+    Client: HeaderBackend<Block>
+        + BlockBackend<Block>
+        + StorageProvider<Block, BE>
+        + ProvideRuntimeApi<Block>
+        + Send
+        + Sync
+        + 'static,
+    Client::Api: AlephSessionApi<Block>,
     SO: SyncOracle + Send + Sync + 'static,
 {
     fn emergency_finalize(
@@ -274,6 +393,105 @@ where
             .map(|c| c.snapshot())
             .ok_or(Error::NetworkInfoCachingNotEnabled.into())
     }
+
+    fn validator_network_connectivity(&self) -> RpcResult<Option<CommitteeConnectivity>> {
+        Ok(self.validator_connectivity.snapshot())
+    }
+
+    fn finality_version_timeline(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<FinalityVersionTimeline> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let runtime_api = self.client.runtime_api();
+        let history = runtime_api.finality_version_history(at).map_err(|e| {
+            Error::FailedRuntimeApiCall("finality_version_history", at.to_string(), e.to_string())
+        })?;
+        let scheduled = runtime_api.finality_version_change(at).map_err(|e| {
+            Error::FailedRuntimeApiCall("finality_version_change", at.to_string(), e.to_string())
+        })?;
+        Ok(FinalityVersionTimeline { history, scheduled })
+    }
+
+    fn era_committee(&self, at: Option<BlockHash>) -> RpcResult<EraCommitteeInfo<AccountId>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .era_committee_info(at)
+            .map_err(|e| {
+                Error::FailedRuntimeApiCall("era_committee_info", at.to_string(), e.to_string())
+                    .into()
+            })
+    }
+
+    fn finality_proof(&self, hash: BlockHash) -> RpcResult<Option<FinalityProof>> {
+        let header = self
+            .client
+            .header(hash)
+            .map_err(|e| Error::FailedHeaderDecoding(hash.to_string(), e))?
+            .ok_or(Error::UnknownHash(hash.to_string()))?;
+        let justification = match self
+            .client
+            .justifications(hash)
+            .map_err(|e| Error::FailedJustificationRead(hash.to_string(), e))?
+            .and_then(|justifications| justifications.into_justification(ALEPH_ENGINE_ID))
+        {
+            Some(justification) => justification,
+            None => return Ok(None),
+        };
+        let runtime_api = self.client.runtime_api();
+        // runtimes before version 2 of the API return a single emergency finalizer
+        let has_emergency_finalizer_sets = runtime_api
+            .api_version::<dyn AlephSessionApi<Block>>(hash)
+            .ok()
+            .flatten()
+            .map_or(false, |version| version >= 2);
+        let authority_data = match has_emergency_finalizer_sets {
+            true => runtime_api.authority_data(hash),
+            #[allow(deprecated)]
+            false => runtime_api
+                .authority_data_before_version_2(hash)
+                .map(SessionAuthorityData::from),
+        }
+        .map_err(|e| {
+            Error::FailedRuntimeApiCall("authority_data", hash.to_string(), e.to_string())
+        })?;
+
+        Ok(Some(FinalityProof {
+            header: header.encode().into(),
+            justification: justification.into(),
+            authority_data: authority_data.encode().into(),
+        }))
+    }
+
+    fn sync_state(&self) -> RpcResult<SyncState> {
+        let info = self.client.info();
+        let runtime_api = self.client.runtime_api();
+        let session_period = runtime_api.session_period(info.best_hash).map_err(|e| {
+            Error::FailedRuntimeApiCall("session_period", info.best_hash.to_string(), e.to_string())
+        })?;
+        let authorities = runtime_api.authorities(info.best_hash).map_err(|e| {
+            Error::FailedRuntimeApiCall("authorities", info.best_hash.to_string(), e.to_string())
+        })?;
+        let our_keys = self
+            .keystore
+            .keys(KEY_TYPE)
+            .map_err(|e| Error::FailedKeystoreRead(e.to_string()))?;
+        let in_committee = authorities
+            .iter()
+            .any(|authority| our_keys.contains(&authority.to_raw_vec()));
+
+        Ok(SyncState {
+            major_sync: self.sync_oracle.is_major_syncing(),
+            offline: self.sync_oracle.is_offline(),
+            best_number: info.best_number,
+            best_hash: info.best_hash,
+            finalized_number: info.finalized_number,
+            finalized_hash: info.finalized_hash,
+            current_session: info.best_number / session_period.max(1),
+            in_committee,
+        })
+    }
 }
 
 fn read_storage<
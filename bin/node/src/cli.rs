@@ -7,6 +7,7 @@ pub type AlephNodeChainSpec = sc_service::GenericChainSpec<()>;
 
 use crate::{
     aleph_cli::AlephCli,
+    inspect_backups::InspectBackupsCmd,
     resources::{mainnet_chainspec, testnet_chainspec},
 };
 
@@ -96,6 +97,9 @@ pub enum Subcommand {
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
 
+    /// Inspect, and optionally repair, the AlephBFT backups.
+    InspectBackups(InspectBackupsCmd),
+
     /// The custom benchmark subcommand benchmarking runtime pallets.
     #[cfg(feature = "runtime-benchmarks")]
     #[clap(subcommand)]
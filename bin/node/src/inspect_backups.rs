@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use finality_aleph::{
+    inspect_backups, truncate_broken_tail, BackupEncryptionKey, CurrentBackupUnit,
+};
+use sc_cli::{clap::Parser, Error, Result};
+
+/// Inspect the AlephBFT backups of a node, reporting the sessions they cover and any broken files.
+#[derive(Debug, Parser)]
+pub struct InspectBackupsCmd {
+    /// The path the node saves backups to, as passed to it with `--backup-path`.
+    #[arg(long, value_name = "PATH")]
+    backup_path: PathBuf,
+
+    /// The path to the file containing the key the backups are encrypted with, if any.
+    #[arg(long, value_name = "PATH")]
+    backup_encryption_key_file: Option<PathBuf>,
+
+    /// Truncate backup files with a broken tail, e.g. after a crash in the middle of writing, so
+    /// the node can be restarted cleanly. Only files whose sole problem is an incomplete last unit
+    /// or frame are repaired, by removing it. Files corrupted anywhere else are left untouched.
+    #[arg(long)]
+    repair: bool,
+}
+
+impl InspectBackupsCmd {
+    pub fn run(&self) -> Result<()> {
+        let encryption_key = self
+            .backup_encryption_key_file
+            .as_ref()
+            .map(|path| BackupEncryptionKey::from_file(path))
+            .transpose()
+            .map_err(|e| Error::Input(format!("failed to read the backup encryption key: {e}")))?;
+        let sessions =
+            inspect_backups::<CurrentBackupUnit>(&self.backup_path, encryption_key.as_ref())?;
+        if sessions.is_empty() {
+            println!("No backups found at {}.", self.backup_path.display());
+            return Ok(());
+        }
+        let mut broken = 0;
+        for session in sessions {
+            let units: usize = session.files.iter().map(|file| file.units).sum();
+            println!(
+                "Session {}: {} files, {} units{}",
+                session.session_id,
+                session.files.len(),
+                units,
+                match session.complete {
+                    true => "",
+                    false => ", some files are missing",
+                }
+            );
+            for file in session.files {
+                let Some(problem) = &file.problem else {
+                    continue;
+                };
+                broken += 1;
+                println!(
+                    "  {} ({} bytes, {}): {}",
+                    file.path.display(),
+                    file.size,
+                    match file.encrypted {
+                        true => "encrypted",
+                        false => "plaintext",
+                    },
+                    problem
+                );
+                match (self.repair, file.truncate_to) {
+                    (true, Some(length)) => {
+                        truncate_broken_tail(&file)?;
+                        println!("    truncated to {length} bytes");
+                    }
+                    (false, Some(length)) => {
+                        println!("    can be repaired by truncating to {length} bytes")
+                    }
+                    (_, None) => println!("    cannot be repaired by truncating"),
+                }
+            }
+        }
+        if broken == 0 {
+            println!("All backup files are intact.");
+        }
+        Ok(())
+    }
+}
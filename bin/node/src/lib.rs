@@ -3,6 +3,7 @@ mod aleph_node_rpc;
 mod cli;
 mod config;
 mod executor;
+mod inspect_backups;
 mod resources;
 mod rpc;
 mod service;
@@ -7,17 +7,20 @@
 
 use std::sync::Arc;
 
-use finality_aleph::{Justification, JustificationTranslator, ValidatorAddressCache};
+use finality_aleph::{
+    Justification, JustificationTranslator, ValidatorAddressCache, ValidatorConnectivity,
+};
 use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
 use primitives::{AccountId, Balance, Block, Nonce};
-use sc_client_api::StorageProvider;
+use sc_client_api::{BlockBackend, StorageProvider};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus::SyncOracle;
+use sp_keystore::KeystorePtr;
 
 /// Full client dependencies.
 pub struct FullDeps<C, P, SO> {
@@ -31,6 +34,8 @@ pub struct FullDeps<C, P, SO> {
     pub justification_translator: JustificationTranslator,
     pub sync_oracle: SO,
     pub validator_address_cache: Option<ValidatorAddressCache>,
+    pub validator_connectivity: ValidatorConnectivity,
+    pub keystore: KeystorePtr,
 }
 
 /// Instantiate all full RPC extensions.
@@ -41,6 +46,7 @@ where
     C: ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + HeaderMetadata<Block, Error = BlockChainError>
+        + BlockBackend<Block>
         + StorageProvider<Block, BE>
         + Send
         + Sync
@@ -48,7 +54,8 @@ where
     BE: sc_client_api::Backend<Block> + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
         + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-        + BlockBuilder<Block>,
+        + BlockBuilder<Block>
+        + pallet_aleph_runtime_api::AlephSessionApi<Block>,
     P: TransactionPool + 'static,
     SO: SyncOracle + Send + Sync + 'static,
 {
@@ -64,6 +71,8 @@ where
         justification_translator,
         sync_oracle,
         validator_address_cache,
+        validator_connectivity,
+        keystore,
     } = deps;
 
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
@@ -78,6 +87,8 @@ where
             client,
             sync_oracle,
             validator_address_cache,
+            validator_connectivity,
+            keystore,
         )
         .into_rpc(),
     )?;
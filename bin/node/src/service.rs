@@ -7,10 +7,11 @@ use std::{
 
 use fake_runtime_api::fake_runtime::RuntimeApi;
 use finality_aleph::{
-    build_network, get_aleph_block_import, run_validator_node, AlephConfig, BlockImporter,
-    BuildNetworkOutput, ChannelProvider, FavouriteSelectChainProvider, Justification,
-    JustificationTranslator, MillisecsPerBlock, RateLimiterConfig, RedirectingBlockImport,
-    SessionPeriod, SubstrateChainStatus, SyncOracle, ValidatorAddressCache,
+    build_network, get_aleph_block_import, run_validator_node, AlephConfig, BackupEncryptionKey,
+    BackupRetention, BlockImporter, BuildNetworkOutput, ChannelProvider,
+    FavouriteSelectChainProvider, Justification, JustificationTranslator, MillisecsPerBlock,
+    RateLimiterConfig, RedirectingBlockImport, SessionPeriod, SubstrateChainStatus, SyncOracle,
+    ValidatorAddressCache, ValidatorConnectivity,
 };
 use log::warn;
 use pallet_aleph_runtime_api::AlephSessionApi;
@@ -138,6 +139,8 @@ pub fn new_partial(config: &Configuration) -> Result<ServiceComponents, ServiceE
         justification_channel_provider.get_sender(),
         justification_translator,
         select_chain_provider.select_chain(),
+        client.clone(),
+        get_aleph_runtime_vars(&client).session_period,
     );
 
     let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
@@ -235,7 +238,11 @@ fn get_proposer_factory(
 fn get_rate_limit_config(aleph_config: &AlephCli) -> RateLimiterConfig {
     RateLimiterConfig {
         alephbft_network_bit_rate: aleph_config.alephbft_network_bit_rate(),
+        alephbft_per_peer_bit_rate: aleph_config.alephbft_per_peer_bit_rate(),
+        alephbft_network_egress_bit_rate: aleph_config.alephbft_network_egress_bit_rate(),
+        alephbft_adaptive_bit_rate: aleph_config.alephbft_adaptive_bit_rate(),
         substrate_network_bit_rate: aleph_config.substrate_network_bit_rate(),
+        substrate_network_egress_bit_rate: aleph_config.substrate_network_egress_bit_rate(),
     }
 }
 
@@ -255,6 +262,13 @@ pub fn new_authority(
     let mut service_components = new_partial(&config)?;
 
     let backup_path = backup_path(&aleph_config, config.base_path.path());
+    let backup_encryption_key = aleph_config
+        .backup_encryption_key_file()
+        .map(|path| BackupEncryptionKey::from_file(&path))
+        .transpose()
+        .map_err(|e| {
+            ServiceError::Other(format!("failed to read the backup encryption key: {e}"))
+        })?;
 
     let backoff_authoring_blocks = Some(LimitNonfinalized(aleph_config.max_nonfinalized_blocks()));
     let prometheus_registry = config.prometheus_registry().cloned();
@@ -297,6 +311,7 @@ pub fn new_authority(
     let rate_limiter_config = get_rate_limit_config(&aleph_config);
     let network_config = finality_aleph::SubstrateNetworkConfig {
         substrate_network_bit_rate: rate_limiter_config.substrate_network_bit_rate,
+        substrate_network_egress_bit_rate: rate_limiter_config.substrate_network_egress_bit_rate,
         network_config: config.network.clone(),
     };
 
@@ -323,15 +338,18 @@ pub fn new_authority(
     let chain_status = SubstrateChainStatus::new(service_components.backend.clone())
         .map_err(|e| ServiceError::Other(format!("failed to set up chain status: {e}")))?;
     let validator_address_cache = get_validator_address_cache(&aleph_config);
+    let validator_connectivity = ValidatorConnectivity::new();
     let rpc_builder = {
         let client = service_components.client.clone();
         let pool = service_components.transaction_pool.clone();
         let sync_oracle = sync_oracle.clone();
         let validator_address_cache = validator_address_cache.clone();
+        let validator_connectivity = validator_connectivity.clone();
         let import_justification_tx = service_components
             .justification_channel_provider
             .get_sender();
         let chain_status = chain_status.clone();
+        let keystore = service_components.keystore_container.keystore();
         Box::new(move |deny_unsafe, _| {
             let deps = RpcFullDeps {
                 client: client.clone(),
@@ -341,6 +359,8 @@ pub fn new_authority(
                 justification_translator: JustificationTranslator::new(chain_status.clone()),
                 sync_oracle: sync_oracle.clone(),
                 validator_address_cache: validator_address_cache.clone(),
+                validator_connectivity: validator_connectivity.clone(),
+                keystore: keystore.clone(),
             };
 
             Ok(create_full_rpc(deps)?)
@@ -394,11 +414,18 @@ pub fn new_authority(
         registry: prometheus_registry,
         unit_creation_delay: aleph_config.unit_creation_delay(),
         backup_saving_path: backup_path,
+        backup_encryption_key,
+        backup_retention: BackupRetention {
+            sessions_to_keep: aleph_config.backup_sessions_to_keep(),
+            max_total_size: aleph_config.backup_max_size(),
+        },
         external_addresses: aleph_config.external_addresses(),
         validator_port: aleph_config.validator_port(),
         rate_limiter_config,
         sync_oracle,
+        finality_stall_timeout: aleph_config.finality_stall_timeout(),
         validator_address_cache,
+        validator_connectivity,
         transaction_pool: service_components.transaction_pool,
     };
 
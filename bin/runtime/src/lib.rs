@@ -44,13 +44,17 @@ use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustm
 use pallet_tx_pause::RuntimeCallNameOf;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::{
-    crypto::SignatureSet, staking::MAX_NOMINATORS_REWARDED_PER_VALIDATOR, wrap_methods, Address,
-    AlephNodeSessionKeys as SessionKeys, ApiError as AlephApiError, AuraId, AuthorityId as AlephId,
-    AuthoritySignature, BlockNumber as AlephBlockNumber, Header as AlephHeader, Score,
-    SessionAuthorityData, SessionCommittee, SessionIndex, SessionInfoProvider,
-    SessionValidatorError, TotalIssuanceProvider as TotalIssuanceProviderT,
-    Version as FinalityVersion, ADDRESSES_ENCODING, DEFAULT_BAN_REASON_LENGTH, DEFAULT_MAX_WINNERS,
-    DEFAULT_SESSIONS_PER_ERA, DEFAULT_SESSION_PERIOD, MAX_BLOCK_SIZE, MILLISECS_PER_BLOCK, TOKEN,
+    crypto::SignatureSet,
+    staking::{MAX_NOMINATORS_REWARDED_PER_VALIDATOR, MIN_VALIDATOR_BOND},
+    wrap_methods, Address, AlephNodeSessionKeys as SessionKeys, ApiError as AlephApiError, AuraId,
+    AuthorityId as AlephId, AuthoritySignature, BanReason, BlockNumber as AlephBlockNumber,
+    EmergencyFinalization, EquivocationHandler, EquivocationProof, EraCommitteeInfo, EraValidators,
+    Header as AlephHeader, Score, SessionAuthorityData, SessionCommittee, SessionIndex,
+    SessionInfoProvider, SessionValidatorError, TotalIssuanceProvider as TotalIssuanceProviderT,
+    ValidatorEraPerformance, Version as FinalityVersion, VersionChange, ADDRESSES_ENCODING,
+    DEFAULT_BAN_REASON_LENGTH, DEFAULT_MAX_VALIDATORS, DEFAULT_MAX_WINNERS,
+    DEFAULT_SESSIONS_PER_ERA, DEFAULT_SESSION_PERIOD, DEFAULT_UNIT_CREATION_DELAY, MAX_BLOCK_SIZE,
+    MAX_EMERGENCY_FINALIZERS, MILLISECS_PER_BLOCK, TOKEN,
 };
 pub use primitives::{AccountId, AccountIndex, Balance, Hash, Nonce, Signature};
 use sp_api::impl_runtime_apis;
@@ -80,10 +84,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("aleph-node"),
     impl_name: create_runtime_str!("aleph-node"),
     authoring_version: 1,
-    spec_version: 16_000_000,
+    spec_version: 16_000_045,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 19,
+    transaction_version: 20,
     state_version: 0,
 };
 
@@ -321,6 +325,9 @@ impl SessionInfoProvider<AlephBlockNumber> for SessionInfoImpl {
         )
         .0
     }
+    fn session_of_block(block: AlephBlockNumber) -> SessionIndex {
+        block / SessionPeriod::get()
+    }
 }
 
 pub struct TotalIssuanceProvider;
@@ -330,6 +337,26 @@ impl TotalIssuanceProviderT for TotalIssuanceProvider {
     }
 }
 
+/// Removes authorities proven to have equivocated from the current session and bans them from
+/// the committee starting with the next era.
+pub struct EquivocationPunisher;
+impl EquivocationHandler<AlephId> for EquivocationPunisher {
+    fn on_equivocation(offender: &AlephId, session: SessionIndex) {
+        if let Some(validator) = Session::key_owner(primitives::KEY_TYPE, offender.as_ref()) {
+            Session::disable(&validator);
+            CommitteeManagement::ban_validator(&validator, BanReason::Equivocation(session));
+        }
+    }
+}
+
+parameter_types! {
+    // About a week with the default session and era lengths.
+    pub const SessionAuthoritiesHistoryDepth: SessionIndex = 7 * DEFAULT_SESSIONS_PER_ERA;
+    // Higher delays would slow down AlephBFT so much that finalization could not keep up.
+    pub const MaxUnitCreationDelay: u64 = 10 * DEFAULT_UNIT_CREATION_DELAY;
+    pub const MaxEmergencyFinalizers: u32 = MAX_EMERGENCY_FINALIZERS;
+}
+
 impl pallet_aleph::Config for Runtime {
     type AuthorityId = AlephId;
     type RuntimeEvent = RuntimeEvent;
@@ -342,12 +369,50 @@ impl pallet_aleph::Config for Runtime {
     >;
     type NextSessionAuthorityProvider = Session;
     type TotalIssuanceProvider = TotalIssuanceProvider;
+    type EquivocationHandler = EquivocationPunisher;
+    type SessionAuthoritiesHistoryDepth = SessionAuthoritiesHistoryDepth;
+    type MaxUnitCreationDelay = MaxUnitCreationDelay;
+    type MaxEmergencyFinalizers = MaxEmergencyFinalizers;
 }
 
 parameter_types! {
     pub const SessionPeriod: u32 = DEFAULT_SESSION_PERIOD;
     pub const MaximumBanReasonLength: u32 = DEFAULT_BAN_REASON_LENGTH;
     pub const MaxWinners: u32 = DEFAULT_MAX_WINNERS;
+    pub const MaxValidators: u32 = DEFAULT_MAX_VALIDATORS;
+}
+
+pub struct CandidateInfo;
+
+impl pallet_elections::CandidateInfoProvider for CandidateInfo {
+    type AccountId = AccountId;
+
+    fn bonded(who: &AccountId) -> Balance {
+        <Staking as sp_staking::StakingInterface>::stake(who)
+            .map(|stake| stake.active)
+            .unwrap_or_default()
+    }
+
+    fn has_session_keys(who: &AccountId) -> bool {
+        pallet_session::NextKeys::<Runtime>::contains_key(who)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn make_eligible(who: &AccountId) {
+        use frame_support::traits::fungible::Mutate;
+        use sp_runtime::traits::TrailingZeroInput;
+
+        let _ = Balances::set_balance(who, 2 * MIN_VALIDATOR_BOND);
+        let _ = <Staking as sp_staking::StakingInterface>::bond(who, MIN_VALIDATOR_BOND, who);
+        let keys = SessionKeys::decode(&mut TrailingZeroInput::zeroes())
+            .expect("Keys decode from an infinite input");
+        pallet_session::NextKeys::<Runtime>::insert(who, keys);
+    }
+}
+
+parameter_types! {
+    pub const MinimalCandidateBond: Balance = MIN_VALIDATOR_BOND;
+    pub const MaxCandidates: u32 = 100;
 }
 
 impl pallet_elections::Config for Runtime {
@@ -356,6 +421,10 @@ impl pallet_elections::Config for Runtime {
     type ValidatorProvider = Staking;
     type MaxWinners = MaxWinners;
     type BannedValidators = CommitteeManagement;
+    type CandidateInfoProvider = CandidateInfo;
+    type MinimalCandidateBond = MinimalCandidateBond;
+    type MaxValidators = MaxValidators;
+    type MaxCandidates = MaxCandidates;
 }
 
 impl pallet_operations::Config for Runtime {
@@ -377,6 +446,9 @@ impl pallet_committee_management::Config for Runtime {
     type FinalityCommitteeManager = Aleph;
     type SessionPeriod = SessionPeriod;
     type AbftScoresProvider = Aleph;
+    type PerformanceThresholdOrigin = EnsureRoot<AccountId>;
+    type BanAppealOrigin = EnsureRoot<AccountId>;
+    type PerformanceReportHistoryDepth = HistoryDepth;
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
@@ -1034,6 +1106,13 @@ pub type SignedBlock = generic::SignedBlock<Block>;
 /// BlockId type as expected by this runtime.
 pub type BlockId = generic::BlockId<Block>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+    pallet_aleph::migrations::v3::Migration<Runtime>,
+    pallet_aleph::migrations::v4::Migration<Runtime>,
+    pallet_elections::migrations::v6::Migration<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -1041,11 +1120,16 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
-    frame_benchmarking::define_benchmarks!();
+    frame_benchmarking::define_benchmarks!(
+        [pallet_aleph, Aleph]
+        [pallet_elections, Elections]
+        [pallet_operations, Operations]
+    );
 }
 
 type EventRecord = frame_system::EventRecord<RuntimeEvent, Hash>;
@@ -1206,6 +1290,20 @@ impl_runtime_apis! {
             Aleph::next_session_finality_version()
         }
 
+        fn finality_version_change() -> Option<VersionChange> {
+            Aleph::finality_version_change()
+        }
+
+        fn finality_version_history() -> Vec<VersionChange> {
+            Aleph::finality_version_history()
+        }
+
+        fn authority_data_for_session(session: SessionIndex) -> Option<SessionAuthorityData> {
+            Aleph::authorities_for_session(session).map(|(authorities, emergency_finalizers)| {
+                SessionAuthorityData::new(authorities, emergency_finalizers)
+            })
+        }
+
         fn predict_session_committee(
             session: SessionIndex,
         ) -> Result<SessionCommittee<AccountId>, SessionValidatorError> {
@@ -1218,6 +1316,30 @@ impl_runtime_apis! {
             queued_keys.into_iter().filter_map(|(account_id, keys)| keys.get(AURA).map(|key| (account_id, key))).collect()
         }
 
+        fn era_committee_info() -> EraCommitteeInfo<AccountId> {
+            EraCommitteeInfo {
+                current_validators: Elections::current_era_validators(),
+                current_committee_size: pallet_elections::CommitteeSize::<Runtime>::get(),
+                next_validators: EraValidators {
+                    reserved: pallet_elections::NextEraReservedValidators::<Runtime>::get()
+                        .into_inner(),
+                    non_reserved: pallet_elections::NextEraNonReservedValidators::<Runtime>::get()
+                        .into_inner(),
+                },
+                next_committee_size: pallet_elections::NextEraCommitteeSize::<Runtime>::get(),
+                production_ban_config: CommitteeManagement::production_ban_config(),
+                finality_ban_config: CommitteeManagement::finality_ban_config(),
+            }
+        }
+
+        fn era_performance_report(era: EraIndex) -> Vec<(AccountId, ValidatorEraPerformance)> {
+            CommitteeManagement::era_performance_report(era)
+        }
+
+        fn projected_bans() -> Vec<(AccountId, BanReason)> {
+            CommitteeManagement::projected_bans()
+        }
+
         fn key_owner(key: AlephId) -> Option<AccountId> {
             Session::key_owner(primitives::KEY_TYPE, key.as_ref())
         }
@@ -1243,6 +1365,26 @@ impl_runtime_apis! {
         fn submit_abft_score(score: Score, signature: SignatureSet<AuthoritySignature>) -> Option<()> {
             Aleph::submit_abft_score(score, signature)
         }
+
+        fn submit_equivocation_report(proof: EquivocationProof<AuthoritySignature>) -> Option<()> {
+            Aleph::submit_equivocation_report(proof)
+        }
+
+        fn emergency_finalizations(session: SessionIndex) -> Vec<(Hash, EmergencyFinalization<AlephBlockNumber, AlephId>)> {
+            Aleph::emergency_finalizations(session)
+        }
+
+        fn submit_emergency_finalization(
+            hash: Hash,
+            number: AlephBlockNumber,
+            signatures: SignatureSet<AuthoritySignature>,
+        ) -> Option<()> {
+            Aleph::submit_emergency_finalization(hash, number, signatures)
+        }
+
+        fn next_session_unit_creation_delay() -> Option<u64> {
+            Aleph::next_session_unit_creation_delay()
+        }
     }
 
     impl pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance> for Runtime {
@@ -225,6 +225,15 @@ impl<PK: PublicKey + PeerId, A: Data, D: Data> Manager<PK, A, D> {
             .map_err(|_| SendError::ConnectionClosed)
     }
 
+    /// Peers we currently have an active connection with.
+    pub fn connected_peers(&self) -> HashSet<PK> {
+        self.have
+            .keys()
+            .filter(|peer_id| self.active_connection(peer_id))
+            .cloned()
+            .collect()
+    }
+
     /// A status of the manager, to be displayed somewhere.
     pub fn status_report(&self) -> ManagerStatus<PK> {
         ManagerStatus::new(self)
@@ -237,6 +246,8 @@ impl<PK: PublicKey + PeerId, A: Data, D: Data> Manager<PK, A, D> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use futures::{channel::mpsc, StreamExt};
 
     use super::{AddResult::*, Manager, SendError};
@@ -276,6 +287,22 @@ mod tests {
         manager.remove_peer(&peer_id_b);
     }
 
+    #[test]
+    fn reports_connected_peers() {
+        let (own_id, _) = key();
+        let mut manager = Manager::<MockPublicKey, Address, Data>::new(own_id, Metrics::noop());
+        let (peer_id, _) = key();
+        let address = String::from("43.43.43.43:43000");
+        manager.add_peer(peer_id.clone(), address);
+        assert!(manager.connected_peers().is_empty());
+        let (tx, rx) = mpsc::unbounded();
+        assert_eq!(manager.add_connection(peer_id.clone(), tx), Added);
+        assert_eq!(manager.connected_peers(), HashSet::from([peer_id]));
+        // a connection with a closed channel is no longer active
+        drop(rx);
+        assert!(manager.connected_peers().is_empty());
+    }
+
     #[tokio::test]
     async fn send_receive() {
         let (mut connecting_id, _) = key();
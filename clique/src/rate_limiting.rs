@@ -1,4 +1,4 @@
-use rate_limiter::{RateLimitedAsyncRead, SharedRateLimiter};
+use rate_limiter::{RateLimitedAsyncRead, RateLimitedAsyncWrite, SharedRateLimiter};
 
 use crate::{ConnectionInfo, Data, Dialer, Listener, PeerAddressInfo, Splittable, Splitted};
 
@@ -11,10 +11,20 @@ where
     }
 }
 
+impl<Write> ConnectionInfo for RateLimitedAsyncWrite<Write>
+where
+    Write: ConnectionInfo,
+{
+    fn peer_address_info(&self) -> PeerAddressInfo {
+        self.inner().peer_address_info()
+    }
+}
+
 /// Implementation of the [Dialer] trait governing all returned [Dialer::Connection] instances by a rate-limiting wrapper.
 pub struct RateLimitingDialer<D> {
     dialer: D,
     rate_limiter: SharedRateLimiter,
+    egress_rate_limiter: SharedRateLimiter,
 }
 
 impl<D> Clone for RateLimitingDialer<D>
@@ -25,15 +35,21 @@ where
         Self {
             dialer: self.dialer.clone(),
             rate_limiter: self.rate_limiter.share(),
+            egress_rate_limiter: self.egress_rate_limiter.share(),
         }
     }
 }
 
 impl<D> RateLimitingDialer<D> {
-    pub fn new(dialer: D, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(
+        dialer: D,
+        rate_limiter: SharedRateLimiter,
+        egress_rate_limiter: SharedRateLimiter,
+    ) -> Self {
         Self {
             dialer,
             rate_limiter,
+            egress_rate_limiter,
         }
     }
 }
@@ -48,7 +64,7 @@ where
 {
     type Connection = Splitted<
         RateLimitedAsyncRead<<D::Connection as Splittable>::Receiver>,
-        <D::Connection as Splittable>::Sender,
+        RateLimitedAsyncWrite<<D::Connection as Splittable>::Sender>,
     >;
     type Error = D::Error;
 
@@ -57,7 +73,7 @@ where
         let (sender, receiver) = connection.split();
         Ok(Splitted(
             RateLimitedAsyncRead::new(receiver, self.rate_limiter.share()),
-            sender,
+            RateLimitedAsyncWrite::new(sender, self.egress_rate_limiter.share()),
         ))
     }
 }
@@ -66,13 +82,19 @@ where
 pub struct RateLimitingListener<L> {
     listener: L,
     rate_limiter: SharedRateLimiter,
+    egress_rate_limiter: SharedRateLimiter,
 }
 
 impl<L> RateLimitingListener<L> {
-    pub fn new(listener: L, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(
+        listener: L,
+        rate_limiter: SharedRateLimiter,
+        egress_rate_limiter: SharedRateLimiter,
+    ) -> Self {
         Self {
             listener,
             rate_limiter,
+            egress_rate_limiter,
         }
     }
 }
@@ -84,7 +106,7 @@ where
 {
     type Connection = Splitted<
         RateLimitedAsyncRead<<L::Connection as Splittable>::Receiver>,
-        <L::Connection as Splittable>::Sender,
+        RateLimitedAsyncWrite<<L::Connection as Splittable>::Sender>,
     >;
     type Error = L::Error;
 
@@ -93,7 +115,7 @@ where
         let (sender, receiver) = connection.split();
         Ok(Splitted(
             RateLimitedAsyncRead::new(receiver, self.rate_limiter.share()),
-            sender,
+            RateLimitedAsyncWrite::new(sender, self.egress_rate_limiter.share()),
         ))
     }
 }
@@ -1,6 +1,8 @@
 use std::{
+    collections::HashSet,
     fmt::{Debug, Display},
     pin::Pin,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
@@ -79,6 +81,37 @@ impl<PK: PublicKey, D: Data, A: Data> Network<PK, A, D> for ServiceInterface<PK,
     }
 }
 
+/// A handle for checking which peers the service currently has an active connection with.
+#[derive(Clone)]
+pub struct ConnectedPeers<PK: PublicKey> {
+    peers: Arc<Mutex<HashSet<PK>>>,
+}
+
+impl<PK: PublicKey> ConnectedPeers<PK> {
+    fn new() -> Self {
+        ConnectedPeers {
+            peers: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    fn set(&self, peers: HashSet<PK>) {
+        *self.peers.lock().expect("mutex is not poisoned") = peers;
+    }
+
+    /// Whether there is an active connection with the peer.
+    pub fn contains(&self, peer: &PK) -> bool {
+        self.peers
+            .lock()
+            .expect("mutex is not poisoned")
+            .contains(peer)
+    }
+
+    /// All the peers with an active connection.
+    pub fn snapshot(&self) -> HashSet<PK> {
+        self.peers.lock().expect("mutex is not poisoned").clone()
+    }
+}
+
 /// Trait abstracting spawning tasks
 pub trait SpawnHandleT {
     /// Run task
@@ -148,6 +181,7 @@ where
     spawn_handle: SH,
     secret_key: SK,
     metrics: Metrics,
+    connected_peers: ConnectedPeers<SK::PublicKey>,
 }
 
 impl<SK: SecretKey, D: Data, A: Data + Debug, ND: Dialer<A>, NL: Listener, SH: SpawnHandleT>
@@ -184,6 +218,7 @@ where
                 spawn_handle,
                 secret_key,
                 metrics,
+                connected_peers: ConnectedPeers::new(),
             },
             ServiceInterface {
                 commands_for_service,
@@ -192,6 +227,15 @@ where
         )
     }
 
+    /// A handle for checking which peers the service is connected with.
+    pub fn connected_peers(&self) -> ConnectedPeers<SK::PublicKey> {
+        self.connected_peers.clone()
+    }
+
+    fn update_connected_peers(&self) {
+        self.connected_peers.set(self.manager.connected_peers());
+    }
+
     fn spawn_new_outgoing(
         &mut self,
         public_key: SK::PublicKey,
@@ -354,6 +398,7 @@ where
                 maybe_command = self.commands_from_interface.next() => {
                     let command = maybe_command.ok_or(Error::Commands)?;
                     self.handle_command(command, &result_for_parent);
+                    self.update_connected_peers();
                 },
                 maybe_authorization_request = authorization_requests.next() => {
                     let (public_key, response_channel) = maybe_authorization_request.ok_or(Error::AuthorizationRequests)?;
@@ -364,10 +409,12 @@ where
                 maybe_data_for_network = worker_results.next() => {
                     let (public_key, maybe_data_for_network) = maybe_data_for_network.ok_or(Error::ConnectionWorker)?;
                     self.handle_data_for_network(public_key, maybe_data_for_network, &result_for_parent);
+                    self.update_connected_peers();
                 },
                 // periodically reporting what we are trying to do
                 _ = status_ticker.tick() => {
                     info!(target: LOG_TARGET, "Clique Network status: {}", self.manager.status_report());
+                    self.update_connected_peers();
                 }
                 // received exit signal, stop the network
                 // all workers will be killed automatically after the manager gets dropped
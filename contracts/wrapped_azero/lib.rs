@@ -0,0 +1,335 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+//! Wrapped AZERO: a PSP22 token backed 1:1 by the native tokens held by the contract.
+//!
+//! Anyone can wrap native tokens by calling `deposit` with some value attached and unwrap them
+//! again with `withdraw`, so other contracts can handle the native token through the same PSP22
+//! interface they use for any other asset.
+
+use ink::{
+    prelude::{string::String, vec::Vec},
+    primitives::AccountId,
+};
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    /// Custom error type for implementation-based errors.
+    Custom(String),
+    /// Returned when an account does not have enough tokens to complete the operation.
+    InsufficientBalance,
+    /// Returned if there is not enough allowance to complete the operation.
+    InsufficientAllowance,
+    /// Returned if recipient's address is zero.
+    ZeroRecipientAddress,
+    /// Returned if sender's address is zero.
+    ZeroSenderAddress,
+    /// Returned if a safe transfer check failed.
+    SafeTransferCheckFailed(String),
+}
+
+#[ink::trait_definition]
+pub trait PSP22 {
+    /// Returns the total token supply.
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    /// Returns the account balance for the specified `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u128;
+
+    /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> u128;
+
+    /// Transfers `value` tokens from the caller's account to `to`.
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    /// Transfers `value` tokens on behalf of `from` to `to`, using the caller's allowance.
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Allows `spender` to withdraw from the caller's account up to `value` tokens.
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: u128) -> Result<(), PSP22Error>;
+
+    /// Increases the allowance granted to `spender` by `delta_value`.
+    #[ink(message)]
+    fn increase_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: u128,
+    ) -> Result<(), PSP22Error>;
+
+    /// Decreases the allowance granted to `spender` by `delta_value`.
+    #[ink(message)]
+    fn decrease_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: u128,
+    ) -> Result<(), PSP22Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP22Metadata {
+    /// Returns the token name.
+    #[ink(message)]
+    fn token_name(&self) -> Option<String>;
+
+    /// Returns the token symbol.
+    #[ink(message)]
+    fn token_symbol(&self) -> Option<String>;
+
+    /// Returns the token decimals.
+    #[ink(message)]
+    fn token_decimals(&self) -> u8;
+}
+
+#[ink::contract]
+mod wrapped_azero {
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+
+    use crate::{PSP22Error, PSP22Metadata, PSP22};
+
+    /// Same as the native token.
+    pub const DECIMALS: u8 = 12;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct WrappedAzero {
+        total_supply: Balance,
+        balances: Mapping<AccountId, Balance>,
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+    }
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        amount: Balance,
+    }
+
+    impl WrappedAzero {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Wraps the native tokens sent with the call, minting the same amount to the caller.
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<(), PSP22Error> {
+            let value = self.env().transferred_value();
+            if value == 0 {
+                return Ok(());
+            }
+            let caller = self.env().caller();
+            self.total_supply = self
+                .total_supply
+                .checked_add(value)
+                .ok_or(PSP22Error::Custom(String::from("Total supply overflow")))?;
+            self.balances
+                .insert(caller, &self.balance_of_impl(caller).saturating_add(value));
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Burns `value` tokens of the caller and sends back the same amount of native tokens.
+        #[ink(message)]
+        pub fn withdraw(&mut self, value: Balance) -> Result<(), PSP22Error> {
+            if value == 0 {
+                return Ok(());
+            }
+            let caller = self.env().caller();
+            let balance = self.balance_of_impl(caller);
+            if balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            self.balances.insert(caller, &(balance - value));
+            self.total_supply -= value;
+            self.env().emit_event(Transfer {
+                from: Some(caller),
+                to: None,
+                value,
+            });
+            self.env()
+                .transfer(caller, value)
+                .map_err(|_| PSP22Error::Custom(String::from("Native transfer failed")))
+        }
+
+        fn balance_of_impl(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        fn allowance_impl(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        fn transfer_impl(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), PSP22Error> {
+            if from == to || value == 0 {
+                return Ok(());
+            }
+            let from_balance = self.balance_of_impl(from);
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value));
+            // Cannot overflow, as the sum of all balances is equal to the total supply.
+            self.balances
+                .insert(to, &self.balance_of_impl(to).saturating_add(value));
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        fn set_allowance(&mut self, owner: AccountId, spender: AccountId, amount: Balance) {
+            self.allowances.insert((owner, spender), &amount);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount,
+            });
+        }
+    }
+
+    impl PSP22 for WrappedAzero {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balance_of_impl(owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowance_impl(owner, spender)
+        }
+
+        #[ink(message)]
+        fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.transfer_impl(self.env().caller(), to, value)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let caller = self.env().caller();
+            if caller == from {
+                return self.transfer_impl(from, to, value);
+            }
+            let allowance = self.allowance_impl(from, caller);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.transfer_impl(from, to, value)?;
+            if value > 0 {
+                self.set_allowance(from, caller, allowance - value);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            if owner != spender {
+                self.set_allowance(owner, spender, value);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            if owner != spender && delta_value > 0 {
+                let amount = self
+                    .allowance_impl(owner, spender)
+                    .saturating_add(delta_value);
+                self.set_allowance(owner, spender, amount);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            if owner != spender && delta_value > 0 {
+                let allowance = self.allowance_impl(owner, spender);
+                if allowance < delta_value {
+                    return Err(PSP22Error::InsufficientAllowance);
+                }
+                self.set_allowance(owner, spender, allowance - delta_value);
+            }
+            Ok(())
+        }
+    }
+
+    impl PSP22Metadata for WrappedAzero {
+        #[ink(message)]
+        fn token_name(&self) -> Option<String> {
+            Some(String::from("Wrapped AZERO"))
+        }
+
+        #[ink(message)]
+        fn token_symbol(&self) -> Option<String> {
+            Some(String::from("wAZERO"))
+        }
+
+        #[ink(message)]
+        fn token_decimals(&self) -> u8 {
+            DECIMALS
+        }
+    }
+}
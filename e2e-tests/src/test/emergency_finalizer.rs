@@ -24,14 +24,14 @@ async fn set_emergency_finalizer_test() -> anyhow::Result<()> {
     let root = config.create_root_connection().await;
     let current_finalizer = root.as_connection().emergency_finalizer(None).await;
 
-    assert_ne!(current_finalizer, Some(public));
+    assert_ne!(current_finalizer, Some((vec![public], 1)));
 
-    root.set_emergency_finalizer(finalizer.public().into(), TxStatus::Finalized)
+    root.set_emergency_finalizer(vec![finalizer.public().into()], 1, TxStatus::Finalized)
         .await?;
     root.wait_for_n_sessions(2, BlockStatus::Finalized).await;
 
     let current_finalizer = root.as_connection().emergency_finalizer(None).await;
-    assert_eq!(current_finalizer, Some(public));
+    assert_eq!(current_finalizer, Some((vec![public], 1)));
     Ok(())
 }
 
@@ -57,7 +57,7 @@ async fn setup() -> anyhow::Result<AlephKeyPair> {
     let connection = config.create_root_connection().await;
 
     connection
-        .set_emergency_finalizer(finalizer.public().into(), TxStatus::Finalized)
+        .set_emergency_finalizer(vec![finalizer.public().into()], 1, TxStatus::Finalized)
         .await?;
     connection
         .wait_for_n_sessions(2, BlockStatus::Finalized)
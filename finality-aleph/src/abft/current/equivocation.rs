@@ -0,0 +1,231 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    io::Result as IoResult,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{channel::mpsc, AsyncWrite, StreamExt};
+use log::{debug, info, warn};
+use parity_scale_codec::Decode;
+
+use crate::{
+    abft::current::UncheckedSignedUnit,
+    aleph_primitives::{AuthoritySignature, EquivocationProof, UnitCoord},
+    block::UnverifiedHeader,
+    party::backup::Saver,
+    runtime_api::RuntimeApi,
+    SessionId,
+};
+
+const LOG_TARGET: &str = "aleph-party";
+/// Length of the encoded signature closing every encoded signed unit.
+const SIGNATURE_LENGTH: usize = 64;
+/// Bytes that did not decode into a unit are dropped once there are more of them than this, as
+/// AlephBFT writes whole units to the backup.
+const MAX_PENDING_BYTES: usize = 1024 * 1024;
+
+/// Splits an encoded signed unit into the encoded unit and the signature of its creator.
+fn split_signed_unit(encoded: &[u8]) -> Option<(Vec<u8>, AuthoritySignature)> {
+    let unit_length = encoded.len().checked_sub(SIGNATURE_LENGTH)?;
+    let signature = AuthoritySignature::decode(&mut &encoded[unit_length..]).ok()?;
+    Some((encoded[..unit_length].to_vec(), signature))
+}
+
+/// Remembers the units of a session and finds pairs of different units created by the same
+/// authority for the same round.
+#[derive(Default)]
+struct EquivocationDetector {
+    units: HashMap<(u64, u16), (Vec<u8>, AuthoritySignature)>,
+    reported: HashSet<u64>,
+}
+
+impl EquivocationDetector {
+    /// Returns a proof of equivocation if the encoded signed unit conflicts with a unit seen
+    /// before. Every offender is reported at most once.
+    fn on_unit(&mut self, encoded: &[u8]) -> Option<EquivocationProof<AuthoritySignature>> {
+        let (unit, signature) = split_signed_unit(encoded)?;
+        let coord = UnitCoord::from_encoded_unit(&unit)?;
+        if self.reported.contains(&coord.creator) {
+            return None;
+        }
+        match self.units.entry((coord.creator, coord.round)) {
+            Entry::Vacant(entry) => {
+                entry.insert((unit, signature));
+                None
+            }
+            Entry::Occupied(entry) if entry.get().0 == unit => None,
+            Entry::Occupied(entry) => {
+                self.reported.insert(coord.creator);
+                let (first_unit, first_signature) = entry.get().clone();
+                Some(EquivocationProof {
+                    first_unit,
+                    first_signature,
+                    second_unit: unit,
+                    second_signature: signature,
+                })
+            }
+        }
+    }
+}
+
+/// Submits the equivocation proofs found by the saver to the chain, away from the write path of
+/// the backup. Runs until the saver is dropped.
+pub struct EquivocationReporter<RA: RuntimeApi> {
+    proofs: mpsc::UnboundedReceiver<EquivocationProof<AuthoritySignature>>,
+    runtime_api: RA,
+}
+
+impl<RA: RuntimeApi> EquivocationReporter<RA> {
+    pub async fn run(mut self) {
+        while let Some(proof) = self.proofs.next().await {
+            if let Err(e) = self.runtime_api.submit_equivocation_report(proof) {
+                debug!(target: LOG_TARGET, "Failed to submit an equivocation report: {}.", e);
+            }
+        }
+    }
+}
+
+/// Wraps the saver of the AlephBFT backup, decoding the units saved to it and passing a proof to
+/// the reporter whenever two of them show an equivocation.
+pub struct EquivocationReportingSaver<UH: UnverifiedHeader> {
+    inner: Saver,
+    pending: Vec<u8>,
+    detector: EquivocationDetector,
+    session_id: SessionId,
+    proofs_for_reporter: mpsc::UnboundedSender<EquivocationProof<AuthoritySignature>>,
+    _phantom: PhantomData<fn() -> UH>,
+}
+
+// None of the fields is ever pinned, the inner saver is boxed.
+impl<UH: UnverifiedHeader> Unpin for EquivocationReportingSaver<UH> {}
+
+impl<UH: UnverifiedHeader> EquivocationReportingSaver<UH> {
+    pub fn new<RA: RuntimeApi>(
+        inner: Saver,
+        session_id: SessionId,
+        runtime_api: RA,
+    ) -> (Self, EquivocationReporter<RA>) {
+        let (proofs_for_reporter, proofs) = mpsc::unbounded();
+        (
+            EquivocationReportingSaver {
+                inner,
+                pending: Vec::new(),
+                detector: EquivocationDetector::default(),
+                session_id,
+                proofs_for_reporter,
+                _phantom: PhantomData,
+            },
+            EquivocationReporter {
+                proofs,
+                runtime_api,
+            },
+        )
+    }
+
+    fn on_written(&mut self, written: &[u8]) {
+        self.pending.extend_from_slice(written);
+        loop {
+            let mut input = &self.pending[..];
+            if UncheckedSignedUnit::<UH>::decode(&mut input).is_err() {
+                break;
+            }
+            let consumed = self.pending.len() - input.len();
+            let encoded: Vec<u8> = self.pending.drain(..consumed).collect();
+            if let Some(proof) = self.detector.on_unit(&encoded) {
+                self.report(proof);
+            }
+        }
+        if self.pending.len() > MAX_PENDING_BYTES {
+            warn!(target: LOG_TARGET, "Dropping {} bytes of the backup of session {:?} that do not decode into a unit.", self.pending.len(), self.session_id);
+            self.pending.clear();
+        }
+    }
+
+    fn report(&self, proof: EquivocationProof<AuthoritySignature>) {
+        let offender = UnitCoord::from_encoded_unit(&proof.first_unit).map(|coord| coord.creator);
+        info!(target: LOG_TARGET, "Found an equivocation of node {:?} in session {:?}, reporting it.", offender, self.session_id);
+        if self.proofs_for_reporter.unbounded_send(proof).is_err() {
+            debug!(target: LOG_TARGET, "Equivocation reporter of session {:?} has stopped.", self.session_id);
+        }
+    }
+}
+
+impl<UH: UnverifiedHeader> AsyncWrite for EquivocationReportingSaver<UH> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+        let this = self.get_mut();
+        let result = this.inner.as_mut().poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = result {
+            this.on_written(&buf[..written]);
+        }
+        result
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.get_mut().inner.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.get_mut().inner.as_mut().poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parity_scale_codec::{DecodeAll, Encode};
+
+    use super::{split_signed_unit, EquivocationDetector};
+    use crate::{aleph_primitives::UnitCoord, CurrentBackupUnit};
+
+    const CREATOR: u64 = 3;
+    const ROUND: u16 = 7;
+    const SESSION: u64 = 42;
+
+    // Encodes a signed unit of a committee of four, with the first three nodes as parents and no
+    // data, by hand, so that a change of the AlephBFT encoding breaks the tests below.
+    fn encoded_signed_unit(combined_hash: u8, signature: u8) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        // pre-unit: creator, round and control hash
+        CREATOR.encode_to(&mut encoded);
+        ROUND.encode_to(&mut encoded);
+        4u32.encode_to(&mut encoded);
+        vec![0b0111u32].encode_to(&mut encoded);
+        [combined_hash; 32].encode_to(&mut encoded);
+        // no data
+        None::<()>.encode_to(&mut encoded);
+        SESSION.encode_to(&mut encoded);
+        [signature; 64].encode_to(&mut encoded);
+        encoded
+    }
+
+    #[test]
+    fn reads_coordinates_of_aleph_bft_units() {
+        let encoded = encoded_signed_unit(1, 2);
+        let unit = CurrentBackupUnit::decode_all(&mut &encoded[..])
+            .expect("the layout should match the one of AlephBFT");
+        assert_eq!(unit.encode(), encoded);
+        let (unit, _) = split_signed_unit(&encoded).expect("the unit is signed");
+        assert_eq!(
+            UnitCoord::from_encoded_unit(&unit),
+            Some(UnitCoord {
+                creator: CREATOR,
+                round: ROUND,
+                session: SESSION,
+            })
+        );
+    }
+
+    #[test]
+    fn detects_equivocation_once() {
+        let mut detector = EquivocationDetector::default();
+        let first = encoded_signed_unit(1, 2);
+        let second = encoded_signed_unit(3, 4);
+        assert!(detector.on_unit(&first).is_none());
+        assert!(detector.on_unit(&first).is_none());
+        let proof = detector.on_unit(&second).expect("the units conflict");
+        assert_eq!(proof.first_unit, split_signed_unit(&first).unwrap().0);
+        assert_eq!(proof.second_unit, split_signed_unit(&second).unwrap().0);
+        assert!(detector.on_unit(&encoded_signed_unit(5, 6)).is_none());
+    }
+}
@@ -4,10 +4,12 @@ use current_aleph_bft::{create_config, default_delay_config, Config, LocalIO, Te
 use log::debug;
 use network_clique::SpawnHandleExt;
 
+mod equivocation;
 mod network;
 mod performance;
 mod traits;
 
+pub use equivocation::EquivocationReportingSaver;
 pub use network::NetworkData;
 pub use performance::Service as PerformanceService;
 
@@ -29,6 +31,10 @@ use crate::{
     CurrentNetworkData, Hasher, Keychain, NodeIndex, SessionId, SignatureSet, UnitCreationDelay,
 };
 
+/// A unit of the current version of AlephBFT, as stored in the backups.
+pub type UncheckedSignedUnit<UH> =
+    current_aleph_bft::UncheckedSignedUnit<Hasher, AlephData<UH>, Signature>;
+
 type WrappedNetwork<H, ADN> = NetworkWrapper<
     current_aleph_bft::NetworkData<Hasher, AlephData<H>, Signature, SignatureSet<Signature>>,
     ADN,
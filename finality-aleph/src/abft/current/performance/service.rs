@@ -1,21 +1,31 @@
+use std::collections::HashMap;
+
 use current_aleph_bft::NodeCount;
 use futures::{
     channel::{mpsc, oneshot},
     StreamExt,
 };
 use log::{debug, error, warn};
+use parity_scale_codec::Encode;
+use sp_runtime::traits::{BlakeTwo256, Hash as _};
 
 use crate::{
     abft::{
         current::performance::{scorer::Scorer, Batch},
-        LOG_TARGET,
+        SignatureSet, LOG_TARGET,
     },
+    aleph_primitives::{Hash, RawScore, Score, ScoreNonce},
+    crypto::Signature,
     data_io::AlephData,
-    metrics::ScoreMetrics,
+    metrics::{ParticipationMetrics, ScoreMetrics},
     party::manager::Runnable,
-    Hasher, UnverifiedHeader,
+    runtime_api::RuntimeApi,
+    Hasher, NodeIndex, SessionId, UnverifiedHeader,
 };
 
+/// Every how many batches we attempt to submit a score to the chain.
+const SCORE_SUBMISSION_PERIOD: usize = 300;
+
 struct FinalizationWrapper<UH, FH>
 where
     UH: UnverifiedHeader,
@@ -59,27 +69,47 @@ where
 }
 
 /// A service computing the performance score of ABFT nodes based on batches of ordered units.
-pub struct Service<UH>
+/// Every now and then it sends the hash of a score to the aggregator and, once the hash gets
+/// multisigned by the committee, submits the score to the chain.
+pub struct Service<UH, RA>
 where
     UH: UnverifiedHeader,
+    RA: RuntimeApi,
 {
     my_index: usize,
+    session_id: SessionId,
     batches_from_abft: mpsc::UnboundedReceiver<Batch<UH>>,
+    hashes_for_aggregator: mpsc::UnboundedSender<Hash>,
+    multisigned_hashes_from_aggregator: mpsc::UnboundedReceiver<(Hash, SignatureSet<Signature>)>,
+    runtime_api: RA,
+    pending_scores: HashMap<Hash, Score>,
+    nonce: ScoreNonce,
     scorer: Scorer,
     metrics: ScoreMetrics,
+    participation_metrics: ParticipationMetrics,
 }
 
-impl<UH> Service<UH>
+impl<UH, RA> Service<UH, RA>
 where
     UH: UnverifiedHeader,
+    RA: RuntimeApi,
 {
     /// Create a new service, together with a unit finalization handler that should be passed to
     /// ABFT. It will wrap the provided finalization handler and call it in the background.
+    #[allow(clippy::too_many_arguments)]
     pub fn new<FH>(
         my_index: usize,
         n_members: usize,
+        session_id: SessionId,
         finalization_handler: FH,
+        hashes_for_aggregator: mpsc::UnboundedSender<Hash>,
+        multisigned_hashes_from_aggregator: mpsc::UnboundedReceiver<(
+            Hash,
+            SignatureSet<Signature>,
+        )>,
+        runtime_api: RA,
         metrics: ScoreMetrics,
+        participation_metrics: ParticipationMetrics,
     ) -> (
         Self,
         impl current_aleph_bft::UnitFinalizationHandler<Data = AlephData<UH>, Hasher = Hasher>,
@@ -91,26 +121,81 @@ where
         (
             Service {
                 my_index,
+                session_id,
                 batches_from_abft,
+                hashes_for_aggregator,
+                multisigned_hashes_from_aggregator,
+                runtime_api,
+                pending_scores: HashMap::new(),
+                nonce: 1,
                 scorer: Scorer::new(NodeCount(n_members)),
                 metrics,
+                participation_metrics,
             },
             FinalizationWrapper::new(finalization_handler, batches_for_us),
         )
     }
+
+    fn make_score(&mut self, points: RawScore) -> Score {
+        let score = Score {
+            session_id: self.session_id.0,
+            nonce: self.nonce,
+            points,
+        };
+        self.nonce += 1;
+        score
+    }
+
+    fn send_for_aggregation(&mut self, points: RawScore) {
+        let score = self.make_score(points);
+        // This has to match the message the pallet verifies the multisignature against.
+        let hash = BlakeTwo256::hash_of(&score.encode());
+        debug!(target: LOG_TARGET, "Gathering multisignature under ABFT score with nonce {}.", score.nonce);
+        self.pending_scores.insert(hash, score);
+        if let Err(e) = self.hashes_for_aggregator.unbounded_send(hash) {
+            warn!(target: LOG_TARGET, "Failed to send ABFT score hash to the aggregator: {}.", e);
+        }
+    }
+
+    fn report_participation(&self, batch: &Batch<UH>) {
+        for unit in batch {
+            self.participation_metrics
+                .report_unit_ordered(NodeIndex(unit.creator.0), unit.creator.0 == self.my_index);
+        }
+    }
+
+    fn submit_score(&mut self, hash: Hash, signature: SignatureSet<Signature>) {
+        let score = match self.pending_scores.remove(&hash) {
+            Some(score) => score,
+            None => {
+                debug!(target: LOG_TARGET, "Received multisignature under unknown ABFT score hash {:?}.", hash);
+                return;
+            }
+        };
+        debug!(target: LOG_TARGET, "Submitting ABFT score with nonce {}.", score.nonce);
+        if let Err(e) = self.runtime_api.submit_abft_score(score, signature) {
+            warn!(target: LOG_TARGET, "Failed to submit ABFT score: {}.", e);
+        }
+    }
 }
 
 #[async_trait::async_trait]
-impl<UH> Runnable for Service<UH>
+impl<UH, RA> Runnable for Service<UH, RA>
 where
     UH: UnverifiedHeader,
+    RA: RuntimeApi,
 {
     async fn run(mut self, mut exit: oneshot::Receiver<()>) {
+        let mut batch_counter: usize = 1;
+        let mut no_more_multisigned_hashes = false;
         loop {
             tokio::select! {
                 maybe_batch = self.batches_from_abft.next() => {
                     let score = match maybe_batch {
-                        Some(batch) => self.scorer.process_batch(batch),
+                        Some(batch) => {
+                            self.report_participation(&batch);
+                            self.scorer.process_batch(batch)
+                        },
                         None => {
                             error!(target: LOG_TARGET, "Batches' channel closed, ABFT performance scoring terminating.");
                             break;
@@ -118,8 +203,18 @@ where
                     };
                     debug!(target: LOG_TARGET, "Received ABFT score: {:?}.", score);
                     self.metrics.report_score(score[self.my_index]);
-                    // TODO(A0-4339): sometimes submit these scores to the chain.
+                    if batch_counter % SCORE_SUBMISSION_PERIOD == 0 {
+                        self.send_for_aggregation(score);
+                    }
+                    batch_counter = batch_counter.wrapping_add(1);
                 }
+                maybe_multisigned = self.multisigned_hashes_from_aggregator.next(), if !no_more_multisigned_hashes => match maybe_multisigned {
+                    Some((hash, signature)) => self.submit_score(hash, signature),
+                    None => {
+                        debug!(target: LOG_TARGET, "Multisigned hashes' channel closed, ABFT scores will no longer be submitted.");
+                        no_more_multisigned_hashes = true;
+                    }
+                },
                 _ = &mut exit => {
                     debug!(target: LOG_TARGET, "ABFT performance scoring task received exit signal. Terminating.");
                     break;
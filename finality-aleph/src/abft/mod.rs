@@ -20,8 +20,9 @@ use aleph_bft_crypto::{PartialMultisignature, Signature as AbftSignature};
 pub use crypto::Keychain;
 pub use current::{
     create_aleph_config as current_create_aleph_config, run_member as run_current_member,
+    EquivocationReportingSaver as CurrentEquivocationReportingSaver,
     NetworkData as CurrentNetworkData, PerformanceService as CurrentPerformanceService,
-    VERSION as CURRENT_VERSION,
+    UncheckedSignedUnit as CurrentUncheckedSignedUnit, VERSION as CURRENT_VERSION,
 };
 pub use legacy::{
     create_aleph_config as legacy_create_aleph_config, run_member as run_legacy_member,
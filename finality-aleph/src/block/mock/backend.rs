@@ -12,7 +12,8 @@ use crate::{
         mock::{MockBlock, MockHeader, MockJustification, MockNotification},
         Block, BlockImport, BlockStatus, ChainStatus, ChainStatusNotifier,
         EquivocationProof as EquivocationProofT, FinalizationStatus, Finalizer, Header,
-        HeaderVerifier, Justification as JustificationT, JustificationVerifier, VerifiedHeader,
+        HeaderVerifier, Justification as JustificationT, JustificationVerifier, VerificationError,
+        VerifiedHeader,
     },
     nodes::VERIFIER_CACHE_SIZE,
     session::{SessionBoundaryInfo, SessionId},
@@ -410,6 +411,12 @@ impl Display for VerifierError {
     }
 }
 
+impl VerificationError for VerifierError {
+    fn proves_invalid(&self) -> bool {
+        !matches!(self, VerifierError::Session)
+    }
+}
+
 impl Backend {
     fn cached(&self, block_number: BlockNumber) -> Result<(), VerifierError> {
         let top_number = self
@@ -82,9 +82,16 @@ pub trait Justification: Clone + Send + Sync + Debug + 'static {
     fn into_unverified(self) -> Self::Unverified;
 }
 
+/// An error returned by a verifier.
+pub trait VerificationError: Display + Debug {
+    /// Whether the error proves the verified data is invalid, rather than only that it cannot be
+    /// verified at the moment, e.g. because it comes from a session we know nothing about yet.
+    fn proves_invalid(&self) -> bool;
+}
+
 /// A verifier of justifications.
 pub trait JustificationVerifier<J: Justification> {
-    type Error: Display + Debug;
+    type Error: VerificationError;
 
     /// Verifies the raw justification and returns a full justification if successful, otherwise an
     /// error.
@@ -106,7 +113,7 @@ pub struct VerifiedHeader<H: Header, P: EquivocationProof> {
 /// A verifier of headers.
 pub trait HeaderVerifier<H: Header>: Clone + Send + Sync + 'static {
     type EquivocationProof: EquivocationProof;
-    type Error: Display + Debug;
+    type Error: VerificationError;
 
     /// Verifies the raw header and returns a struct containing a full header and possibly
     /// an equivocation proof if successful, otherwise an error.
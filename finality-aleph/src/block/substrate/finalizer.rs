@@ -1,3 +1,4 @@
+use log::debug;
 use sc_client_api::{Backend, Finalizer as SubstrateFinalizer, HeaderBackend, LockImportRun};
 use sp_blockchain::Error as ClientError;
 use sp_runtime::traits::Header as SubstrateHeader;
@@ -5,10 +6,12 @@ use sp_runtime::traits::Header as SubstrateHeader;
 use crate::{
     aleph_primitives::Block,
     block::{
-        substrate::{InnerJustification, Justification},
-        Finalizer,
+        substrate::{InnerJustification, Justification, LOG_TARGET},
+        Finalizer, Header,
     },
     finalization::{AlephFinalizer, BlockFinalizer},
+    justification::AlephJustification,
+    runtime_api::RuntimeApi,
 };
 
 impl<BE, C> Finalizer<Justification> for AlephFinalizer<Block, BE, C>
@@ -31,3 +34,55 @@ where
         }
     }
 }
+
+/// Finalizes blocks and submits the emergency finalizations to be noted on chain.
+pub struct EmergencyFinalizationSubmitter<F, RA>
+where
+    F: Finalizer<Justification>,
+    RA: RuntimeApi,
+{
+    finalizer: F,
+    runtime_api: RA,
+}
+
+impl<F, RA> EmergencyFinalizationSubmitter<F, RA>
+where
+    F: Finalizer<Justification>,
+    RA: RuntimeApi,
+{
+    pub fn new(finalizer: F, runtime_api: RA) -> Self {
+        EmergencyFinalizationSubmitter {
+            finalizer,
+            runtime_api,
+        }
+    }
+}
+
+impl<F, RA> Finalizer<Justification> for EmergencyFinalizationSubmitter<F, RA>
+where
+    F: Finalizer<Justification>,
+    RA: RuntimeApi,
+{
+    type Error = F::Error;
+
+    fn finalize(&self, justification: Justification) -> Result<(), Self::Error> {
+        let emergency_finalization = match &justification.inner_justification {
+            InnerJustification::AlephJustification(
+                aleph_justification @ (AlephJustification::EmergencySignature(_)
+                | AlephJustification::EmergencyMultisignature(_)),
+            ) => Some((justification.header.id(), aleph_justification.clone())),
+            _ => None,
+        };
+        self.finalizer.finalize(justification)?;
+        if let Some((block, aleph_justification)) = emergency_finalization {
+            debug!(target: LOG_TARGET, "Submitting emergency finalization of {:?}.", block);
+            if let Err(e) = self
+                .runtime_api
+                .submit_emergency_finalization(block, aleph_justification)
+            {
+                debug!(target: LOG_TARGET, "Failed to submit emergency finalization: {}.", e);
+            }
+        }
+        Ok(())
+    }
+}
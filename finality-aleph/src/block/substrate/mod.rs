@@ -16,11 +16,12 @@ mod status_notifier;
 mod verification;
 
 pub use chain_status::SubstrateChainStatus;
+pub use finalizer::EmergencyFinalizationSubmitter;
 pub use justification::{
     InnerJustification, Justification, JustificationTranslator, TranslateError,
 };
 pub use status_notifier::SubstrateChainStatusNotifier;
-pub use verification::{SubstrateFinalizationInfo, VerifierCache};
+pub use verification::{SubstrateFinalizationInfo, VerificationError, VerifierCache};
 
 use crate::{
     block::{BestBlockSelector, BlockchainEvents},
@@ -16,6 +16,7 @@ use crate::{
             FinalizationInfo,
         },
         EquivocationProof as EquivocationProofT, Header as HeaderT,
+        VerificationError as VerificationErrorT,
     },
 };
 
@@ -145,6 +146,31 @@ impl From<HeaderVerificationError> for VerificationError {
     }
 }
 
+impl VerificationErrorT for VerificationError {
+    fn proves_invalid(&self) -> bool {
+        use HeaderVerificationError::*;
+        use VerificationError::*;
+        match self {
+            Verification(_)
+            | Cache(CacheError::BadGenesisHeader)
+            | HeaderVerification(
+                PreDigestLookupError(_)
+                | IncorrectGenesis
+                | MissingSeal
+                | IncorrectSeal
+                | IncorrectAuthority,
+            ) => true,
+            Cache(
+                CacheError::UnknownAuthorities(_)
+                | CacheError::UnknownAuraAuthorities(_)
+                | CacheError::SessionTooOld(_, _)
+                | CacheError::SessionInFuture(_, _),
+            )
+            | HeaderVerification(HeaderTooNew(_) | MissingAuthorityData) => false,
+        }
+    }
+}
+
 impl Display for VerificationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         use VerificationError::*;
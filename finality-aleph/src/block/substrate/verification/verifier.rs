@@ -3,22 +3,24 @@ use std::fmt::{Display, Error as FmtError, Formatter};
 use sp_runtime::RuntimeAppPublic;
 
 use crate::{
-    aleph_primitives::SessionAuthorityData, crypto::AuthorityVerifier,
-    justification::AlephJustification, AuthorityId,
+    aleph_primitives::{EmergencyFinalizerSet, SessionAuthorityData},
+    crypto::AuthorityVerifier,
+    justification::AlephJustification,
+    AuthorityId,
 };
 
 /// A justification verifier within a single session.
 #[derive(Clone, PartialEq, Debug)]
 pub struct SessionVerifier {
     authority_verifier: AuthorityVerifier,
-    emergency_signer: Option<AuthorityId>,
+    emergency_signers: Option<EmergencyFinalizerSet<AuthorityId>>,
 }
 
 impl From<SessionAuthorityData> for SessionVerifier {
     fn from(authority_data: SessionAuthorityData) -> Self {
         SessionVerifier {
             authority_verifier: AuthorityVerifier::new(authority_data.authorities().to_vec()),
-            emergency_signer: authority_data.emergency_finalizer().clone(),
+            emergency_signers: authority_data.emergency_finalizers().clone(),
         }
     }
 }
@@ -58,15 +60,105 @@ impl SessionVerifier {
                     false => Err(BadMultisignature),
                 }
             }
-            EmergencySignature(signature) => match self
-                .emergency_signer
-                .as_ref()
-                .ok_or(NoEmergencySigner)?
-                .verify(&bytes, signature)
-            {
-                true => Ok(()),
-                false => Err(BadEmergencySignature),
-            },
+            EmergencySignature(signature) => {
+                let emergency_signers = self.emergency_signers.as_ref().ok_or(NoEmergencySigner)?;
+                match emergency_signers.threshold() == 1
+                    && emergency_signers
+                        .keys()
+                        .iter()
+                        .any(|signer| signer.verify(&bytes, signature))
+                {
+                    true => Ok(()),
+                    false => Err(BadEmergencySignature),
+                }
+            }
+            EmergencyMultisignature(multisignature) => {
+                match self
+                    .emergency_signers
+                    .as_ref()
+                    .ok_or(NoEmergencySigner)?
+                    .is_complete(&bytes, &multisignature.clone().into())
+                {
+                    true => Ok(()),
+                    false => Err(BadEmergencySignature),
+                }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use sp_core::Pair;
+
+    use super::{SessionVerificationError, SessionVerifier};
+    use crate::{
+        aleph_primitives::{AuthorityPair, EmergencyFinalizerSet, SessionAuthorityData},
+        crypto::Signature,
+        justification::AlephJustification,
+        SignatureSet,
+    };
+
+    const MSG: [u8; 4] = [1, 2, 3, 4];
+
+    fn pairs(count: usize) -> Vec<AuthorityPair> {
+        (0..count).map(|_| AuthorityPair::generate().0).collect()
+    }
+
+    fn verifier(pairs: &[AuthorityPair], threshold: u32) -> SessionVerifier {
+        let keys = pairs.iter().map(|pair| pair.public()).collect();
+        SessionAuthorityData::new(Vec::new(), EmergencyFinalizerSet::new(keys, threshold)).into()
+    }
+
+    fn emergency_multisignature(pairs: &[AuthorityPair], signers: &[usize]) -> AlephJustification {
+        let signatures = signers.iter().fold(
+            SignatureSet::with_size(pairs.len().into()),
+            |signatures, signer| {
+                let signature: Signature = pairs[*signer].sign(&MSG).into();
+                signatures.add_signature(&signature, (*signer).into())
+            },
+        );
+        AlephJustification::EmergencyMultisignature(signatures)
+    }
+
+    #[test]
+    fn accepts_threshold_of_emergency_signatures() {
+        let pairs = pairs(3);
+        let verifier = verifier(&pairs, 2);
+
+        assert_eq!(
+            verifier.verify_bytes(&emergency_multisignature(&pairs, &[0, 2]), MSG.to_vec()),
+            Ok(())
+        );
+        assert_eq!(
+            verifier.verify_bytes(&emergency_multisignature(&pairs, &[1]), MSG.to_vec()),
+            Err(SessionVerificationError::BadEmergencySignature)
+        );
+    }
+
+    #[test]
+    fn single_emergency_signature_requires_threshold_one() {
+        let pairs = pairs(3);
+        let justification = AlephJustification::EmergencySignature(pairs[1].sign(&MSG));
+
+        assert_eq!(
+            verifier(&pairs, 1).verify_bytes(&justification, MSG.to_vec()),
+            Ok(())
+        );
+        assert_eq!(
+            verifier(&pairs, 2).verify_bytes(&justification, MSG.to_vec()),
+            Err(SessionVerificationError::BadEmergencySignature)
+        );
+    }
+
+    #[test]
+    fn rejects_emergency_signatures_without_signers() {
+        let pairs = pairs(1);
+        let verifier: SessionVerifier = SessionAuthorityData::new(Vec::new(), None).into();
+
+        assert_eq!(
+            verifier.verify_bytes(&emergency_multisignature(&pairs, &[0]), MSG.to_vec()),
+            Err(SessionVerificationError::NoEmergencySigner)
+        );
+    }
+}
@@ -1,38 +1,77 @@
 use std::{
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
+    sync::Arc,
 };
 
 use futures::channel::mpsc::{self, TrySendError, UnboundedReceiver, UnboundedSender};
 use log::{debug, warn};
+use pallet_aleph_runtime_api::AlephSessionApi;
+use parking_lot::Mutex;
+use sc_client_api::Backend;
 use sc_consensus::{
     BlockCheckParams, BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult,
     JustificationImport,
 };
 use sp_consensus::{Error as ConsensusError, SelectChain};
+use sp_consensus_aura::AuraApi;
 use sp_runtime::{traits::Header as HeaderT, Justification as SubstrateJustification};
 
 use crate::{
-    aleph_primitives::{Block, BlockHash, BlockNumber, ALEPH_ENGINE_ID},
-    block::substrate::{Justification, JustificationTranslator, TranslateError},
+    aleph_primitives::{AuraId, Block, BlockHash, BlockNumber, Header, ALEPH_ENGINE_ID},
+    block::{
+        substrate::{
+            Justification, JustificationTranslator, SubstrateFinalizationInfo, TranslateError,
+            VerificationError, VerifierCache,
+        },
+        JustificationVerifier, VerificationError as _,
+    },
     justification::{backwards_compatible_decode, DecodeError},
-    BlockId,
+    nodes::VERIFIER_CACHE_SIZE,
+    runtime_api::RuntimeApiImpl,
+    session::SessionBoundaryInfo,
+    session_map::{AuthorityProviderImpl, FinalizedBlockProviderImpl},
+    BlockId, ClientForAleph, SessionPeriod,
 };
 
 /// Constructs block import specific for aleph consensus.
-pub fn get_aleph_block_import<I, SC>(
+pub fn get_aleph_block_import<I, SC, C, BE>(
     inner: I,
     justification_tx: UnboundedSender<Justification>,
     translator: JustificationTranslator,
     select_chain: SC,
+    client: Arc<C>,
+    session_period: SessionPeriod,
 ) -> impl BlockImport<Block, Error = I::Error> + JustificationImport<Block, Error = ConsensusError> + Clone
 where
     I: BlockImport<Block> + Send + Sync + Clone,
     SC: SelectChain<Block> + Send + Sync,
+    C: ClientForAleph<Block, BE> + Send + Sync + 'static,
+    C::Api: AlephSessionApi<Block> + AuraApi<Block, AuraId>,
+    BE: Backend<Block> + 'static,
 {
     let favourite_marker_import = FavouriteMarkerBlockImport::new(inner, select_chain);
 
-    AlephBlockImport::new(favourite_marker_import, justification_tx, translator)
+    let session_info = SessionBoundaryInfo::new(session_period);
+    let genesis_header = sp_blockchain::HeaderBackend::header(&*client, client.info().genesis_hash)
+        .ok()
+        .flatten()
+        .expect("the genesis block should be present");
+    let justification_verifier = VerifierCache::new(
+        session_info.clone(),
+        SubstrateFinalizationInfo::new(client.clone()),
+        AuthorityProviderImpl::new(client.clone(), RuntimeApiImpl::new(client.clone())),
+        FinalizedBlockProviderImpl::new(SubstrateFinalizationInfo::new(client), session_info),
+        VERIFIER_CACHE_SIZE,
+        genesis_header,
+    );
+
+    AlephBlockImport::new(
+        favourite_marker_import,
+        justification_tx,
+        translator,
+        justification_verifier,
+    )
 }
 
 /// A wrapper around a block import that also checks if the newly imported block is potentially
@@ -91,14 +130,43 @@ where
 
 /// A wrapper around a block import that also extracts any present justifications and sends them to
 /// our components which will process them further and possibly finalize the block.
-#[derive(Clone)]
-pub struct AlephBlockImport<I>
+/// Blocks with attached justifications that are certainly bogus are rejected before being imported.
+pub struct AlephBlockImport<I, V>
 where
     I: BlockImport<Block> + Clone + Send,
+    V: JustificationVerifier<Justification, Error = VerificationError> + Send,
 {
     inner: I,
     justification_tx: UnboundedSender<Justification>,
     translator: JustificationTranslator,
+    justification_verifier: Arc<Mutex<V>>,
+}
+
+impl<I, V> Clone for AlephBlockImport<I, V>
+where
+    I: BlockImport<Block> + Clone + Send,
+    V: JustificationVerifier<Justification, Error = VerificationError> + Send,
+{
+    fn clone(&self) -> Self {
+        AlephBlockImport {
+            inner: self.inner.clone(),
+            justification_tx: self.justification_tx.clone(),
+            translator: self.translator.clone(),
+            justification_verifier: self.justification_verifier.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum PreVerificationError {
+    Decode(DecodeError),
+    Verification(VerificationError),
+}
+
+impl From<DecodeError> for PreVerificationError {
+    fn from(decode_error: DecodeError) -> Self {
+        Self::Decode(decode_error)
+    }
 }
 
 #[derive(Debug)]
@@ -115,19 +183,48 @@ impl<TE: Debug> From<DecodeError> for SendJustificationError<TE> {
     }
 }
 
-impl<I> AlephBlockImport<I>
+impl<I, V> AlephBlockImport<I, V>
 where
     I: BlockImport<Block> + Clone + Send,
+    V: JustificationVerifier<Justification, Error = VerificationError> + Send,
 {
     pub fn new(
         inner: I,
         justification_tx: UnboundedSender<Justification>,
         translator: JustificationTranslator,
-    ) -> AlephBlockImport<I> {
+        justification_verifier: V,
+    ) -> AlephBlockImport<I, V> {
         AlephBlockImport {
             inner,
             justification_tx,
             translator,
+            justification_verifier: Arc::new(Mutex::new(justification_verifier)),
+        }
+    }
+
+    /// Verifies a justification attached to a block that is about to be imported. Fails with
+    /// a verification error only if the justification is certainly bogus. If it cannot be verified
+    /// yet, e.g. because the authorities of its session are not known, it will be verified after
+    /// the import as usual. A justification that cannot be decoded says nothing about the block,
+    /// so it fails with a decoding error instead.
+    fn pre_verify_justification(
+        &self,
+        header: Header,
+        justification: &[u8],
+    ) -> Result<(), PreVerificationError> {
+        let aleph_justification = backwards_compatible_decode(justification.to_vec())?;
+        let justification = Justification::aleph_justification(header, aleph_justification);
+        match self
+            .justification_verifier
+            .lock()
+            .verify_justification(justification)
+        {
+            Ok(_) => Ok(()),
+            Err(e) if e.proves_invalid() => Err(PreVerificationError::Verification(e)),
+            Err(e) => {
+                debug!(target: "aleph-justification", "Could not pre-verify justification: {}.", e);
+                Ok(())
+            }
         }
     }
 
@@ -156,9 +253,10 @@ where
 }
 
 #[async_trait::async_trait]
-impl<I> BlockImport<Block> for AlephBlockImport<I>
+impl<I, V> BlockImport<Block> for AlephBlockImport<I, V>
 where
     I: BlockImport<Block> + Clone + Send,
+    V: JustificationVerifier<Justification, Error = VerificationError> + Send,
 {
     type Error = I::Error;
 
@@ -176,7 +274,23 @@ where
         let number = *block.header.number();
         let post_hash = block.post_hash();
 
-        let justifications = block.justifications.take();
+        let mut justifications = block.justifications.take();
+        if let Some(justification) = justifications
+            .as_ref()
+            .and_then(|just| just.get(ALEPH_ENGINE_ID))
+        {
+            match self.pre_verify_justification(block.post_header(), justification) {
+                Ok(()) => (),
+                Err(PreVerificationError::Verification(e)) => {
+                    warn!(target: "aleph-justification", "Rejecting block {:?} {:?} with a bogus justification: {}", number, post_hash, e);
+                    return Ok(ImportResult::KnownBad);
+                }
+                Err(PreVerificationError::Decode(e)) => {
+                    warn!(target: "aleph-justification", "Importing block {:?} {:?} without its justification, which could not be decoded: {:?}", number, post_hash, e);
+                    justifications = None;
+                }
+            }
+        }
 
         debug!(target: "aleph-justification", "Importing block {:?} {:?} {:?}", number, block.header.hash(), block.post_hash());
         let result = self.inner.import_block(block).await;
@@ -201,9 +315,10 @@ where
 }
 
 #[async_trait::async_trait]
-impl<I> JustificationImport<Block> for AlephBlockImport<I>
+impl<I, V> JustificationImport<Block> for AlephBlockImport<I, V>
 where
     I: BlockImport<Block> + Clone + Send,
+    V: JustificationVerifier<Justification, Error = VerificationError> + Send,
 {
     type Error = ConsensusError;
 
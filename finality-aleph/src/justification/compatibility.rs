@@ -292,7 +292,8 @@ mod test {
             Ok(AlephJustification::CommitteeMultisignature(signature)) => {
                 assert_eq!(signature.size(), NodeCount(expected_node_count))
             }
-            Ok(AlephJustification::EmergencySignature(_)) => {
+            Ok(AlephJustification::EmergencySignature(_))
+            | Ok(AlephJustification::EmergencyMultisignature(_)) => {
                 panic!("decoded V1 as emergency signature")
             }
             Err(e) => panic!("decoding V1 failed: {e}"),
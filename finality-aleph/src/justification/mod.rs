@@ -13,12 +13,15 @@ pub use compatibility::{backwards_compatible_decode, versioned_encode, Error as
 
 const LOG_TARGET: &str = "aleph-justification";
 
-/// A proof of block finality, currently in the form of a sufficiently long list of signatures or a
-/// sudo signature of a block for emergency finalization.
+/// A proof of block finality, currently in the form of a sufficiently long list of signatures or
+/// signatures of emergency finalizers of a block for emergency finalization.
 #[derive(Clone, Encode, Decode, Debug, PartialEq, Eq)]
 pub enum AlephJustification {
     CommitteeMultisignature(SignatureSet<Signature>),
+    /// A signature of any emergency finalizer, enough only when the threshold is one.
     EmergencySignature(AuthoritySignature),
+    /// Signatures of emergency finalizers, indexed by the position of their keys in the set.
+    EmergencyMultisignature(SignatureSet<Signature>),
 }
 
 impl From<AlephJustification> for Justification {
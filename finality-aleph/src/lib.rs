@@ -25,8 +25,8 @@ use tokio::time::Duration;
 
 use crate::{
     abft::{
-        CurrentNetworkData, Keychain, LegacyNetworkData, NodeCount, NodeIndex, Recipient,
-        SignatureSet, SpawnHandle, CURRENT_VERSION, LEGACY_VERSION,
+        CurrentNetworkData, CurrentUncheckedSignedUnit, Keychain, LegacyNetworkData, NodeCount,
+        NodeIndex, Recipient, SignatureSet, SpawnHandle, CURRENT_VERSION, LEGACY_VERSION,
     },
     aggregation::{CurrentRmcNetworkData, LegacyRmcNetworkData},
     block::UnverifiedHeader,
@@ -53,6 +53,7 @@ mod party;
 mod runtime_api;
 mod session;
 mod session_map;
+mod stall_detector;
 mod sync;
 mod sync_oracle;
 #[cfg(test)]
@@ -67,10 +68,15 @@ pub use crate::{
     justification::AlephJustification,
     network::{
         address_cache::{ValidatorAddressCache, ValidatorAddressingInfo},
-        build_network, BuildNetworkOutput, ProtocolNetwork, SubstrateNetworkConfig,
-        SubstratePeerId,
+        build_network,
+        connectivity::{CommitteeConnectivity, MemberConnectivity, ValidatorConnectivity},
+        BuildNetworkOutput, ProtocolNetwork, SubstrateNetworkConfig, SubstratePeerId,
     },
     nodes::run_validator_node,
+    party::backup::{
+        inspect_backups, truncate_broken_tail, BackupEncryptionKey, BackupFileReport,
+        BackupRetention, SessionBackupReport,
+    },
     session::SessionPeriod,
     sync::FavouriteSelectChainProvider,
     sync_oracle::SyncOracle,
@@ -85,6 +91,9 @@ pub struct MillisecsPerBlock(pub u64);
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd, Encode, Decode)]
 pub struct UnitCreationDelay(pub u64);
 
+/// A unit as stored in the AlephBFT backups of the node.
+pub type CurrentBackupUnit = CurrentUncheckedSignedUnit<aleph_primitives::Header>;
+
 type LegacySplitData<UH> = Split<LegacyNetworkData<UH>, LegacyRmcNetworkData>;
 type CurrentSplitData<UH> = Split<CurrentNetworkData<UH>, CurrentRmcNetworkData>;
 
@@ -253,8 +262,16 @@ type Hasher = abft::HashWrapper<BlakeTwo256>;
 pub struct RateLimiterConfig {
     /// Maximum bit-rate in bits per second of the alephbft validator network.
     pub alephbft_network_bit_rate: u64,
+    /// Maximum bit-rate in bits per second of a single peer of the alephbft validator network, unlimited if not set.
+    pub alephbft_per_peer_bit_rate: Option<u64>,
+    /// Maximum egress bit-rate in bits per second of the alephbft validator network, unlimited if not set.
+    pub alephbft_network_egress_bit_rate: Option<u64>,
+    /// Whether to loosen the alephbft validator network rate-limit when finalization falls behind.
+    pub alephbft_adaptive_bit_rate: bool,
     /// Maximum bit-rate in bits per second of the substrate network (shared by sync, gossip, etc.).
     pub substrate_network_bit_rate: u64,
+    /// Maximum egress bit-rate in bits per second of the substrate network, unlimited if not set.
+    pub substrate_network_egress_bit_rate: Option<u64>,
 }
 
 pub struct AlephConfig<C, T> {
@@ -273,10 +290,14 @@ pub struct AlephConfig<C, T> {
     pub millisecs_per_block: MillisecsPerBlock,
     pub unit_creation_delay: UnitCreationDelay,
     pub backup_saving_path: Option<PathBuf>,
+    pub backup_encryption_key: Option<BackupEncryptionKey>,
+    pub backup_retention: BackupRetention,
     pub external_addresses: Vec<String>,
     pub validator_port: u16,
     pub rate_limiter_config: RateLimiterConfig,
     pub sync_oracle: SyncOracle,
+    pub finality_stall_timeout: Option<Duration>,
     pub validator_address_cache: Option<ValidatorAddressCache>,
+    pub validator_connectivity: ValidatorConnectivity,
     pub transaction_pool: Arc<T>,
 }
@@ -1,11 +1,13 @@
 mod abft_score;
 mod best_block;
 mod finality_rate;
+mod participation;
 mod slo;
 mod timing;
 pub mod transaction_pool;
 
 pub use abft_score::ScoreMetrics;
+pub use participation::ParticipationMetrics;
 pub use slo::{run_metrics_service, SloMetrics};
 pub use timing::{Checkpoint, DefaultClock};
 pub type TimingBlockMetrics = timing::TimingBlockMetrics<DefaultClock>;
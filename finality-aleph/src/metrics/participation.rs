@@ -0,0 +1,134 @@
+use substrate_prometheus_endpoint::{
+    register, Counter, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64,
+};
+
+use crate::{NodeIndex, SessionId};
+
+/// Metrics describing how the node participates in the consensus of the current session.
+/// All of them are reset whenever a new session starts.
+#[derive(Clone)]
+pub enum ParticipationMetrics {
+    Prometheus {
+        session: Gauge<U64>,
+        in_committee: Gauge<U64>,
+        units_created: Gauge<U64>,
+        units_by_creator: GaugeVec<U64>,
+        multisignatures_collected: Gauge<U64>,
+        multisignatures_total: Counter<U64>,
+        my_index: Gauge<U64>,
+    },
+    Noop,
+}
+
+impl ParticipationMetrics {
+    pub fn new(registry: Option<&Registry>) -> Result<Self, PrometheusError> {
+        let registry = match registry {
+            Some(registry) => registry,
+            None => return Ok(ParticipationMetrics::Noop),
+        };
+        Ok(ParticipationMetrics::Prometheus {
+            session: register(
+                Gauge::new("aleph_participation_session", "Current session")?,
+                registry,
+            )?,
+            in_committee: register(
+                Gauge::new(
+                    "aleph_participation_in_committee",
+                    "Whether this node is a member of the committee of the current session",
+                )?,
+                registry,
+            )?,
+            units_created: register(
+                Gauge::new(
+                    "aleph_participation_units_created",
+                    "Number of units created by this node that got ordered in the current session",
+                )?,
+                registry,
+            )?,
+            units_by_creator: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "aleph_participation_units_by_creator",
+                        "Number of units received from each committee member that got ordered in the current session",
+                    ),
+                    &["creator"],
+                )?,
+                registry,
+            )?,
+            multisignatures_collected: register(
+                Gauge::new(
+                    "aleph_participation_multisignatures_collected",
+                    "Number of multisignatures collected by the aggregator in the current session",
+                )?,
+                registry,
+            )?,
+            multisignatures_total: register(
+                Counter::new(
+                    "aleph_participation_multisignatures_total",
+                    "Number of multisignatures collected by the aggregator in all sessions",
+                )?,
+                registry,
+            )?,
+            my_index: register(
+                Gauge::new(
+                    "aleph_participation_my_index",
+                    "Index of this node in the committee of the current session",
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    pub fn noop() -> Self {
+        ParticipationMetrics::Noop
+    }
+
+    /// Starts tracking a new session, in which this node has the given index in the committee, if any.
+    pub fn start_session(&self, session_id: SessionId, node_id: Option<NodeIndex>) {
+        if let ParticipationMetrics::Prometheus {
+            session,
+            in_committee,
+            units_created,
+            units_by_creator,
+            multisignatures_collected,
+            my_index,
+            ..
+        } = self
+        {
+            session.set(session_id.0.into());
+            in_committee.set(node_id.is_some().into());
+            my_index.set(node_id.map(|id| id.0 as u64).unwrap_or_default());
+            units_created.set(0);
+            units_by_creator.reset();
+            multisignatures_collected.set(0);
+        }
+    }
+
+    pub fn report_unit_ordered(&self, creator: NodeIndex, is_mine: bool) {
+        if let ParticipationMetrics::Prometheus {
+            units_created,
+            units_by_creator,
+            ..
+        } = self
+        {
+            units_by_creator
+                .with_label_values(&[&creator.0.to_string()])
+                .inc();
+            if is_mine {
+                units_created.inc();
+            }
+        }
+    }
+
+    pub fn report_multisignature(&self) {
+        if let ParticipationMetrics::Prometheus {
+            multisignatures_collected,
+            multisignatures_total,
+            ..
+        } = self
+        {
+            multisignatures_collected.inc();
+            multisignatures_total.inc();
+        }
+    }
+}
@@ -14,7 +14,7 @@ use crate::{
 pub struct ValidatorAddressingInfo {
     /// Session to which given information applies.
     pub session: SessionId,
-    /// Network level address of the validator, i.e. IP address (for validator network)
+    /// Network level address of the validator, i.e. IP address or DNS name (for validator network)
     pub network_level_address: String,
     /// PeerId of the validator used in validator (clique) network
     pub validator_network_peer_id: String,
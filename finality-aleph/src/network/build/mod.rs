@@ -1,7 +1,7 @@
 use std::sync::{atomic::AtomicBool, Arc};
 
 use log::error;
-use rate_limiter::SharedRateLimiter;
+use rate_limiter::{RateLimiterMetrics, SharedRateLimiter};
 use sc_client_api::Backend;
 use sc_network::{
     config::{NetworkConfiguration, ProtocolId},
@@ -52,6 +52,8 @@ pub struct NetworkOutput<TP: TransactionPool + 'static> {
 pub struct SubstrateNetworkConfig {
     /// Maximum bit-rate in bits per second of the substrate network (shared by sync, gossip, etc.).
     pub substrate_network_bit_rate: u64,
+    /// Maximum egress bit-rate in bits per second of the substrate network, unlimited if not set.
+    pub substrate_network_egress_bit_rate: Option<u64>,
     /// Configuration of the network service.
     pub network_config: NetworkConfiguration,
 }
@@ -82,9 +84,25 @@ where
     let (base_protocol_config, events_from_network) =
         setup_base_protocol::<TP::Block>(genesis_hash);
 
+    let rate_limiter_metrics = |direction| {
+        RateLimiterMetrics::new(metrics_registry.as_ref(), "substrate", direction).unwrap_or_else(
+            |e| {
+                error!(target: LOG_TARGET, "Failed to create rate-limiter metrics: {}.", e);
+                RateLimiterMetrics::noop()
+            },
+        )
+    };
     let network_rate_limit = network_config.substrate_network_bit_rate;
-    let rate_limiter = SharedRateLimiter::new(network_rate_limit.into());
-    let transport_builder = |config| transport::build_transport(rate_limiter, config);
+    let rate_limiter = SharedRateLimiter::new(network_rate_limit.into())
+        .with_metrics(rate_limiter_metrics("ingress"));
+    let egress_rate_limiter = SharedRateLimiter::new_optional(
+        network_config
+            .substrate_network_egress_bit_rate
+            .map(Into::into),
+    )
+    .with_metrics(rate_limiter_metrics("egress"));
+    let transport_builder =
+        |config| transport::build_transport(rate_limiter, egress_rate_limiter, config);
 
     let (
         network,
@@ -3,13 +3,19 @@ use rate_limiter::{FuturesRateLimitedAsyncReadWrite, SharedRateLimiter};
 
 struct RateLimitedStreamMuxer<SM> {
     rate_limiter: SharedRateLimiter,
+    egress_rate_limiter: SharedRateLimiter,
     stream_muxer: SM,
 }
 
 impl<SM> RateLimitedStreamMuxer<SM> {
-    pub fn new(stream_muxer: SM, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(
+        stream_muxer: SM,
+        rate_limiter: SharedRateLimiter,
+        egress_rate_limiter: SharedRateLimiter,
+    ) -> Self {
         Self {
             rate_limiter,
+            egress_rate_limiter,
             stream_muxer,
         }
     }
@@ -37,8 +43,11 @@ where
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<Self::Substream, Self::Error>> {
         let rate_limiter = self.rate_limiter.share();
+        let egress_rate_limiter = self.egress_rate_limiter.share();
         self.inner().poll_inbound(cx).map(|result| {
-            result.map(|substream| FuturesRateLimitedAsyncReadWrite::new(substream, rate_limiter))
+            result.map(|substream| {
+                FuturesRateLimitedAsyncReadWrite::new(substream, rate_limiter, egress_rate_limiter)
+            })
         })
     }
 
@@ -47,8 +56,11 @@ where
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<Self::Substream, Self::Error>> {
         let rate_limiter = self.rate_limiter.share();
+        let egress_rate_limiter = self.egress_rate_limiter.share();
         self.inner().poll_outbound(cx).map(|result| {
-            result.map(|substream| FuturesRateLimitedAsyncReadWrite::new(substream, rate_limiter))
+            result.map(|substream| {
+                FuturesRateLimitedAsyncReadWrite::new(substream, rate_limiter, egress_rate_limiter)
+            })
         })
     }
 
@@ -72,6 +84,7 @@ where
 /// [sc_network::NetworkWorker].
 pub fn build_transport(
     rate_limiter: SharedRateLimiter,
+    egress_rate_limiter: SharedRateLimiter,
     config: sc_network::transport::NetworkConfig,
 ) -> impl Transport<
     Output = (
@@ -94,6 +107,7 @@ pub fn build_transport(
         }
     }
     let rate_limiter = ClonableSharedRateLimiter(rate_limiter);
+    let egress_rate_limiter = ClonableSharedRateLimiter(egress_rate_limiter);
 
     sc_network::transport::build_transport(
         config.keypair,
@@ -104,7 +118,11 @@ pub fn build_transport(
     .map(move |(peer_id, stream_muxer), _| {
         (
             peer_id,
-            RateLimitedStreamMuxer::new(stream_muxer, rate_limiter.share()),
+            RateLimitedStreamMuxer::new(
+                stream_muxer,
+                rate_limiter.share(),
+                egress_rate_limiter.share(),
+            ),
         )
     })
 }
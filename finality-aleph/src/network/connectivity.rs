@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use log::warn;
+use network_clique::{ConnectedPeers, PublicKey};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use substrate_prometheus_endpoint::{
+    register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64,
+};
+
+use crate::session::SessionId;
+
+/// Connection details of a single committee member, as seen by this node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberConnectivity {
+    /// Index of the member in the committee.
+    pub index: usize,
+    /// PeerId of the member used in validator (clique) network, if we received its authentication.
+    pub validator_network_peer_id: Option<String>,
+    /// Whether this node has an authenticated connection with the member.
+    pub connected: bool,
+}
+
+/// Connections of this node with the other members of the committee of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeConnectivity {
+    /// Session to which given information applies.
+    pub session: SessionId,
+    /// Index of this node in the committee.
+    pub own_index: usize,
+    /// All the other members of the committee, ordered by index.
+    pub members: Vec<MemberConnectivity>,
+}
+
+/// Stores the most recent connectivity of this node with the committee it is a member of.
+#[derive(Clone, Default)]
+pub struct ValidatorConnectivity {
+    data: Arc<Mutex<Option<CommitteeConnectivity>>>,
+}
+
+impl ValidatorConnectivity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, connectivity: Option<CommitteeConnectivity>) {
+        *self.data.lock() = connectivity;
+    }
+
+    pub fn snapshot(&self) -> Option<CommitteeConnectivity> {
+        self.data.lock().clone()
+    }
+}
+
+#[derive(Clone)]
+enum Metrics {
+    Prometheus {
+        session: Gauge<U64>,
+        committee_size: Gauge<U64>,
+        connected_members: Gauge<U64>,
+        connected: GaugeVec<U64>,
+    },
+    Noop,
+}
+
+impl Metrics {
+    fn new(registry: Option<&Registry>) -> Result<Self, PrometheusError> {
+        let registry = match registry {
+            Some(registry) => registry,
+            None => return Ok(Metrics::Noop),
+        };
+        Ok(Metrics::Prometheus {
+            session: register(
+                Gauge::new(
+                    "aleph_validator_network_connectivity_session",
+                    "Session the validator network connectivity is reported for",
+                )?,
+                registry,
+            )?,
+            committee_size: register(
+                Gauge::new(
+                    "aleph_validator_network_committee_size",
+                    "Number of other members of the committee of the current session",
+                )?,
+                registry,
+            )?,
+            connected_members: register(
+                Gauge::new(
+                    "aleph_validator_network_connected_members",
+                    "Number of other committee members this node has an authenticated connection with",
+                )?,
+                registry,
+            )?,
+            connected: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "aleph_validator_network_connected",
+                        "Whether this node has an authenticated connection with the committee member",
+                    ),
+                    &["member"],
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    fn report(&self, connectivity: Option<&CommitteeConnectivity>) {
+        if let Metrics::Prometheus {
+            session,
+            committee_size,
+            connected_members,
+            connected,
+        } = self
+        {
+            connected.reset();
+            let Some(connectivity) = connectivity else {
+                committee_size.set(0);
+                connected_members.set(0);
+                return;
+            };
+            session.set(connectivity.session.0.into());
+            committee_size.set(connectivity.members.len() as u64);
+            connected_members.set(
+                connectivity
+                    .members
+                    .iter()
+                    .filter(|member| member.connected)
+                    .count() as u64,
+            );
+            for member in &connectivity.members {
+                connected
+                    .with_label_values(&[&member.index.to_string()])
+                    .set(member.connected.into());
+            }
+        }
+    }
+}
+
+/// Publishes the connectivity of this node with its committee, both as metrics and for the RPC.
+pub struct ConnectivityReporter<P: PublicKey> {
+    connected_peers: ConnectedPeers<P>,
+    validator_connectivity: ValidatorConnectivity,
+    metrics: Metrics,
+}
+
+impl<P: PublicKey> ConnectivityReporter<P> {
+    pub fn new(
+        connected_peers: ConnectedPeers<P>,
+        validator_connectivity: ValidatorConnectivity,
+        registry: Option<&Registry>,
+    ) -> Self {
+        let metrics = Metrics::new(registry).unwrap_or_else(|e| {
+            warn!(target: "aleph-network", "Failed to create connectivity metrics: {}", e);
+            Metrics::Noop
+        });
+        ConnectivityReporter {
+            connected_peers,
+            validator_connectivity,
+            metrics,
+        }
+    }
+
+    /// Whether there is an active connection with the peer in the validator network.
+    pub fn is_connected(&self, peer_id: &P) -> bool {
+        self.connected_peers.contains(peer_id)
+    }
+
+    pub fn report(&self, connectivity: Option<CommitteeConnectivity>) {
+        self.metrics.report(connectivity.as_ref());
+        self.validator_connectivity.update(connectivity);
+    }
+}
@@ -14,9 +14,11 @@ pub use substrate::{PeerId as SubstratePeerId, ProtocolNetwork};
 pub mod address_cache;
 mod base_protocol;
 mod build;
+pub mod connectivity;
 pub mod data;
 #[cfg(test)]
 pub mod mock;
+pub mod rate_control;
 pub mod session;
 mod substrate;
 pub mod tcp;
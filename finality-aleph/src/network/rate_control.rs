@@ -0,0 +1,114 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_timer::Delay;
+use log::debug;
+use rate_limiter::RateScaler;
+use sp_blockchain::HeaderBackend;
+
+use crate::{
+    aleph_primitives::{Block, BlockNumber},
+    network::LOG_TARGET,
+};
+
+/// How often we check the finalization lag.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Finalization lag above which we loosen the rate-limit.
+const HIGH_LAG: BlockNumber = 10;
+/// Finalization lag at or below which we tighten the rate-limit back.
+const LOW_LAG: BlockNumber = 3;
+/// By how many percentage points the rate-limit changes on every adjustment.
+const SCALE_STEP_PERCENT: u64 = 25;
+/// The configured rate-limit, we never go below it.
+const MIN_SCALE_PERCENT: u64 = 100;
+/// The rate-limit will never be loosened above this.
+const MAX_SCALE_PERCENT: u64 = 400;
+
+/// The scale of the rate-limit that should follow the current one, given the finalization lag.
+fn next_scale(current: u64, lag: BlockNumber) -> u64 {
+    if lag > HIGH_LAG {
+        current
+            .saturating_add(SCALE_STEP_PERCENT)
+            .min(MAX_SCALE_PERCENT)
+    } else if lag <= LOW_LAG {
+        current
+            .saturating_sub(SCALE_STEP_PERCENT)
+            .max(MIN_SCALE_PERCENT)
+    } else {
+        current
+    }
+}
+
+/// Loosens the rate-limit of the validator network when finalization falls behind and tightens it back
+/// to the configured one when finalization catches up, so the rate-limit never causes us to fall out of consensus.
+pub struct AdaptiveRateController<C: HeaderBackend<Block>> {
+    client: Arc<C>,
+    rate_scaler: RateScaler,
+}
+
+impl<C: HeaderBackend<Block>> AdaptiveRateController<C> {
+    pub fn new(client: Arc<C>, rate_scaler: RateScaler) -> Self {
+        AdaptiveRateController {
+            client,
+            rate_scaler,
+        }
+    }
+
+    fn adjust(&self) {
+        let info = self.client.info();
+        let lag = info.best_number.saturating_sub(info.finalized_number);
+        let current = self.rate_scaler.percent();
+        let next = next_scale(current, lag);
+        if next != current {
+            debug!(
+                target: LOG_TARGET,
+                "Finalization lag is {} blocks, scaling the validator network rate-limit from {}% to {}%.",
+                lag,
+                current,
+                next
+            );
+            self.rate_scaler.set_percent(next);
+        }
+    }
+
+    pub async fn run(self) {
+        loop {
+            Delay::new(CHECK_INTERVAL).await;
+            self.adjust();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        next_scale, HIGH_LAG, LOW_LAG, MAX_SCALE_PERCENT, MIN_SCALE_PERCENT, SCALE_STEP_PERCENT,
+    };
+
+    #[test]
+    fn loosens_when_lagging() {
+        assert_eq!(
+            next_scale(MIN_SCALE_PERCENT, HIGH_LAG + 1),
+            MIN_SCALE_PERCENT + SCALE_STEP_PERCENT
+        );
+        assert_eq!(
+            next_scale(MAX_SCALE_PERCENT, HIGH_LAG + 1),
+            MAX_SCALE_PERCENT
+        );
+    }
+
+    #[test]
+    fn tightens_when_caught_up() {
+        assert_eq!(
+            next_scale(MAX_SCALE_PERCENT, LOW_LAG),
+            MAX_SCALE_PERCENT - SCALE_STEP_PERCENT
+        );
+        assert_eq!(next_scale(MIN_SCALE_PERCENT, 0), MIN_SCALE_PERCENT);
+    }
+
+    #[test]
+    fn keeps_scale_for_moderate_lag() {
+        let current = MIN_SCALE_PERCENT + SCALE_STEP_PERCENT;
+        assert_eq!(next_scale(current, HIGH_LAG), current);
+        assert_eq!(next_scale(current, LOW_LAG + 1), current);
+    }
+}
@@ -89,7 +89,7 @@ impl<A: AddressingInformation> Handler<A> {
         }
     }
 
-    fn index(&self) -> Option<NodeIndex> {
+    pub fn index(&self) -> Option<NodeIndex> {
         match self.authority_index_and_pen {
             Some((index, _)) => Some(index),
             _ => None,
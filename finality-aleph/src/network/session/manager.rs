@@ -12,6 +12,7 @@ use crate::{
     crypto::{AuthorityPen, AuthorityVerifier},
     network::{
         address_cache::{ValidatorAddressCacheUpdater, ValidatorAddressingInfo},
+        connectivity::{CommitteeConnectivity, MemberConnectivity},
         session::{
             data::DataInSession, Authentication, Connections, Discovery, DiscoveryMessage,
             SessionHandler, SessionHandlerError,
@@ -373,6 +374,36 @@ impl<NI: NetworkIdentity, D: Data, VCU: ValidatorAddressCacheUpdater> Manager<NI
         }
     }
 
+    /// Connections with the committee of the latest session we are a validator in, if any.
+    pub fn committee_connectivity<F: Fn(&NI::PeerId) -> bool>(
+        &self,
+        is_connected: F,
+    ) -> Option<CommitteeConnectivity> {
+        let (session_id, session) = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.handler.is_validator())
+            .max_by_key(|(session_id, _)| session_id.0)?;
+        let own_index = session.handler.index()?;
+        let members = (0..session.handler.node_count().0)
+            .map(NodeIndex)
+            .filter(|node_id| *node_id != own_index)
+            .map(|node_id| {
+                let peer_id = session.handler.peer_id(&node_id);
+                MemberConnectivity {
+                    index: node_id.0,
+                    connected: peer_id.as_ref().map(&is_connected).unwrap_or(false),
+                    validator_network_peer_id: peer_id.map(|peer_id| peer_id.to_string()),
+                }
+            })
+            .collect();
+        Some(CommitteeConnectivity {
+            session: *session_id,
+            own_index: own_index.0,
+            members,
+        })
+    }
+
     pub fn status_report(&self) {
         let mut status = String::from("Connection Manager status report: ");
 
@@ -639,4 +670,54 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn reports_committee_connectivity() {
+        let mut manager = build();
+        assert!(manager.committee_connectivity(|_| true).is_none());
+        let (validator_data, verifier) = crypto_basics(NUM_NODES);
+        let (node_id, pen) = validator_data[0].clone();
+        let session_id = SessionId(43);
+        manager
+            .update_validator_session(PreValidatorSession {
+                session_id,
+                verifier: verifier.clone(),
+                node_id,
+                pen,
+            })
+            .unwrap();
+        let connectivity = manager
+            .committee_connectivity(|_| true)
+            .expect("we are a validator");
+        assert_eq!(connectivity.session, session_id);
+        assert_eq!(connectivity.own_index, 0);
+        assert_eq!(connectivity.members.len(), NUM_NODES - 1);
+        assert!(connectivity.members.iter().all(|member| !member.connected));
+        let mut other_manager = build();
+        let (node_id, pen) = validator_data[1].clone();
+        let (ManagerActions { maybe_message, .. }, _) = other_manager
+            .update_validator_session(PreValidatorSession {
+                session_id,
+                verifier,
+                node_id,
+                pen,
+            })
+            .unwrap();
+        let message = maybe_message.expect("there should be a discovery message");
+        manager.on_discovery_message(message);
+        let connectivity = manager
+            .committee_connectivity(|_| true)
+            .expect("we are a validator");
+        for member in connectivity.members {
+            assert_eq!(member.connected, member.index == 1);
+            assert_eq!(
+                member.validator_network_peer_id.is_some(),
+                member.index == 1
+            );
+        }
+        let connectivity = manager
+            .committee_connectivity(|_| false)
+            .expect("we are a validator");
+        assert!(connectivity.members.iter().all(|member| !member.connected));
+    }
 }
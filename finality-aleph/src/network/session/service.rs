@@ -17,6 +17,7 @@ use crate::{
     crypto::{AuthorityPen, AuthorityVerifier},
     network::{
         address_cache::ValidatorAddressCacheUpdater,
+        connectivity::ConnectivityReporter,
         session::{
             data::DataInSession,
             manager::{
@@ -30,6 +31,9 @@ use crate::{
     MillisecsPerBlock, NodeIndex, SessionId, SessionPeriod, STATUS_REPORT_INTERVAL,
 };
 
+/// How often the connectivity with the committee is refreshed in metrics and for the RPC.
+const CONNECTIVITY_REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Commands for manipulating sessions, stopping them and starting both validator and non-validator
 /// sessions.
 enum SessionCommand<D: Data> {
@@ -186,6 +190,7 @@ pub struct Service<
     messages_from_user: mpsc::UnboundedReceiver<(D, SessionId, Recipient)>,
     validator_network: CN,
     gossip_network: GN,
+    connectivity_reporter: ConnectivityReporter<NI::PeerId>,
     maintenance_period: Duration,
     initial_delay: Duration,
 }
@@ -226,6 +231,7 @@ where
         validator_network: CN,
         gossip_network: GN,
         validator_address_cache_updater: VCU,
+        connectivity_reporter: ConnectivityReporter<NI::PeerId>,
         config: Config,
     ) -> (
         Service<D, NI, CN, GN, VCU>,
@@ -250,6 +256,7 @@ where
                 messages_from_user,
                 validator_network,
                 gossip_network,
+                connectivity_reporter,
                 maintenance_period,
                 initial_delay,
             },
@@ -354,6 +361,7 @@ where
             time::interval_at(Instant::now() + self.initial_delay, self.maintenance_period);
 
         let mut status_ticker = time::interval(STATUS_REPORT_INTERVAL);
+        let mut connectivity_ticker = time::interval(CONNECTIVITY_REPORT_INTERVAL);
         loop {
             trace!(target: "aleph-network", "Manager Loop started a next iteration");
             tokio::select! {
@@ -401,6 +409,10 @@ where
                 },
                 _ = status_ticker.tick() => {
                     self.manager.status_report();
+                },
+                _ = connectivity_ticker.tick() => {
+                    let connectivity = self.manager.committee_connectivity(|peer_id| self.connectivity_reporter.is_connected(peer_id));
+                    self.connectivity_reporter.report(connectivity);
                 }
             }
         }
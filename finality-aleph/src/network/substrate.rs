@@ -2,6 +2,10 @@ use std::{
     borrow::{Borrow, BorrowMut},
     collections::HashSet,
     fmt::{Debug, Display, Error as FmtError, Formatter},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use log::{debug, info, trace, warn};
@@ -27,6 +31,7 @@ pub type BoxedNotificationService = Box<dyn sc_network::config::NotificationServ
 pub struct ProtocolNetwork {
     service: BoxedNotificationService,
     connected_peers: HashSet<PeerId>,
+    peer_counter: Arc<AtomicUsize>,
     last_status_report: time::Instant,
 }
 
@@ -47,6 +52,7 @@ impl ProtocolNetwork {
         Self {
             service,
             connected_peers: HashSet::new(),
+            peer_counter: Arc::new(AtomicUsize::new(0)),
             last_status_report: time::Instant::now(),
         }
     }
@@ -55,6 +61,11 @@ impl ProtocolNetwork {
         self.service.protocol().clone()
     }
 
+    /// A counter that always holds the current number of connected peers.
+    pub fn peer_counter(&self) -> Arc<AtomicUsize> {
+        self.peer_counter.clone()
+    }
+
     fn random_peer<'a>(&'a self, peer_ids: &'a HashSet<PeerId>) -> Option<&'a PeerId> {
         peer_ids
             .intersection(&self.connected_peers)
@@ -75,10 +86,14 @@ impl ProtocolNetwork {
             }
             NotificationStreamOpened { peer, .. } => {
                 self.connected_peers.insert(peer);
+                self.peer_counter
+                    .store(self.connected_peers.len(), Ordering::Relaxed);
                 None
             }
             NotificationStreamClosed { peer } => {
                 self.connected_peers.remove(&peer);
+                self.peer_counter
+                    .store(self.connected_peers.len(), Ordering::Relaxed);
                 None
             }
             NotificationReceived { peer, notification } => Some((notification, peer)),
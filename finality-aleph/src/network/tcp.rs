@@ -2,15 +2,15 @@ use std::{
     fmt::{Display as FmtDisplay, Error as FmtError, Formatter},
     io::Error as IoError,
     iter,
-    net::ToSocketAddrs as _,
+    net::SocketAddr,
 };
 
 use derive_more::{AsRef, Display};
-use log::info;
+use log::{debug, info, warn};
 use network_clique::{Dialer, Listener, PeerId, PublicKey, SecretKey};
 use parity_scale_codec::{Decode, Encode};
 use sp_core::crypto::KeyTypeId;
-use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::net::{lookup_host, TcpListener, TcpStream, ToSocketAddrs};
 
 use crate::{
     aleph_primitives::AuthorityId,
@@ -141,6 +141,22 @@ impl SignedTcpAddressingInformation {
     }
 }
 
+/// Resolves the addresses, which might be either IP addresses or DNS names, skipping the ones that fail to resolve.
+/// As we resolve anew every time, changes of the IP behind a DNS name are picked up on the next connection attempt.
+async fn resolve(addresses: impl IntoIterator<Item = String>) -> Vec<SocketAddr> {
+    let mut resolved = Vec::new();
+    for address in addresses {
+        match lookup_host(address.as_str()).await {
+            Ok(socket_addresses) => resolved.extend(socket_addresses),
+            Err(e) => debug!(
+                target: LOG_TARGET,
+                "Failed to resolve address {}: {}.", address, e
+            ),
+        }
+    }
+    resolved
+}
+
 #[derive(Clone)]
 struct TcpDialer;
 
@@ -162,11 +178,7 @@ impl Dialer<SignedTcpAddressingInformation> for TcpDialer {
             other_addresses,
             ..
         } = addressing_information;
-        let parsed_addresses: Vec<_> = iter::once(primary_address)
-            .chain(other_addresses)
-            .filter_map(|address| address.to_socket_addrs().ok())
-            .flatten()
-            .collect();
+        let parsed_addresses = resolve(iter::once(primary_address).chain(other_addresses)).await;
         let stream = TcpStream::connect(&parsed_addresses[..]).await?;
         if stream.set_linger(None).is_err() {
             info!(target: LOG_TARGET, "stream.set_linger(None) failed.");
@@ -226,6 +238,15 @@ pub async fn new_tcp_network<A: ToSocketAddrs>(
     Error,
 > {
     let listener = TcpListener::bind(listening_addresses).await?;
+    for address in &external_addresses {
+        if resolve(iter::once(address.clone())).await.is_empty() {
+            warn!(
+                target: LOG_TARGET,
+                "External address {} does not currently resolve, other validators might not be able to connect to us.",
+                address
+            );
+        }
+    }
     let identity = SignedTcpAddressingInformation::new(external_addresses, authority_pen)?;
     Ok((TcpDialer {}, listener, identity))
 }
@@ -248,3 +269,25 @@ pub mod testing {
             .expect("the provided addresses are fine")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::resolve;
+
+    #[tokio::test]
+    async fn resolves_only_valid_addresses() {
+        let resolved = resolve(vec![
+            "127.0.0.1:30343".to_string(),
+            "not an address".to_string(),
+        ])
+        .await;
+        assert_eq!(
+            resolved,
+            vec!["127.0.0.1:30343"
+                .parse::<SocketAddr>()
+                .expect("valid address")]
+        );
+    }
+}
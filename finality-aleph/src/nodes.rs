@@ -6,24 +6,31 @@ use log::{debug, error};
 use network_clique::{RateLimitingDialer, RateLimitingListener, Service, SpawnHandleT};
 use pallet_aleph_runtime_api::AlephSessionApi;
 use primitives::TransactionHash;
-use rate_limiter::SharedRateLimiter;
+use rate_limiter::{RateLimiterMetrics, SharedRateLimiter};
 use sc_client_api::Backend;
 use sc_keystore::{Keystore, LocalKeystore};
-use sc_transaction_pool_api::TransactionPool;
+use sc_transaction_pool_api::{
+    LocalTransactionPool, OffchainTransactionPoolFactory, TransactionPool,
+};
 use sp_consensus_aura::AuraApi;
 
 use crate::{
     aleph_primitives::{AuraId, Block},
     block::{
-        substrate::{JustificationTranslator, SubstrateFinalizationInfo, VerifierCache},
+        substrate::{
+            EmergencyFinalizationSubmitter, JustificationTranslator, SubstrateFinalizationInfo,
+            VerifierCache,
+        },
         BlockchainEvents, ChainStatus, FinalizationStatus, Justification,
     },
     crypto::AuthorityPen,
     finalization::AlephFinalizer,
     idx_to_account::ValidatorIndexToAccountIdConverterImpl,
-    metrics::{run_metrics_service, ScoreMetrics, SloMetrics},
+    metrics::{run_metrics_service, ParticipationMetrics, ScoreMetrics, SloMetrics},
     network::{
         address_cache::validator_address_cache_updater,
+        connectivity::ConnectivityReporter,
+        rate_control::AdaptiveRateController,
         session::{ConnectionManager, ConnectionManagerConfig},
         tcp::{new_tcp_network, KEY_TYPE},
     },
@@ -36,6 +43,7 @@ use crate::{
     session_map::{
         AuthorityProviderImpl, FinalityNotifierImpl, FinalizedBlockProviderImpl, SessionMapUpdater,
     },
+    stall_detector::{FinalityStallDetector, StallDiagnostics},
     sync::{DatabaseIO as SyncDatabaseIO, Service as SyncService, IO as SyncIO},
     AlephConfig,
 };
@@ -60,7 +68,9 @@ where
     C: crate::ClientForAleph<Block, BE> + Send + Sync + 'static,
     C::Api: AlephSessionApi<Block> + AuraApi<Block, AuraId>,
     BE: Backend<Block> + 'static,
-    TP: TransactionPool<Block = Block, Hash = TransactionHash> + 'static,
+    TP: TransactionPool<Block = Block, Hash = TransactionHash>
+        + LocalTransactionPool<Block = Block>
+        + 'static,
 {
     let AlephConfig {
         authentication_network,
@@ -78,11 +88,15 @@ where
         justification_channel_provider,
         block_rx,
         backup_saving_path,
+        backup_encryption_key,
+        backup_retention,
         external_addresses,
         validator_port,
         rate_limiter_config,
         sync_oracle,
+        finality_stall_timeout,
         validator_address_cache,
+        validator_connectivity,
         transaction_pool,
     } = aleph_config;
 
@@ -108,10 +122,40 @@ where
     .await
     .expect("we should have working networking");
 
-    let alephbft_rate_limiter =
-        SharedRateLimiter::new(rate_limiter_config.alephbft_network_bit_rate.into());
-    let dialer = RateLimitingDialer::new(dialer, alephbft_rate_limiter.share());
-    let listener = RateLimitingListener::new(listener, alephbft_rate_limiter);
+    let rate_limiter_metrics = |direction| {
+        RateLimiterMetrics::new(registry.as_ref(), "alephbft", direction).unwrap_or_else(|e| {
+            debug!(target: LOG_TARGET, "Failed to create rate-limiter metrics: {}.", e);
+            RateLimiterMetrics::noop()
+        })
+    };
+    let mut alephbft_rate_limiter =
+        SharedRateLimiter::new(rate_limiter_config.alephbft_network_bit_rate.into())
+            .with_metrics(rate_limiter_metrics("ingress"));
+    if let Some(per_peer_bit_rate) = rate_limiter_config.alephbft_per_peer_bit_rate {
+        alephbft_rate_limiter = alephbft_rate_limiter.with_per_peer_rate(per_peer_bit_rate.into());
+    }
+    if rate_limiter_config.alephbft_adaptive_bit_rate {
+        if let Some(rate_scaler) = alephbft_rate_limiter.rate_scaler() {
+            let rate_controller = AdaptiveRateController::new(client.clone(), rate_scaler);
+            spawn_handle.spawn("aleph/rate_control", rate_controller.run());
+        }
+    }
+    let alephbft_egress_rate_limiter = SharedRateLimiter::new_optional(
+        rate_limiter_config
+            .alephbft_network_egress_bit_rate
+            .map(Into::into),
+    )
+    .with_metrics(rate_limiter_metrics("egress"));
+    let dialer = RateLimitingDialer::new(
+        dialer,
+        alephbft_rate_limiter.share(),
+        alephbft_egress_rate_limiter.share(),
+    );
+    let listener = RateLimitingListener::new(
+        listener,
+        alephbft_rate_limiter,
+        alephbft_egress_rate_limiter,
+    );
 
     let (validator_network_service, validator_network) = Service::new(
         dialer,
@@ -120,6 +164,11 @@ where
         spawn_handle.clone(),
         registry.clone(),
     );
+    let connectivity_reporter = ConnectivityReporter::new(
+        validator_network_service.connected_peers(),
+        validator_connectivity,
+        registry.as_ref(),
+    );
     let (_validator_network_exit, exit) = oneshot::channel();
     spawn_handle.spawn("aleph/validator_network", async move {
         debug!(target: LOG_TARGET, "Validator network has started.");
@@ -150,6 +199,15 @@ where
         debug!(target: LOG_TARGET, "Failed to create metrics: {}.", e);
         ScoreMetrics::noop()
     });
+    let participation_metrics = ParticipationMetrics::new(registry.as_ref()).unwrap_or_else(|e| {
+        debug!(target: LOG_TARGET, "Failed to create participation metrics: {}.", e);
+        ParticipationMetrics::noop()
+    });
+
+    let runtime_api = RuntimeApiImpl::with_transaction_pool(
+        client.clone(),
+        OffchainTransactionPoolFactory::new(transaction_pool.clone()),
+    );
 
     let slo_metrics = SloMetrics::new(registry.as_ref(), chain_status.clone());
     let timing_metrics = slo_metrics.timing_metrics().clone();
@@ -166,6 +224,18 @@ where
     });
 
     let session_info = SessionBoundaryInfo::new(session_period);
+    let stall_diagnostics = StallDiagnostics::new(block_sync_network.peer_counter());
+    if let Some(timeout) = finality_stall_timeout {
+        let stall_detector = FinalityStallDetector::new(
+            client.clone(),
+            session_info.clone(),
+            sync_oracle.clone(),
+            stall_diagnostics.clone(),
+            timeout,
+            registry.as_ref(),
+        );
+        spawn_handle.spawn("aleph/stall_detector", stall_detector.run());
+    }
     let genesis_header = match chain_status.finalized_at(0) {
         Ok(FinalizationStatus::FinalizedWithJustification(justification)) => {
             justification.header().clone()
@@ -187,7 +257,10 @@ where
         VERIFIER_CACHE_SIZE,
         genesis_header,
     );
-    let finalizer = AlephFinalizer::new(client.clone());
+    let finalizer = EmergencyFinalizationSubmitter::new(
+        AlephFinalizer::new(client.clone()),
+        runtime_api.clone(),
+    );
     import_queue_handle.attach_metrics(timing_metrics.clone());
     let justifications_for_sync = justification_channel_provider.get_sender();
     let sync_io = SyncIO::new(
@@ -234,6 +307,7 @@ where
         validator_network,
         authentication_network,
         validator_address_cache_updater,
+        connectivity_reporter,
         ConnectionManagerConfig::with_session_period(&session_period, &millisecs_per_block),
     );
 
@@ -253,6 +327,8 @@ where
         session_authorities,
         sync_oracle,
         backup_saving_path,
+        backup_encryption_key,
+        backup_retention,
         chain_state: ChainStateImpl {
             client: client.clone(),
             _phantom: PhantomData,
@@ -272,8 +348,12 @@ where
             connection_manager,
             keystore,
             score_metrics,
+            participation_metrics.clone(),
+            stall_diagnostics,
+            runtime_api,
         ),
         session_info,
+        participation_metrics,
     });
 
     debug!(target: LOG_TARGET, "Consensus party has started.");
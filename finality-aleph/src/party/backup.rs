@@ -1,21 +1,33 @@
 use std::{
     fmt, fs,
-    fs::File,
-    io::{Error as IoError, Read, Result as IoResult},
+    fs::{File, OpenOptions},
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
     path::{Path, PathBuf},
     pin::Pin,
     str::FromStr,
 };
 
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
 use futures::io::{empty, sink, AllowStdIo, AsyncRead, AsyncWrite, Cursor};
-use log::debug;
+use log::{debug, info, warn};
+use parity_scale_codec::{Decode, Error as CodecError, Input};
 
 const BACKUP_FILE_EXTENSION: &str = ".abfts";
+/// Prefix of every encrypted backup file, backup files without it are in plaintext.
+const ENCRYPTED_BACKUP_MAGIC: &[u8] = b"ABFTENC1";
+const ENCRYPTION_KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+const FRAME_LENGTH_PREFIX: usize = 4;
 
 #[derive(Debug)]
 pub enum BackupLoadError {
     BackupIncomplete(Vec<usize>),
     IOError(IoError),
+    MissingEncryptionKey,
+    DecryptionFailed,
 }
 
 impl fmt::Display for BackupLoadError {
@@ -30,6 +42,15 @@ impl fmt::Display for BackupLoadError {
             BackupLoadError::IOError(err) => {
                 write!(f, "Backup could not be loaded because of IO error: {err}")
             }
+            BackupLoadError::MissingEncryptionKey => {
+                write!(f, "Backup is encrypted, but no encryption key was provided")
+            }
+            BackupLoadError::DecryptionFailed => {
+                write!(
+                    f,
+                    "Backup could not be decrypted, the encryption key might be wrong"
+                )
+            }
         }
     }
 }
@@ -46,27 +67,159 @@ pub type Saver = Pin<Box<dyn AsyncWrite + Send + Sync + Unpin>>;
 pub type Loader = Pin<Box<dyn AsyncRead + Send + Sync + Unpin>>;
 pub type ABFTBackup = (Saver, Loader);
 
-/// Find all `*.abfts` files at `session_path` and return their indexes sorted, if all are present.
-fn get_session_backup_idxs(session_path: &Path) -> Result<Vec<usize>, BackupLoadError> {
-    fs::create_dir_all(session_path)?;
+/// Symmetric key used for encrypting the backups.
+#[derive(Clone)]
+pub struct BackupEncryptionKey([u8; ENCRYPTION_KEY_LENGTH]);
+
+impl fmt::Debug for BackupEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BackupEncryptionKey(..)")
+    }
+}
+
+impl BackupEncryptionKey {
+    /// Reads the key from a file containing either its 32 raw bytes or their hex encoding.
+    pub fn from_file(path: &Path) -> IoResult<Self> {
+        let content = fs::read(path)?;
+        let bytes = match std::str::from_utf8(&content)
+            .ok()
+            .and_then(|hex_key| hex::decode(hex_key.trim().trim_start_matches("0x")).ok())
+        {
+            Some(decoded) if decoded.len() == ENCRYPTION_KEY_LENGTH => decoded,
+            _ => content,
+        };
+        let key = bytes.try_into().map_err(|_| {
+            IoError::new(
+                ErrorKind::InvalidData,
+                "the backup encryption key has to be exactly 32 bytes long",
+            )
+        })?;
+        Ok(BackupEncryptionKey(key))
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(&self.0))
+    }
+}
+
+/// Encrypts every write separately, so a crash in the middle of writing loses at most the last write.
+///
+/// Every write results in a frame consisting of the length of the ciphertext, the nonce and the ciphertext.
+struct EncryptingWriter<W: Write> {
+    inner: W,
+    cipher: XChaCha20Poly1305,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    fn new(mut inner: W, key: &BackupEncryptionKey) -> IoResult<Self> {
+        inner.write_all(ENCRYPTED_BACKUP_MAGIC)?;
+        Ok(EncryptingWriter {
+            inner,
+            cipher: key.cipher(),
+        })
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let nonce: [u8; NONCE_LENGTH] = rand::random();
+        let ciphertext = self
+            .cipher
+            .encrypt(XNonce::from_slice(&nonce), buf)
+            .map_err(|_| IoError::new(ErrorKind::Other, "backup encryption failed"))?;
+        let length = u32::try_from(ciphertext.len())
+            .map_err(|_| IoError::new(ErrorKind::InvalidInput, "backup write too large"))?;
+        let mut frame = Vec::with_capacity(FRAME_LENGTH_PREFIX + NONCE_LENGTH + ciphertext.len());
+        frame.extend_from_slice(&length.to_le_bytes());
+        frame.extend_from_slice(&nonce);
+        frame.extend_from_slice(&ciphertext);
+        self.inner.write_all(&frame)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.inner.flush()
+    }
+}
+
+/// Splits the first frame of an encrypted backup into its nonce and ciphertext, returning also the rest of the backup.
+/// Returns `None` if the frame is truncated.
+fn split_frame(encrypted: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    if encrypted.len() < FRAME_LENGTH_PREFIX + NONCE_LENGTH {
+        return None;
+    }
+    let (length, rest) = encrypted.split_at(FRAME_LENGTH_PREFIX);
+    let length = u32::from_le_bytes(length.try_into().expect("we split exactly 4 bytes")) as usize;
+    let (nonce, rest) = rest.split_at(NONCE_LENGTH);
+    if rest.len() < length {
+        return None;
+    }
+    let (ciphertext, rest) = rest.split_at(length);
+    Some((nonce, ciphertext, rest))
+}
+
+/// Decrypts the frames of an encrypted backup file, with the magic prefix already stripped.
+/// A truncated last frame, e.g. due to a crash in the middle of writing, is ignored.
+fn decrypt_backup(
+    mut encrypted: &[u8],
+    key: &BackupEncryptionKey,
+) -> Result<Vec<u8>, BackupLoadError> {
+    let cipher = key.cipher();
+    let mut plaintext = Vec::new();
+    while !encrypted.is_empty() {
+        let Some((nonce, ciphertext, rest)) = split_frame(encrypted) else {
+            warn!(target: "aleph-party", "Ignoring truncated frame at the end of an encrypted backup.");
+            break;
+        };
+        plaintext.extend(
+            cipher
+                .decrypt(XNonce::from_slice(nonce), ciphertext)
+                .map_err(|_| BackupLoadError::DecryptionFailed)?,
+        );
+        encrypted = rest;
+    }
+    Ok(plaintext)
+}
+
+/// Find all `*.abfts` files at `session_path` and return their indexes sorted.
+fn list_session_backup_idxs(session_path: &Path) -> IoResult<Vec<usize>> {
     let mut session_backups: Vec<_> = fs::read_dir(session_path)?
         .filter_map(|r| r.ok())
         .filter_map(|x| x.file_name().into_string().ok())
         .filter_map(|s| usize::from_str(s.strip_suffix(BACKUP_FILE_EXTENSION)?).ok())
         .collect();
     session_backups.sort_unstable();
+    Ok(session_backups)
+}
+
+/// Find all `*.abfts` files at `session_path` and return their indexes sorted, if all are present.
+fn get_session_backup_idxs(session_path: &Path) -> Result<Vec<usize>, BackupLoadError> {
+    fs::create_dir_all(session_path)?;
+    let session_backups = list_session_backup_idxs(session_path)?;
     if !session_backups.iter().cloned().eq(0..session_backups.len()) {
         return Err(BackupLoadError::BackupIncomplete(session_backups));
     }
     Ok(session_backups)
 }
 
-/// Load session backup at path `session_path` from all `session_idxs`.
-fn load_backup(session_path: &Path, session_idxs: &[usize]) -> Result<Loader, BackupLoadError> {
+/// Load session backup at path `session_path` from all `session_idxs`, decrypting the encrypted files.
+fn load_backup(
+    session_path: &Path,
+    session_idxs: &[usize],
+    encryption_key: Option<&BackupEncryptionKey>,
+) -> Result<Loader, BackupLoadError> {
     let mut buffer = Vec::new();
     for index in session_idxs.iter() {
         let load_path = session_path.join(format!("{index}{BACKUP_FILE_EXTENSION}"));
-        File::open(load_path)?.read_to_end(&mut buffer)?;
+        let mut file_buffer = Vec::new();
+        File::open(load_path)?.read_to_end(&mut file_buffer)?;
+        match file_buffer.strip_prefix(ENCRYPTED_BACKUP_MAGIC) {
+            Some(encrypted) => {
+                let key = encryption_key.ok_or(BackupLoadError::MissingEncryptionKey)?;
+                buffer.extend(decrypt_backup(encrypted, key)?);
+            }
+            None => buffer.extend(file_buffer),
+        }
     }
     Ok(Box::pin(Cursor::new(buffer)))
 }
@@ -85,7 +238,8 @@ fn get_next_path(session_path: &Path, session_idxs: &[usize]) -> PathBuf {
 /// `backup_path` is the path to the backup directory (i.e. the argument to `--backup-saving-path`).
 ///
 /// Returns the newly-created file (opened for writing), and the concatenation of the contents of
-/// all existing files.
+/// all existing files. If `encryption_key` is provided, the new file is encrypted with it.
+/// Existing encrypted files are decrypted transparently, which requires the key.
 ///
 /// Current directory structure (this is an implementation detail, not part of the public API):
 ///   backup-stash/      - the main directory, backup_path/--backup-saving-path
@@ -96,6 +250,7 @@ fn get_next_path(session_path: &Path, session_idxs: &[usize]) -> PathBuf {
 ///       `-- 3.abfts
 pub fn rotate(
     backup_path: Option<PathBuf>,
+    encryption_key: Option<&BackupEncryptionKey>,
     session_id: u32,
 ) -> Result<ABFTBackup, BackupLoadError> {
     debug!(target: "aleph-party", "Loading AlephBFT backup for session {:?}", session_id);
@@ -109,17 +264,253 @@ pub fn rotate(
 
     let session_backup_idxs = get_session_backup_idxs(&session_path)?;
 
-    let backup_loader = load_backup(&session_path, &session_backup_idxs)?;
+    let backup_loader = load_backup(&session_path, &session_backup_idxs, encryption_key)?;
 
     let next_backup_path = get_next_path(&session_path, &session_backup_idxs);
     debug!(target: "aleph-party", "Loaded backup for session {:?}. Creating new backup file at {:?}", session_id, next_backup_path);
-    let backup_saver = Box::pin(AllowStdIo::new(File::create(next_backup_path)?));
+    let backup_file = File::create(next_backup_path)?;
+    let backup_saver: Saver = match encryption_key {
+        Some(key) => Box::pin(AllowStdIo::new(EncryptingWriter::new(backup_file, key)?)),
+        None => Box::pin(AllowStdIo::new(backup_file)),
+    };
 
     debug!(target: "aleph-party", "Backup rotation done for session {:?}", session_id);
     Ok((backup_saver, backup_loader))
 }
 
-/// Removes the backup directory for all old sessions except the current session.
+/// The outcome of inspecting a single backup file.
+#[derive(Debug)]
+pub struct BackupFileReport {
+    pub path: PathBuf,
+    pub encrypted: bool,
+    pub size: u64,
+    /// The number of whole units in the file.
+    pub units: usize,
+    /// What is wrong with the file, if anything.
+    pub problem: Option<String>,
+    /// The length to which the file can be truncated to drop its broken tail, if that fixes the problem.
+    pub truncate_to: Option<u64>,
+}
+
+/// The outcome of inspecting the backup of a single session.
+#[derive(Debug)]
+pub struct SessionBackupReport {
+    pub session_id: u32,
+    /// Whether the backup files are numbered sequentially from zero, as required for loading the backup.
+    pub complete: bool,
+    pub files: Vec<BackupFileReport>,
+}
+
+/// Input remembering whether decoding failed because it ran out of data.
+struct TailInput<'a> {
+    data: &'a [u8],
+    exhausted: bool,
+}
+
+impl Input for TailInput<'_> {
+    fn remaining_len(&mut self) -> Result<Option<usize>, CodecError> {
+        // Not known on purpose, so that decoding a truncated unit fails only when reading past the end.
+        Ok(None)
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> Result<(), CodecError> {
+        if into.len() > self.data.len() {
+            self.exhausted = true;
+            return Err("Not enough data to fill buffer".into());
+        }
+        let (read, rest) = self.data.split_at(into.len());
+        into.copy_from_slice(read);
+        self.data = rest;
+        Ok(())
+    }
+}
+
+/// Decodes as many units from the start of `data` as possible, returning their number and the length they take.
+/// The last value is whether the remaining bytes are only the beginning of a unit, i.e. a broken tail, rather than
+/// bytes that cannot be decoded at all.
+fn decode_units<U: Decode>(data: &[u8]) -> (usize, usize, bool) {
+    let mut input = data;
+    let mut units = 0;
+    while !input.is_empty() {
+        let mut attempt = TailInput {
+            data: input,
+            exhausted: false,
+        };
+        if U::decode(&mut attempt).is_err() {
+            return (units, data.len() - input.len(), attempt.exhausted);
+        }
+        input = attempt.data;
+        units += 1;
+    }
+    (units, data.len(), true)
+}
+
+/// Inspects the content of a backup file, checking it consists of whole units of type `U`.
+fn inspect_content<U: Decode>(
+    content: &[u8],
+    encryption_key: Option<&BackupEncryptionKey>,
+) -> (usize, Option<String>, Option<u64>) {
+    let Some(mut encrypted) = content.strip_prefix(ENCRYPTED_BACKUP_MAGIC) else {
+        let (units, valid_length, broken_tail) = decode_units::<U>(content);
+        if valid_length == content.len() {
+            return (units, None, None);
+        }
+        if !broken_tail {
+            return (
+                units,
+                Some(format!(
+                    "bytes at offset {valid_length} cannot be decoded, the file is corrupted"
+                )),
+                None,
+            );
+        }
+        return (
+            units,
+            Some(format!(
+                "{} trailing bytes do not form a unit",
+                content.len() - valid_length
+            )),
+            Some(valid_length as u64),
+        );
+    };
+    let Some(key) = encryption_key else {
+        return (
+            0,
+            Some("the file is encrypted, but no encryption key was provided".to_string()),
+            None,
+        );
+    };
+    let cipher = key.cipher();
+    let mut units = 0;
+    // Units might span multiple frames, so we can only truncate at frame boundaries that are also unit boundaries.
+    let mut valid_length = ENCRYPTED_BACKUP_MAGIC.len();
+    let mut pending = Vec::new();
+    while !encrypted.is_empty() {
+        let Some((nonce, ciphertext, rest)) = split_frame(encrypted) else {
+            return (
+                units,
+                Some("the last frame is truncated".to_string()),
+                Some(valid_length as u64),
+            );
+        };
+        let Ok(plaintext) = cipher.decrypt(XNonce::from_slice(nonce), ciphertext) else {
+            return (
+                units,
+                Some("a frame failed to decrypt, the encryption key might be wrong".to_string()),
+                None,
+            );
+        };
+        pending.extend(plaintext);
+        let (new_units, decoded_length, broken_tail) = decode_units::<U>(&pending);
+        units += new_units;
+        if !broken_tail {
+            return (
+                units,
+                Some("decrypted data cannot be decoded, the file is corrupted".to_string()),
+                None,
+            );
+        }
+        pending.drain(..decoded_length);
+        encrypted = rest;
+        if pending.is_empty() {
+            valid_length = content.len() - encrypted.len();
+        }
+    }
+    match pending.is_empty() {
+        true => (units, None, None),
+        false => (
+            units,
+            Some(format!(
+                "{} trailing decrypted bytes do not form a unit",
+                pending.len()
+            )),
+            Some(valid_length as u64),
+        ),
+    }
+}
+
+/// Inspects all the backups at `path`, checking that every backup file consists of whole units of type `U`.
+pub fn inspect_backups<U: Decode>(
+    path: &Path,
+    encryption_key: Option<&BackupEncryptionKey>,
+) -> IoResult<Vec<SessionBackupReport>> {
+    let mut reports = Vec::new();
+    for (session_id, session_path) in session_dirs(path)? {
+        let idxs = list_session_backup_idxs(&session_path)?;
+        let complete = idxs.iter().cloned().eq(0..idxs.len());
+        let mut files = Vec::new();
+        for index in idxs {
+            let path = session_path.join(format!("{index}{BACKUP_FILE_EXTENSION}"));
+            let content = fs::read(&path)?;
+            let (units, problem, truncate_to) = inspect_content::<U>(&content, encryption_key);
+            files.push(BackupFileReport {
+                path,
+                encrypted: content.starts_with(ENCRYPTED_BACKUP_MAGIC),
+                size: content.len() as u64,
+                units,
+                problem,
+                truncate_to,
+            });
+        }
+        reports.push(SessionBackupReport {
+            session_id,
+            complete,
+            files,
+        });
+    }
+    Ok(reports)
+}
+
+/// Truncates the broken tail of the inspected file, if that fixes its problem. Returns whether it did.
+pub fn truncate_broken_tail(report: &BackupFileReport) -> IoResult<bool> {
+    match report.truncate_to {
+        Some(length) => {
+            OpenOptions::new()
+                .write(true)
+                .open(&report.path)?
+                .set_len(length)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Which backups of old sessions are kept, the backups of the current session are always kept.
+#[derive(Clone, Debug, Default)]
+pub struct BackupRetention {
+    /// How many sessions preceding the current one keep their backups.
+    pub sessions_to_keep: u32,
+    /// The maximal total size in bytes of all the backups, enforced by removing the oldest sessions.
+    pub max_total_size: Option<u64>,
+}
+
+/// Returns the sessions with a backup directory at `path` together with their directories, sorted by session.
+fn session_dirs(path: &Path) -> IoResult<Vec<(u32, PathBuf)>> {
+    let mut sessions = Vec::new();
+    for read_dir in fs::read_dir(path)? {
+        let item = read_dir?;
+        match item.file_name().to_str().map(|name| name.parse::<u32>()) {
+            Some(Ok(session_id)) => sessions.push((session_id, item.path())),
+            _ => debug!(target: "aleph-party", "backup directory contains unexpected data."),
+        };
+    }
+    sessions.sort_unstable_by_key(|(session_id, _)| *session_id);
+    Ok(sessions)
+}
+
+/// The total size in bytes of all the files in the directory.
+fn dir_size(path: &Path) -> IoResult<u64> {
+    let mut size = 0;
+    for read_dir in fs::read_dir(path)? {
+        let metadata = read_dir?.metadata()?;
+        if metadata.is_file() {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
+/// Removes the backup directories of old sessions not covered by the `retention` policy.
 ///
 /// `backup_path` is the path to the backup directory (i.e. the argument to `--backup-saving-path`).
 /// If it is `None`, nothing is done.
@@ -127,27 +518,269 @@ pub fn rotate(
 /// Any filesystem errors are returned.
 ///
 /// This should be done at the beginning of the new session.
-pub fn remove_old_backups(path: Option<PathBuf>, current_session: u32) -> IoResult<()> {
-    if let Some(path) = path {
-        if !path.exists() {
-            return Ok(());
-        }
-        for read_dir in fs::read_dir(path)? {
-            let item = read_dir?;
-            match item.file_name().to_str() {
-                Some(name) => match name.parse::<u32>() {
-                    Ok(session_id) => {
-                        if session_id < current_session {
-                            fs::remove_dir_all(item.path())?;
-                        }
-                    }
-                    Err(_) => {
-                        debug!(target: "aleph-party", "backup directory contains unexpected data.")
-                    }
-                },
-                None => debug!(target: "aleph-party", "backup directory contains unexpected data."),
-            };
+pub fn remove_old_backups(
+    path: Option<PathBuf>,
+    retention: &BackupRetention,
+    current_session: u32,
+) -> IoResult<()> {
+    let path = match path {
+        Some(path) if path.exists() => path,
+        _ => return Ok(()),
+    };
+    let oldest_kept = current_session.saturating_sub(retention.sessions_to_keep);
+    let mut kept = Vec::new();
+    for (session_id, session_path) in session_dirs(&path)? {
+        if session_id < oldest_kept {
+            fs::remove_dir_all(session_path)?;
+        } else {
+            kept.push((session_id, session_path));
         }
     }
+    if let Some(max_total_size) = retention.max_total_size {
+        let mut sizes = Vec::new();
+        for (session_id, session_path) in kept {
+            sizes.push((session_id, dir_size(&session_path)?, session_path));
+        }
+        let mut total_size: u64 = sizes.iter().map(|(_, size, _)| size).sum();
+        for (session_id, size, session_path) in sizes {
+            if total_size <= max_total_size || session_id >= current_session {
+                break;
+            }
+            debug!(target: "aleph-party", "Removing backup of session {} to fit in the backup size limit.", session_id);
+            fs::remove_dir_all(session_path)?;
+            total_size -= size;
+        }
+    }
+    Ok(())
+}
+
+/// Logs a summary of the existing backups, warning about incomplete ones.
+///
+/// `backup_path` is the path to the backup directory (i.e. the argument to `--backup-saving-path`).
+/// If it is `None`, nothing is done.
+pub fn report_backups(path: Option<PathBuf>) -> IoResult<()> {
+    let path = match path {
+        Some(path) if path.exists() => path,
+        _ => return Ok(()),
+    };
+    let mut total_size = 0;
+    let sessions = session_dirs(&path)?;
+    for (session_id, session_path) in &sessions {
+        let size = dir_size(session_path)?;
+        total_size += size;
+        match get_session_backup_idxs(session_path) {
+            Ok(idxs) => debug!(
+                target: "aleph-party",
+                "Backup of session {} consists of {} file(s) taking {} bytes.", session_id, idxs.len(), size
+            ),
+            Err(e) => {
+                warn!(target: "aleph-party", "Backup of session {} is broken: {}", session_id, e)
+            }
+        }
+    }
+    info!(
+        target: "aleph-party",
+        "Found AlephBFT backups of {} session(s) taking {} bytes in total at {:?}.", sessions.len(), total_size, path
+    );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::Write,
+        path::{Path, PathBuf},
+    };
+
+    use parity_scale_codec::Encode;
+
+    use super::{
+        decrypt_backup, inspect_content, remove_old_backups, session_dirs, BackupEncryptionKey,
+        BackupLoadError, BackupRetention, EncryptingWriter, ENCRYPTED_BACKUP_MAGIC,
+    };
+
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("aleph-backup-{name}-{}", rand::random::<u64>()));
+            fs::create_dir_all(&path).expect("creating a temporary directory works");
+            TestDir(path)
+        }
+
+        fn with_sessions(self, sessions: &[u32], backup_size: usize) -> Self {
+            for session in sessions {
+                let session_path = self.0.join(format!("{session}"));
+                fs::create_dir_all(&session_path).expect("creating a directory works");
+                fs::write(session_path.join("0.abfts"), vec![0; backup_size])
+                    .expect("writing a file works");
+            }
+            self
+        }
+
+        fn path(&self) -> &Path {
+            &self.0
+        }
+
+        fn sessions(&self) -> Vec<u32> {
+            session_dirs(&self.0)
+                .expect("reading the directory works")
+                .into_iter()
+                .map(|(session_id, _)| session_id)
+                .collect()
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn keeps_only_current_session_by_default() {
+        let dir = TestDir::new("default").with_sessions(&[3, 4, 5], 10);
+        remove_old_backups(
+            Some(dir.path().to_path_buf()),
+            &BackupRetention::default(),
+            5,
+        )
+        .expect("removing works");
+        assert_eq!(dir.sessions(), vec![5]);
+    }
+
+    #[test]
+    fn keeps_requested_number_of_sessions() {
+        let dir = TestDir::new("sessions").with_sessions(&[1, 2, 3, 4, 5], 10);
+        let retention = BackupRetention {
+            sessions_to_keep: 2,
+            max_total_size: None,
+        };
+        remove_old_backups(Some(dir.path().to_path_buf()), &retention, 5).expect("removing works");
+        assert_eq!(dir.sessions(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn removes_oldest_sessions_above_size_limit() {
+        let dir = TestDir::new("size").with_sessions(&[1, 2, 3, 4, 5], 10);
+        let retention = BackupRetention {
+            sessions_to_keep: 10,
+            max_total_size: Some(25),
+        };
+        remove_old_backups(Some(dir.path().to_path_buf()), &retention, 5).expect("removing works");
+        assert_eq!(dir.sessions(), vec![4, 5]);
+        let retention = BackupRetention {
+            sessions_to_keep: 10,
+            max_total_size: Some(0),
+        };
+        remove_old_backups(Some(dir.path().to_path_buf()), &retention, 5).expect("removing works");
+        assert_eq!(dir.sessions(), vec![5]);
+    }
+
+    fn encrypt(key: &BackupEncryptionKey, writes: &[&[u8]]) -> Vec<u8> {
+        let mut writer = EncryptingWriter::new(Vec::new(), key).expect("writing to vec works");
+        for data in writes {
+            writer.write_all(data).expect("writing to vec works");
+        }
+        writer.inner
+    }
+
+    #[test]
+    fn decrypts_encrypted_writes() {
+        let key = BackupEncryptionKey([7; 32]);
+        let encrypted = encrypt(&key, &[b"first unit", b"second unit"]);
+        let encrypted = encrypted
+            .strip_prefix(ENCRYPTED_BACKUP_MAGIC)
+            .expect("there is a magic prefix");
+        assert_eq!(
+            decrypt_backup(encrypted, &key).expect("the key is right"),
+            b"first unitsecond unit".to_vec()
+        );
+    }
+
+    #[test]
+    fn ignores_truncated_last_write() {
+        let key = BackupEncryptionKey([7; 32]);
+        let encrypted = encrypt(&key, &[b"first unit", b"second unit"]);
+        let encrypted = &encrypted[ENCRYPTED_BACKUP_MAGIC.len()..encrypted.len() - 1];
+        assert_eq!(
+            decrypt_backup(encrypted, &key).expect("the key is right"),
+            b"first unit".to_vec()
+        );
+    }
+
+    #[test]
+    fn fails_with_wrong_key() {
+        let encrypted = encrypt(&BackupEncryptionKey([7; 32]), &[b"first unit"]);
+        assert!(matches!(
+            decrypt_backup(
+                &encrypted[ENCRYPTED_BACKUP_MAGIC.len()..],
+                &BackupEncryptionKey([8; 32])
+            ),
+            Err(BackupLoadError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn inspects_plaintext_backup_with_truncated_unit() {
+        let mut content = [1u64, 2, 3]
+            .iter()
+            .flat_map(|u| u.encode())
+            .collect::<Vec<_>>();
+        assert_eq!(inspect_content::<u64>(&content, None), (3, None, None));
+        content.truncate(20);
+        let (units, problem, truncate_to) = inspect_content::<u64>(&content, None);
+        assert_eq!(units, 2);
+        assert!(problem.is_some());
+        assert_eq!(truncate_to, Some(16));
+    }
+
+    #[test]
+    fn does_not_truncate_plaintext_backup_corrupted_before_tail() {
+        let mut content = [(1u8, true), (2, false), (3, true)]
+            .iter()
+            .flat_map(|u| u.encode())
+            .collect::<Vec<_>>();
+        content.truncate(5);
+        let (units, problem, truncate_to) = inspect_content::<(u8, bool)>(&content, None);
+        assert_eq!(units, 2);
+        assert!(problem.is_some());
+        assert_eq!(truncate_to, Some(4));
+        content[3] = 7;
+        let (units, problem, truncate_to) = inspect_content::<(u8, bool)>(&content, None);
+        assert_eq!(units, 1);
+        assert!(problem.is_some());
+        assert_eq!(truncate_to, None);
+    }
+
+    #[test]
+    fn inspects_encrypted_backup_with_truncated_frame() {
+        let key = BackupEncryptionKey([7; 32]);
+        let units = [1u64.encode(), 2u64.encode()];
+        let content = encrypt(&key, &[&units[0], &units[1]]);
+        assert_eq!(
+            inspect_content::<u64>(&content, Some(&key)),
+            (2, None, None)
+        );
+        let first_frame_end = (content.len() + ENCRYPTED_BACKUP_MAGIC.len()) / 2;
+        let (units, problem, truncate_to) =
+            inspect_content::<u64>(&content[..content.len() - 1], Some(&key));
+        assert_eq!(units, 1);
+        assert!(problem.is_some());
+        assert_eq!(truncate_to, Some(first_frame_end as u64));
+    }
+
+    #[test]
+    fn does_not_truncate_backup_encrypted_with_other_key() {
+        let content = encrypt(&BackupEncryptionKey([7; 32]), &[&1u64.encode()]);
+        let (_, problem, truncate_to) =
+            inspect_content::<u64>(&content, Some(&BackupEncryptionKey([8; 32])));
+        assert!(problem.is_some());
+        assert_eq!(truncate_to, None);
+        let (_, problem, truncate_to) = inspect_content::<u64>(&content, None);
+        assert!(problem.is_some());
+        assert_eq!(truncate_to, None);
+    }
+}
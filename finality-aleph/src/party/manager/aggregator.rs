@@ -6,25 +6,26 @@ use futures::{
     stream::FusedStream,
     StreamExt,
 };
-use log::{debug, error, trace};
+use log::{debug, error, trace, warn};
 use tokio::time;
 
 use crate::{
     abft::SignatureSet,
     aggregation::{Aggregator, SignableTypedHash},
-    aleph_primitives::BlockHash,
+    aleph_primitives::{BlockHash, Hash},
     block::{
         substrate::{Justification, JustificationTranslator},
         Header, HeaderBackend,
     },
     crypto::Signature,
     justification::AlephJustification,
-    metrics::{Checkpoint, TimingBlockMetrics},
+    metrics::{Checkpoint, ParticipationMetrics, TimingBlockMetrics},
     network::data::Network,
     party::{
         manager::aggregator::AggregatorVersion::{Current, Legacy},
         AuthoritySubtaskCommon, Task,
     },
+    stall_detector::StallDiagnostics,
     sync::JustificationSubmissions,
     BlockId, CurrentRmcNetworkData, Keychain, LegacyRmcNetworkData, SessionBoundaries,
     STATUS_REPORT_INTERVAL,
@@ -55,6 +56,8 @@ where
     pub blocks_from_interpreter: mpsc::UnboundedReceiver<BlockId>,
     pub justifications_for_chain: JS,
     pub justification_translator: JustificationTranslator,
+    pub hashes_from_performance: mpsc::UnboundedReceiver<Hash>,
+    pub multisigned_hashes_for_performance: mpsc::UnboundedSender<(Hash, SignatureSet<Signature>)>,
 }
 
 async fn process_new_block_data<CN, LN>(
@@ -110,6 +113,8 @@ async fn run_aggregator<H, C, CN, LN, JS>(
     client: C,
     session_boundaries: &SessionBoundaries,
     mut metrics: TimingBlockMetrics,
+    participation_metrics: ParticipationMetrics,
+    stall_diagnostics: StallDiagnostics,
     mut exit_rx: oneshot::Receiver<()>,
 ) -> Result<(), Error>
 where
@@ -124,6 +129,8 @@ where
         blocks_from_interpreter,
         mut justifications_for_chain,
         justification_translator,
+        mut hashes_from_performance,
+        multisigned_hashes_for_performance,
     } = io;
 
     let blocks_from_interpreter = blocks_from_interpreter.take_while(|block| {
@@ -138,6 +145,7 @@ where
     pin_mut!(blocks_from_interpreter);
     let mut hash_of_last_block = None;
     let mut no_more_blocks = blocks_from_interpreter.is_terminated();
+    let mut no_more_performance_hashes = hashes_from_performance.is_terminated();
 
     let mut status_ticker = time::interval(STATUS_REPORT_INTERVAL);
 
@@ -158,16 +166,32 @@ where
                     no_more_blocks = true;
                 },
             },
+            maybe_hash = hashes_from_performance.next(), if !no_more_performance_hashes => match maybe_hash {
+                Some(hash) => {
+                    trace!(target: "aleph-party", "Received performance score hash {:?} in aggregator.", hash);
+                    aggregator.start_aggregation(Performance(hash)).await;
+                },
+                None => {
+                    debug!(target: "aleph-party", "Performance score hashes ended in aggregator.");
+                    no_more_performance_hashes = true;
+                },
+            },
             multisigned_hash = aggregator.next_multisigned_hash() => {
                 let (hash, multisignature) = multisigned_hash.ok_or(Error::MultisignaturesStreamTerminated)?;
+                participation_metrics.report_multisignature();
                 match hash {
                     Block(hash) => {
+                        stall_diagnostics.report_aggregated(hash);
                         process_block_hash(hash, multisignature, &mut justifications_for_chain, &justification_translator, &client).map_err(|_| Error::UnableToProcessHash)?;
                         if Some(hash) == hash_of_last_block {
                             hash_of_last_block = None;
                         }
                     },
-                    Performance(_) => unimplemented!("we don't gather multisignatures under performance reports yet"),
+                    Performance(hash) => {
+                        if multisigned_hashes_for_performance.unbounded_send((hash, multisignature)).is_err() {
+                            warn!(target: "aleph-party", "Failed to send multisigned performance score hash {:?}, the performance service is gone.", hash);
+                        }
+                    },
                 }
             },
             _ = status_ticker.tick() => {
@@ -199,6 +223,8 @@ pub fn task<H, C, CN, LN, JS>(
     io: IO<JS>,
     session_boundaries: SessionBoundaries,
     metrics: TimingBlockMetrics,
+    participation_metrics: ParticipationMetrics,
+    stall_diagnostics: StallDiagnostics,
     multikeychain: Keychain,
     version: AggregatorVersion<CN, LN>,
 ) -> Task
@@ -227,6 +253,8 @@ where
                 client,
                 &session_boundaries,
                 metrics,
+                participation_metrics,
+                stall_diagnostics,
                 exit,
             )
             .await;
@@ -3,25 +3,27 @@ use std::{collections::HashSet, marker::PhantomData, sync::Arc};
 use async_trait::async_trait;
 use futures::channel::oneshot;
 use log::{debug, info, trace, warn};
-use network_clique::SpawnHandleExt;
+use network_clique::{SpawnHandleExt, SpawnHandleT};
 use pallet_aleph_runtime_api::AlephSessionApi;
 use sc_keystore::{Keystore, LocalKeystore};
+use sp_api::ApiExt;
 use sp_application_crypto::RuntimeAppPublic;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 
 use crate::{
     abft::{
         current_create_aleph_config, legacy_create_aleph_config, run_current_member,
-        run_legacy_member, CurrentPerformanceService, SpawnHandle,
+        run_legacy_member, CurrentEquivocationReportingSaver, CurrentPerformanceService,
+        SignatureSet, SpawnHandle,
     },
-    aleph_primitives::{BlockHash, BlockNumber, KEY_TYPE},
+    aleph_primitives::{BlockHash, BlockNumber, Hash, KEY_TYPE},
     block::{
         substrate::{Justification, JustificationTranslator},
         BestBlockSelector, Block, Header, HeaderVerifier, UnverifiedHeader,
     },
-    crypto::{AuthorityPen, AuthorityVerifier},
+    crypto::{AuthorityPen, AuthorityVerifier, Signature},
     data_io::{ChainTracker, DataStore, OrderedDataInterpreter, SubstrateChainInfoProvider},
-    metrics::{ScoreMetrics, TimingBlockMetrics},
+    metrics::{ParticipationMetrics, ScoreMetrics, TimingBlockMetrics},
     mpsc,
     network::{
         data::{
@@ -31,9 +33,13 @@ use crate::{
         session::{SessionManager, SessionSender},
     },
     party::{
-        backup::ABFTBackup, manager::aggregator::AggregatorVersion, traits::NodeSessionManager,
+        backup::{ABFTBackup, Saver},
+        manager::aggregator::AggregatorVersion,
+        traits::NodeSessionManager,
         LOG_TARGET,
     },
+    runtime_api::RuntimeApi,
+    stall_detector::StallDiagnostics,
     sync::JustificationSubmissions,
     AuthorityId, BlockId, CurrentRmcNetworkData, Keychain, LegacyRmcNetworkData, NodeIndex,
     ProvideRuntimeApi, SessionBoundaries, SessionBoundaryInfo, SessionId, SessionPeriod,
@@ -56,6 +62,9 @@ use crate::{
 #[cfg(feature = "only_legacy")]
 const ONLY_LEGACY_ENV: &str = "ONLY_LEGACY_PROTOCOL";
 
+/// Version of `AlephSessionApi` in which the unit creation delay was put on chain.
+const UNIT_CREATION_DELAY_API_VERSION: u32 = 3;
+
 type LegacyNetworkType = SimpleNetwork<
     LegacyRmcNetworkData,
     mpsc::UnboundedReceiver<LegacyRmcNetworkData>,
@@ -81,14 +90,17 @@ where
     session_boundaries: SessionBoundaries,
     subtask_common: TaskCommon,
     blocks_for_aggregator: mpsc::UnboundedSender<BlockId>,
+    hashes_for_aggregator: mpsc::UnboundedSender<Hash>,
+    multisigned_hashes_from_aggregator: mpsc::UnboundedReceiver<(Hash, SignatureSet<Signature>)>,
     chain_info: SubstrateChainInfoProvider<H, HB>,
     aggregator_io: aggregator::IO<JS>,
     multikeychain: Keychain,
     exit_rx: oneshot::Receiver<()>,
     backup: ABFTBackup,
+    unit_creation_delay: UnitCreationDelay,
 }
 
-pub struct NodeSessionManagerImpl<H, C, HB, BBS, B, RB, SM, JS, V>
+pub struct NodeSessionManagerImpl<H, C, HB, BBS, B, RB, SM, JS, V, RA>
 where
     H: Header,
     B: Block<UnverifiedHeader = H::Unverified> + BlockT<Hash = BlockHash>,
@@ -101,6 +113,7 @@ where
     SM: SessionManager<VersionedNetworkData<B::UnverifiedHeader>> + 'static,
     JS: JustificationSubmissions<Justification> + Send + Sync + Clone,
     V: HeaderVerifier<H>,
+    RA: RuntimeApi,
 {
     client: Arc<C>,
     header_backend: HB,
@@ -116,10 +129,14 @@ where
     session_manager: SM,
     keystore: Arc<LocalKeystore>,
     score_metrics: ScoreMetrics,
+    participation_metrics: ParticipationMetrics,
+    stall_diagnostics: StallDiagnostics,
+    runtime_api: RA,
     _phantom: PhantomData<(B, H)>,
 }
 
-impl<H, C, HB, BBS, B, RB, SM, JS, V> NodeSessionManagerImpl<H, C, HB, BBS, B, RB, SM, JS, V>
+impl<H, C, HB, BBS, B, RB, SM, JS, V, RA>
+    NodeSessionManagerImpl<H, C, HB, BBS, B, RB, SM, JS, V, RA>
 where
     H: Header,
     B: Block<UnverifiedHeader = H::Unverified> + BlockT<Hash = BlockHash>,
@@ -132,6 +149,7 @@ where
     SM: SessionManager<VersionedNetworkData<B::UnverifiedHeader>> + 'static,
     JS: JustificationSubmissions<Justification> + Send + Sync + Clone,
     V: HeaderVerifier<H>,
+    RA: RuntimeApi,
 {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -149,6 +167,9 @@ where
         session_manager: SM,
         keystore: Arc<LocalKeystore>,
         score_metrics: ScoreMetrics,
+        participation_metrics: ParticipationMetrics,
+        stall_diagnostics: StallDiagnostics,
+        runtime_api: RA,
     ) -> Self {
         Self {
             client,
@@ -165,6 +186,9 @@ where
             session_manager,
             keystore,
             score_metrics,
+            participation_metrics,
+            stall_diagnostics,
+            runtime_api,
             _phantom: PhantomData,
         }
     }
@@ -186,6 +210,7 @@ where
             multikeychain,
             exit_rx,
             backup,
+            unit_creation_delay,
             ..
         } = params;
         let (chain_tracker, data_provider) = ChainTracker::new(
@@ -202,7 +227,7 @@ where
             session_boundaries.clone(),
         );
         let consensus_config =
-            legacy_create_aleph_config(n_members, node_id, session_id, self.unit_creation_delay);
+            legacy_create_aleph_config(n_members, node_id, session_id, unit_creation_delay);
         let data_network = data_network.map();
 
         let (unfiltered_aleph_network, rmc_network) =
@@ -238,6 +263,8 @@ where
                 aggregator_io,
                 session_boundaries,
                 self.metrics.clone(),
+                self.participation_metrics.clone(),
+                self.stall_diagnostics.clone(),
                 multikeychain,
                 AggregatorVersion::<CurrentNetworkType, _>::Legacy(rmc_network),
             ),
@@ -258,11 +285,14 @@ where
             session_boundaries,
             subtask_common,
             blocks_for_aggregator,
+            hashes_for_aggregator,
+            multisigned_hashes_from_aggregator,
             chain_info,
             aggregator_io,
             multikeychain,
             exit_rx,
             backup,
+            unit_creation_delay,
             ..
         } = params;
         let (chain_tracker, data_provider) = ChainTracker::new(
@@ -281,11 +311,27 @@ where
         let (abft_performance, abft_batch_handler) = CurrentPerformanceService::new(
             node_id.into(),
             n_members,
+            session_id,
             ordered_data_interpreter,
+            hashes_for_aggregator,
+            multisigned_hashes_from_aggregator,
+            self.runtime_api.clone(),
             self.score_metrics.clone(),
+            self.participation_metrics.clone(),
         );
         let consensus_config =
-            current_create_aleph_config(n_members, node_id, session_id, self.unit_creation_delay);
+            current_create_aleph_config(n_members, node_id, session_id, unit_creation_delay);
+        let (saver, loader) = backup;
+        let (saver, equivocation_reporter) =
+            CurrentEquivocationReportingSaver::<B::UnverifiedHeader>::new(
+                saver,
+                session_id,
+                self.runtime_api.clone(),
+            );
+        self.spawn_handle
+            .spawn("aleph/equivocation_reporter", equivocation_reporter.run());
+        let saver: Saver = Box::pin(saver);
+        let backup = (saver, loader);
         let data_network = data_network.map();
 
         let (unfiltered_aleph_network, rmc_network) =
@@ -317,6 +363,8 @@ where
                 aggregator_io,
                 session_boundaries,
                 self.metrics.clone(),
+                self.participation_metrics.clone(),
+                self.stall_diagnostics.clone(),
                 multikeychain,
                 AggregatorVersion::<_, LegacyNetworkType>::Current(rmc_network),
             ),
@@ -344,6 +392,9 @@ where
 
         let session_boundaries = self.session_info.boundaries_for_session(session_id);
         let (blocks_for_aggregator, blocks_from_interpreter) = mpsc::unbounded();
+        let (hashes_for_aggregator, hashes_from_performance) = mpsc::unbounded();
+        let (multisigned_hashes_for_performance, multisigned_hashes_from_aggregator) =
+            mpsc::unbounded();
 
         let chain_info = SubstrateChainInfoProvider::new(self.header_backend.clone());
 
@@ -355,6 +406,8 @@ where
             blocks_from_interpreter,
             justifications_for_chain: self.justifications_for_sync.clone(),
             justification_translator: self.justification_translator.clone(),
+            hashes_from_performance,
+            multisigned_hashes_for_performance,
         };
 
         let data_network = match self
@@ -375,6 +428,29 @@ where
             .id()
             .hash();
 
+        let runtime_api = self.client.runtime_api();
+        let unit_creation_delay = match runtime_api
+            .has_api_with::<dyn AlephSessionApi<B>, _>(
+                last_block_of_previous_session_hash,
+                |version| version >= UNIT_CREATION_DELAY_API_VERSION,
+            )
+            .and_then(|has_delay| match has_delay {
+                true => runtime_api
+                    .next_session_unit_creation_delay(last_block_of_previous_session_hash),
+                // older runtimes do not keep the delay on chain
+                false => Ok(None),
+            }) {
+            Ok(Some(delay)) => {
+                info!(target: LOG_TARGET, "Using unit creation delay of {}ms set on chain.", delay);
+                UnitCreationDelay(delay)
+            }
+            Ok(None) => self.unit_creation_delay,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to read the unit creation delay from chain, using the configured one: {}.", e);
+                self.unit_creation_delay
+            }
+        };
+
         let params = SubtasksParams {
             n_members: authorities.len(),
             node_id,
@@ -383,11 +459,14 @@ where
             session_boundaries,
             subtask_common,
             blocks_for_aggregator,
+            hashes_for_aggregator,
+            multisigned_hashes_from_aggregator,
             chain_info,
             aggregator_io,
             multikeychain,
             exit_rx,
             backup,
+            unit_creation_delay,
         };
 
         match self
@@ -436,8 +515,8 @@ where
 }
 
 #[async_trait]
-impl<H, C, HB, BBS, B, RB, SM, JS, V> NodeSessionManager
-    for NodeSessionManagerImpl<H, C, HB, BBS, B, RB, SM, JS, V>
+impl<H, C, HB, BBS, B, RB, SM, JS, V, RA> NodeSessionManager
+    for NodeSessionManagerImpl<H, C, HB, BBS, B, RB, SM, JS, V, RA>
 where
     H: Header,
     B: Block<UnverifiedHeader = H::Unverified> + BlockT<Hash = BlockHash>,
@@ -450,6 +529,7 @@ where
     SM: SessionManager<VersionedNetworkData<B::UnverifiedHeader>> + 'static,
     JS: JustificationSubmissions<Justification> + Send + Sync + Clone,
     V: HeaderVerifier<H>,
+    RA: RuntimeApi,
 {
     type Error = SM::Error;
 
@@ -7,7 +7,9 @@ use primitives::AuthorityId;
 use tokio::{task::spawn_blocking, time::sleep};
 
 use crate::{
+    metrics::ParticipationMetrics,
     party::{
+        backup::{BackupEncryptionKey, BackupRetention},
         manager::{Handle, Task, TaskCommon as AuthoritySubtaskCommon},
         traits::{ChainState, NodeSessionManager},
     },
@@ -31,8 +33,11 @@ pub(crate) struct ConsensusPartyParams<CS, NSM> {
     pub chain_state: CS,
     pub sync_oracle: SyncOracle,
     pub backup_saving_path: Option<PathBuf>,
+    pub backup_encryption_key: Option<BackupEncryptionKey>,
+    pub backup_retention: BackupRetention,
     pub session_manager: NSM,
     pub session_info: SessionBoundaryInfo,
+    pub participation_metrics: ParticipationMetrics,
 }
 
 pub(crate) struct ConsensusParty<CS, NSM>
@@ -44,8 +49,11 @@ where
     chain_state: CS,
     sync_oracle: SyncOracle,
     backup_saving_path: Option<PathBuf>,
+    backup_encryption_key: Option<BackupEncryptionKey>,
+    backup_retention: BackupRetention,
     session_manager: NSM,
     session_info: SessionBoundaryInfo,
+    participation_metrics: ParticipationMetrics,
 }
 
 const SESSION_STATUS_CHECK_PERIOD: Duration = Duration::from_millis(1000);
@@ -60,18 +68,23 @@ where
             session_authorities,
             sync_oracle,
             backup_saving_path,
+            backup_encryption_key,
+            backup_retention,
             chain_state,
             session_manager,
             session_info,
-            ..
+            participation_metrics,
         } = params;
         Self {
             sync_oracle,
             session_authorities,
             backup_saving_path,
+            backup_encryption_key,
+            backup_retention,
             chain_state,
             session_manager,
             session_info,
+            participation_metrics,
         }
     }
 
@@ -105,8 +118,11 @@ where
         let last_block = self.session_info.last_block_of_session(session_id);
         if session_id.0.checked_sub(1).is_some() {
             let backup_saving_path = self.backup_saving_path.clone();
+            let backup_retention = self.backup_retention.clone();
             spawn_blocking(move || {
-                if let Err(e) = backup::remove_old_backups(backup_saving_path, session_id.0) {
+                if let Err(e) =
+                    backup::remove_old_backups(backup_saving_path, &backup_retention, session_id.0)
+                {
                     warn!(target: "aleph-party", "Error when clearing old backups: {}", e);
                 }
             });
@@ -145,10 +161,15 @@ where
         let authorities = authority_data.authorities();
 
         trace!(target: "aleph-party", "Authority data for session {:?}: {:?}", session_id, authorities);
-        let mut maybe_authority_task = if let Some(node_id) =
-            self.session_manager.node_idx(authorities)
-        {
-            match backup::rotate(self.backup_saving_path.clone(), session_id.0) {
+        let maybe_node_id = self.session_manager.node_idx(authorities);
+        self.participation_metrics
+            .start_session(session_id, maybe_node_id);
+        let mut maybe_authority_task = if let Some(node_id) = maybe_node_id {
+            match backup::rotate(
+                self.backup_saving_path.clone(),
+                self.backup_encryption_key.as_ref(),
+                session_id.0,
+            ) {
                 Ok(backup) => {
                     debug!(target: "aleph-party", "Running session {:?} as authority id {:?}", session_id, node_id);
                     Some(
@@ -234,6 +255,9 @@ where
     }
 
     pub async fn run(mut self) {
+        if let Err(e) = backup::report_backups(self.backup_saving_path.clone()) {
+            warn!(target: "aleph-party", "Error when inspecting existing backups: {}", e);
+        }
         let starting_session = self.catch_up().await;
         for curr_id in starting_session.0.. {
             info!(target: "aleph-party", "Running session {:?}.", curr_id);
@@ -267,6 +291,7 @@ mod tests {
 
     use crate::{
         aleph_primitives::{AuthorityId, SessionAuthorityData},
+        metrics::ParticipationMetrics,
         party::{
             mocks::{MockChainState, MockNodeSessionManager},
             ConsensusParty, ConsensusPartyParams, SESSION_STATUS_CHECK_PERIOD,
@@ -516,8 +541,11 @@ mod tests {
             chain_state,
             sync_oracle,
             backup_saving_path: None,
+            backup_encryption_key: None,
+            backup_retention: Default::default(),
             session_manager,
             session_info,
+            participation_metrics: ParticipationMetrics::noop(),
         };
 
         (ConsensusParty::new(params), controller)
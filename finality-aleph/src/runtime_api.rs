@@ -8,12 +8,24 @@ use frame_support::StorageHasher;
 use pallet_aleph_runtime_api::AlephSessionApi;
 use parity_scale_codec::{Decode, DecodeAll, Encode, Error as DecodeError};
 use sc_client_api::Backend;
+use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_api::ApiExt;
 use sp_application_crypto::key_types::AURA;
 use sp_core::twox_128;
-use sp_runtime::traits::{Block, OpaqueKeys};
+use sp_runtime::{
+    traits::{Block, OpaqueKeys},
+    RuntimeAppPublic,
+};
 
 use crate::{
-    aleph_primitives::{AccountId, AuraId},
+    abft::SignatureSet,
+    aleph_primitives::{
+        crypto::{IndexedSignature, SignatureSet as PrimitivesSignatureSet},
+        AccountId, AuraId, AuthoritySignature, EquivocationProof, Score, SessionAuthorityData,
+    },
+    block::BlockId,
+    crypto::Signature,
+    justification::AlephJustification,
     BlockHash, ClientForAleph,
 };
 
@@ -23,6 +35,37 @@ pub trait RuntimeApi: Clone + Send + Sync + 'static {
     /// Returns aura authorities for the next session using state from block `at`
     fn next_aura_authorities(&self, at: BlockHash)
         -> Result<Vec<(AccountId, AuraId)>, Self::Error>;
+    /// Returns the authority data of the current session using state from block `at`, whatever
+    /// the version of the runtime at that block.
+    fn authority_data(&self, at: BlockHash) -> Result<SessionAuthorityData, Self::Error>;
+    /// Returns the authority data of the next session using state from block `at`, whatever the
+    /// version of the runtime at that block. `None` if the runtime was not able to provide it.
+    fn next_session_authority_data(
+        &self,
+        at: BlockHash,
+    ) -> Result<Option<SessionAuthorityData>, Self::Error>;
+    /// Submits a multisigned ABFT performance score as an unsigned transaction on top of the
+    /// current best block.
+    fn submit_abft_score(
+        &self,
+        score: Score,
+        signature: SignatureSet<Signature>,
+    ) -> Result<(), Self::Error>;
+    /// Submits a proof of equivocation of an AlephBFT node as an unsigned transaction on top of
+    /// the current best block. Does nothing if the runtime does not accept such reports yet.
+    fn submit_equivocation_report(
+        &self,
+        proof: EquivocationProof<AuthoritySignature>,
+    ) -> Result<(), Self::Error>;
+    /// Submits the signatures of the emergency finalizers from the justification of the block as
+    /// an unsigned transaction on top of the current best block, so that the emergency
+    /// finalization is noted on chain. Does nothing for other justifications, or if the runtime
+    /// does not note emergency finalizations yet.
+    fn submit_emergency_finalization(
+        &self,
+        block: BlockId,
+        justification: AlephJustification,
+    ) -> Result<(), Self::Error>;
 }
 
 pub struct RuntimeApiImpl<C, B, BE>
@@ -33,6 +76,7 @@ where
     BE: Backend<B> + 'static,
 {
     client: Arc<C>,
+    transaction_pool: Option<OffchainTransactionPoolFactory<B>>,
     _phantom: PhantomData<(B, BE)>,
 }
 
@@ -44,7 +88,11 @@ where
     BE: Backend<B> + 'static,
 {
     fn clone(&self) -> Self {
-        RuntimeApiImpl::new(self.client.clone())
+        RuntimeApiImpl {
+            client: self.client.clone(),
+            transaction_pool: self.transaction_pool.clone(),
+            _phantom: PhantomData,
+        }
     }
 }
 
@@ -58,6 +106,20 @@ where
     pub fn new(client: Arc<C>) -> Self {
         Self {
             client,
+            transaction_pool: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates an instance that is also able to submit transactions, which some of the runtime
+    /// calls require.
+    pub fn with_transaction_pool(
+        client: Arc<C>,
+        transaction_pool: OffchainTransactionPoolFactory<B>,
+    ) -> Self {
+        Self {
+            client,
+            transaction_pool: Some(transaction_pool),
             _phantom: PhantomData,
         }
     }
@@ -115,6 +177,9 @@ pub enum ApiError {
     NoStorageMapEntry(String, String),
     NoStorageValue(String, String),
     DecodeError(DecodeError),
+    NoTransactionPool,
+    CallFailure(String),
+    SubmissionFailure,
 }
 
 impl Display for ApiError {
@@ -131,12 +196,48 @@ impl Display for ApiError {
                 write!(f, "storage value not found under {}{}", pallet, item)
             }
             ApiError::DecodeError(error) => write!(f, "decode error: {:?}", error),
+            ApiError::NoTransactionPool => {
+                write!(
+                    f,
+                    "no transaction pool available for submitting transactions"
+                )
+            }
+            ApiError::CallFailure(error) => write!(f, "runtime api call failed: {}", error),
+            ApiError::SubmissionFailure => write!(f, "runtime rejected the submitted transaction"),
         }
     }
 }
 
 type QueuedKeys = Vec<(AccountId, primitives::AlephNodeSessionKeys)>;
 
+/// Version of `AlephSessionApi` in which the authority data got a threshold set of emergency
+/// finalizers.
+const EMERGENCY_FINALIZER_SET_API_VERSION: u32 = 2;
+/// Version of `AlephSessionApi` in which equivocation reports were added.
+const EQUIVOCATION_REPORT_API_VERSION: u32 = 3;
+/// Version of `AlephSessionApi` in which emergency finalizations started being noted on chain.
+const EMERGENCY_FINALIZATION_API_VERSION: u32 = 3;
+
+impl<C, B, BE> RuntimeApiImpl<C, B, BE>
+where
+    C: ClientForAleph<B, BE> + Send + Sync + 'static,
+    C::Api: AlephSessionApi<B>,
+    B: Block<Hash = BlockHash>,
+    BE: Backend<B> + 'static,
+{
+    fn api_version(&self, at: BlockHash) -> Result<u32, ApiError> {
+        self.client
+            .runtime_api()
+            .api_version::<dyn AlephSessionApi<B>>(at)
+            .map_err(|e| ApiError::CallFailure(e.to_string()))?
+            .ok_or_else(|| ApiError::CallFailure("AlephSessionApi is not available".into()))
+    }
+
+    fn has_emergency_finalizer_sets(&self, at: BlockHash) -> Result<bool, ApiError> {
+        Ok(self.api_version(at)? >= EMERGENCY_FINALIZER_SET_API_VERSION)
+    }
+}
+
 impl<C, B, BE> RuntimeApi for RuntimeApiImpl<C, B, BE>
 where
     C: ClientForAleph<B, BE> + Send + Sync + 'static,
@@ -146,6 +247,35 @@ where
 {
     type Error = ApiError;
 
+    fn authority_data(&self, at: BlockHash) -> Result<SessionAuthorityData, Self::Error> {
+        let runtime_api = self.client.runtime_api();
+        match self.has_emergency_finalizer_sets(at)? {
+            true => runtime_api.authority_data(at),
+            #[allow(deprecated)]
+            false => runtime_api
+                .authority_data_before_version_2(at)
+                .map(Into::into),
+        }
+        .map_err(|e| ApiError::CallFailure(e.to_string()))
+    }
+
+    fn next_session_authority_data(
+        &self,
+        at: BlockHash,
+    ) -> Result<Option<SessionAuthorityData>, Self::Error> {
+        let runtime_api = self.client.runtime_api();
+        match self.has_emergency_finalizer_sets(at)? {
+            true => runtime_api
+                .next_session_authority_data(at)
+                .map(|data| data.ok()),
+            #[allow(deprecated)]
+            false => runtime_api
+                .next_session_authority_data_before_version_2(at)
+                .map(|data| data.ok().map(Into::into)),
+        }
+        .map_err(|e| ApiError::CallFailure(e.to_string()))
+    }
+
     fn next_aura_authorities(
         &self,
         at: BlockHash,
@@ -160,6 +290,88 @@ where
             .filter_map(|(account_id, keys)| keys.get(AURA).map(|key| (account_id, key)))
             .collect())
     }
+
+    fn submit_abft_score(
+        &self,
+        score: Score,
+        signature: SignatureSet<Signature>,
+    ) -> Result<(), Self::Error> {
+        let transaction_pool = self
+            .transaction_pool
+            .as_ref()
+            .ok_or(ApiError::NoTransactionPool)?;
+        let at = self.client.info().best_hash;
+        let mut runtime_api = self.client.runtime_api();
+        runtime_api.register_extension(transaction_pool.offchain_transaction_pool(at));
+        runtime_api
+            .submit_abft_score(at, score, signature.into())
+            .map_err(|e| ApiError::CallFailure(e.to_string()))?
+            .ok_or(ApiError::SubmissionFailure)
+    }
+
+    fn submit_equivocation_report(
+        &self,
+        proof: EquivocationProof<AuthoritySignature>,
+    ) -> Result<(), Self::Error> {
+        let transaction_pool = self
+            .transaction_pool
+            .as_ref()
+            .ok_or(ApiError::NoTransactionPool)?;
+        let at = self.client.info().best_hash;
+        if self.api_version(at)? < EQUIVOCATION_REPORT_API_VERSION {
+            return Ok(());
+        }
+        let mut runtime_api = self.client.runtime_api();
+        runtime_api.register_extension(transaction_pool.offchain_transaction_pool(at));
+        runtime_api
+            .submit_equivocation_report(at, proof)
+            .map_err(|e| ApiError::CallFailure(e.to_string()))?
+            .ok_or(ApiError::SubmissionFailure)
+    }
+
+    fn submit_emergency_finalization(
+        &self,
+        block: BlockId,
+        justification: AlephJustification,
+    ) -> Result<(), Self::Error> {
+        let signatures = match justification {
+            AlephJustification::CommitteeMultisignature(_) => return Ok(()),
+            AlephJustification::EmergencyMultisignature(signatures) => signatures.into(),
+            // a single signature does not say which of the emergency finalizers made it
+            AlephJustification::EmergencySignature(signature) => {
+                let msg = block.hash().encode();
+                let index = self
+                    .authority_data(block.hash())?
+                    .emergency_finalizers()
+                    .as_ref()
+                    .and_then(|finalizers| {
+                        finalizers
+                            .keys()
+                            .iter()
+                            .position(|key| key.verify(&msg, &signature))
+                    })
+                    .ok_or(ApiError::SubmissionFailure)?;
+                PrimitivesSignatureSet(vec![IndexedSignature {
+                    index: index as u64,
+                    signature,
+                }])
+            }
+        };
+        let transaction_pool = self
+            .transaction_pool
+            .as_ref()
+            .ok_or(ApiError::NoTransactionPool)?;
+        let at = self.client.info().best_hash;
+        if self.api_version(at)? < EMERGENCY_FINALIZATION_API_VERSION {
+            return Ok(());
+        }
+        let mut runtime_api = self.client.runtime_api();
+        runtime_api.register_extension(transaction_pool.offchain_transaction_pool(at));
+        runtime_api
+            .submit_emergency_finalization(at, block.hash(), block.number(), signatures)
+            .map_err(|e| ApiError::CallFailure(e.to_string()))?
+            .ok_or(ApiError::SubmissionFailure)
+    }
 }
 
 #[cfg(test)]
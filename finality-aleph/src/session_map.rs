@@ -126,7 +126,7 @@ where
 
     fn authority_data(&self, block_number: BlockNumber) -> Option<SessionAuthorityData> {
         let block_hash = self.block_hash(block_number)?;
-        match self.client.runtime_api().authority_data(block_hash) {
+        match self.api.authority_data(block_hash) {
             Ok(data) => Some(data),
             Err(_) => AlephSessionApi::authorities(self.client.runtime_api().deref(), block_hash)
                 .map(|authorities| SessionAuthorityData::new(authorities, None))
@@ -136,12 +136,7 @@ where
 
     fn next_authority_data(&self, block_number: BlockNumber) -> Option<SessionAuthorityData> {
         let block_hash = self.block_hash(block_number)?;
-        match self
-            .client
-            .runtime_api()
-            .next_session_authority_data(block_hash)
-            .map(|r| r.ok())
-        {
+        match self.api.next_session_authority_data(block_hash) {
             Ok(maybe_data) => maybe_data,
             Err(_) => self
                 .client
@@ -0,0 +1,164 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use futures_timer::Delay;
+use log::{debug, info, warn};
+use parking_lot::Mutex;
+use sp_blockchain::HeaderBackend;
+use substrate_prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+
+use crate::{
+    aleph_primitives::{Block, BlockHash, BlockNumber},
+    session::SessionBoundaryInfo,
+    SyncOracle,
+};
+
+const LOG_TARGET: &str = "aleph-stall-detector";
+/// How often we check whether finalization progressed.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Information gathered from other components of the node, included in the diagnostics
+/// when finalization stalls.
+#[derive(Clone)]
+pub struct StallDiagnostics {
+    sync_peers: Arc<AtomicUsize>,
+    last_aggregated: Arc<Mutex<Option<(BlockHash, Instant)>>>,
+}
+
+impl StallDiagnostics {
+    /// Create diagnostics reading the number of peers from the provided counter.
+    pub fn new(sync_peers: Arc<AtomicUsize>) -> Self {
+        StallDiagnostics {
+            sync_peers,
+            last_aggregated: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Report that a multisignature under the hash of the block was aggregated.
+    pub fn report_aggregated(&self, hash: BlockHash) {
+        *self.last_aggregated.lock() = Some((hash, Instant::now()));
+    }
+}
+
+#[derive(Clone)]
+enum Metrics {
+    Prometheus { stalls: Counter<U64> },
+    Noop,
+}
+
+impl Metrics {
+    fn new(registry: Option<&Registry>) -> Result<Self, PrometheusError> {
+        match registry {
+            Some(registry) => Ok(Metrics::Prometheus {
+                stalls: register(
+                    Counter::new(
+                        "aleph_finality_stalls",
+                        "Number of times finalization did not progress for longer than the stall timeout",
+                    )?,
+                    registry,
+                )?,
+            }),
+            None => Ok(Metrics::Noop),
+        }
+    }
+
+    fn report_stall(&self) {
+        if let Metrics::Prometheus { stalls } = self {
+            stalls.inc();
+        }
+    }
+}
+
+/// Watches finalization and, whenever it does not progress for longer than the configured
+/// timeout, logs a snapshot of the state of the node that might help explain why.
+pub struct FinalityStallDetector<C: HeaderBackend<Block>> {
+    client: Arc<C>,
+    session_info: SessionBoundaryInfo,
+    sync_oracle: SyncOracle,
+    diagnostics: StallDiagnostics,
+    timeout: Duration,
+    metrics: Metrics,
+}
+
+impl<C: HeaderBackend<Block>> FinalityStallDetector<C> {
+    pub fn new(
+        client: Arc<C>,
+        session_info: SessionBoundaryInfo,
+        sync_oracle: SyncOracle,
+        diagnostics: StallDiagnostics,
+        timeout: Duration,
+        registry: Option<&Registry>,
+    ) -> Self {
+        let metrics = Metrics::new(registry).unwrap_or_else(|e| {
+            debug!(target: LOG_TARGET, "Failed to create metrics: {}.", e);
+            Metrics::Noop
+        });
+        FinalityStallDetector {
+            client,
+            session_info,
+            sync_oracle,
+            diagnostics,
+            timeout,
+            metrics,
+        }
+    }
+
+    fn dump_diagnostics(&self, stalled_for: Duration) {
+        let info = self.client.info();
+        let last_aggregated = match *self.diagnostics.last_aggregated.lock() {
+            Some((hash, at)) => format!("{:?} ({}s ago)", hash, at.elapsed().as_secs()),
+            None => "none".to_string(),
+        };
+        warn!(
+            target: LOG_TARGET,
+            "Finalization stalled for {}s: finalized=#{} ({:?}), best=#{} ({:?}), session={}, major_sync={}, sync_peers={}, last_aggregated={}.",
+            stalled_for.as_secs(),
+            info.finalized_number,
+            info.finalized_hash,
+            info.best_number,
+            info.best_hash,
+            self.session_info
+                .session_id_from_block_num(info.finalized_number + 1)
+                .0,
+            self.sync_oracle.major_sync(),
+            self.diagnostics.sync_peers.load(Ordering::Relaxed),
+            last_aggregated,
+        );
+    }
+
+    pub async fn run(self) {
+        let mut last_finalized: BlockNumber = self.client.info().finalized_number;
+        let mut last_progress = Instant::now();
+        let mut last_dump = None;
+        loop {
+            Delay::new(CHECK_INTERVAL).await;
+            let finalized = self.client.info().finalized_number;
+            if finalized != last_finalized {
+                if last_dump.is_some() {
+                    info!(target: LOG_TARGET, "Finalization resumed at #{}.", finalized);
+                }
+                last_finalized = finalized;
+                last_progress = Instant::now();
+                last_dump = None;
+                continue;
+            }
+            let stalled_for = last_progress.elapsed();
+            if stalled_for < self.timeout {
+                continue;
+            }
+            // We report every stall once, but keep dumping diagnostics every timeout while it lasts.
+            match last_dump {
+                None => self.metrics.report_stall(),
+                Some(at) if at.elapsed() < self.timeout => continue,
+                Some(_) => (),
+            }
+            self.dump_diagnostics(stalled_for);
+            last_dump = Some(Instant::now());
+        }
+    }
+}
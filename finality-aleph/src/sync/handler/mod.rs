@@ -8,7 +8,7 @@ use crate::{
     block::{
         Block, BlockImport, ChainStatus, Finalizer, Header, HeaderVerifier, Justification,
         JustificationVerifier, UnverifiedHeader, UnverifiedHeaderFor, UnverifiedJustification,
-        VerifiedHeader,
+        VerificationError, VerifiedHeader,
     },
     session::{SessionBoundaryInfo, SessionId},
     sync::{
@@ -18,6 +18,7 @@ use crate::{
             InitializationError as ForestInitializationError, Interest, Status as ForestStatus,
         },
         handler::request_handler::RequestHandler,
+        reputation::Misbehavior,
         PeerId,
     },
     BlockId, SyncOracle,
@@ -217,6 +218,27 @@ where
     }
 }
 
+impl<B, J, CS, V, F> Error<B, J, CS, V, F>
+where
+    J: Justification,
+    B: Block<UnverifiedHeader = UnverifiedHeaderFor<J>>,
+    CS: ChainStatus<B, J>,
+    V: JustificationVerifier<J> + HeaderVerifier<J::Header>,
+    F: Finalizer<J>,
+{
+    /// The misbehavior of the peer that sent the data, if the error proves any. Data that we
+    /// cannot verify yet, e.g. from a future session, is not a misbehavior.
+    pub fn misbehavior(&self) -> Option<Misbehavior> {
+        match self {
+            Error::JustificationVerifier(e) if e.proves_invalid() => {
+                Some(Misbehavior::InvalidJustification)
+            }
+            Error::HeaderVerifier(e) if e.proves_invalid() => Some(Misbehavior::InvalidHeader),
+            _ => None,
+        }
+    }
+}
+
 impl<B, J, CS, V, F> From<ForestError> for Error<B, J, CS, V, F>
 where
     J: Justification,
@@ -742,6 +764,7 @@ mod tests {
             data::{BranchKnowledge::*, NetworkData, Request, ResponseItem, ResponseItems, State},
             forest::{ExtensionRequest, Interest},
             handler::Action,
+            reputation::Misbehavior,
             Justification, MockPeerId,
         },
         BlockId, BlockNumber, SessionPeriod, SyncOracle,
@@ -1867,7 +1890,9 @@ mod tests {
             header,
         );
         match handler.handle_state(state, peer) {
-            Err(Error::HeaderVerifier(_)) => (),
+            Err(e @ Error::HeaderVerifier(_)) => {
+                assert_eq!(e.misbehavior(), Some(Misbehavior::InvalidHeader))
+            }
             e => panic!("should return Verifier error, {e:?}"),
         };
         let mut header = MockHeader::random_parentless(1000).random_child();
@@ -1879,6 +1904,18 @@ mod tests {
         };
     }
 
+    #[test]
+    fn future_session_justification_is_not_misbehavior() {
+        let (mut handler, _backend, _keep, _genesis) = setup();
+        let peer = rand::random();
+        let header = MockHeader::random_parentless(1000).random_child();
+        let state = State::new(MockJustification::for_header(header.clone()), header);
+        match handler.handle_state(state, peer) {
+            Err(e @ Error::JustificationVerifier(_)) => assert_eq!(e.misbehavior(), None),
+            e => panic!("should return Verifier error, {e:?}"),
+        };
+    }
+
     #[test]
     fn detects_equivocated_state() {
         fn equivocated_state(state: &State<MockJustification>) -> State<MockJustification> {
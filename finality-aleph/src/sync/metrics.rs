@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use substrate_prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use substrate_prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum Event {
@@ -18,6 +18,8 @@ pub enum Event {
     HandleStateResponse,
     HandleJustificationFromUser,
     HandleInternalRequest,
+    PenalizePeer,
+    IgnoreBannedPeer,
 }
 
 use Event::*;
@@ -39,11 +41,13 @@ impl Event {
             HandleStateResponse => "handle_state_response",
             HandleJustificationFromUser => "handle_justification_from_user",
             HandleInternalRequest => "handle_internal_request",
+            PenalizePeer => "penalize_peer",
+            IgnoreBannedPeer => "ignore_banned_peer",
         }
     }
 }
 
-const ALL_EVENTS: [Event; 14] = [
+const ALL_EVENTS: [Event; 16] = [
     Broadcast,
     SendRequest,
     SendTo,
@@ -58,6 +62,8 @@ const ALL_EVENTS: [Event; 14] = [
     HandleStateResponse,
     HandleJustificationFromUser,
     HandleInternalRequest,
+    PenalizePeer,
+    IgnoreBannedPeer,
 ];
 
 const ERRORING_EVENTS: [Event; 11] = [
@@ -78,6 +84,8 @@ pub enum Metrics {
     Prometheus {
         event_calls: HashMap<Event, Counter<U64>>,
         event_errors: HashMap<Event, Counter<U64>>,
+        penalized_peers: Gauge<U64>,
+        banned_peers: Gauge<U64>,
     },
     Noop,
 }
@@ -114,9 +122,25 @@ impl Metrics {
                 )?,
             );
         }
+        let penalized_peers = register(
+            Gauge::new(
+                "aleph_sync_penalized_peers",
+                "number of peers with lowered reputation",
+            )?,
+            &registry,
+        )?;
+        let banned_peers = register(
+            Gauge::new(
+                "aleph_sync_banned_peers",
+                "number of peers with reputation too low to sync with",
+            )?,
+            &registry,
+        )?;
         Ok(Metrics::Prometheus {
             event_calls,
             event_errors,
+            penalized_peers,
+            banned_peers,
         })
     }
 
@@ -139,4 +163,16 @@ impl Metrics {
             }
         }
     }
+
+    pub fn report_reputation(&self, penalized: usize, banned: usize) {
+        if let Metrics::Prometheus {
+            penalized_peers,
+            banned_peers,
+            ..
+        } = self
+        {
+            penalized_peers.set(penalized as u64);
+            banned_peers.set(banned as u64);
+        }
+    }
 }
@@ -14,6 +14,7 @@ mod forest;
 mod handler;
 mod message_limiter;
 mod metrics;
+mod reputation;
 mod select_chain;
 mod service;
 mod task_queue;
@@ -0,0 +1,154 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::sync::PeerId;
+
+/// The reputation below which we ignore all data from a peer and stop sending requests to it.
+const BAN_THRESHOLD: i32 = -100;
+/// How much reputation a peer regains on every decay, recovering up to the neutral zero.
+const DECAY: i32 = 5;
+
+/// Things a peer can do that make us trust it less.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Misbehavior {
+    /// Sent a justification that does not verify.
+    InvalidJustification,
+    /// Sent a header that does not verify.
+    InvalidHeader,
+}
+
+impl Misbehavior {
+    fn penalty(&self) -> i32 {
+        use Misbehavior::*;
+        match self {
+            InvalidJustification => 50,
+            InvalidHeader => 30,
+        }
+    }
+}
+
+/// Keeps track of how much we trust the peers we sync with.
+///
+/// Every peer starts with a neutral reputation of zero, which is lowered whenever it misbehaves
+/// and slowly recovers over time. Peers with reputation below `BAN_THRESHOLD` are banned, i.e.
+/// we ignore their data and do not send them requests until their reputation recovers.
+pub struct Reputation<I: PeerId> {
+    scores: HashMap<I, i32>,
+}
+
+impl<I: PeerId> Default for Reputation<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: PeerId> Reputation<I> {
+    pub fn new() -> Self {
+        Reputation {
+            scores: HashMap::new(),
+        }
+    }
+
+    /// Lower the reputation of the peer, returns whether it became banned.
+    pub fn report(&mut self, peer: I, misbehavior: Misbehavior) -> bool {
+        let was_banned = self.is_banned(&peer);
+        let score = self.scores.entry(peer).or_insert(0);
+        *score = score.saturating_sub(misbehavior.penalty());
+        !was_banned && *score < BAN_THRESHOLD
+    }
+
+    /// Whether we should ignore the peer.
+    pub fn is_banned(&self, peer: &I) -> bool {
+        self.scores
+            .get(peer)
+            .map(|score| *score < BAN_THRESHOLD)
+            .unwrap_or(false)
+    }
+
+    /// The provided peers without the banned ones.
+    pub fn filter(&self, peers: HashSet<I>) -> HashSet<I> {
+        peers
+            .into_iter()
+            .filter(|peer| !self.is_banned(peer))
+            .collect()
+    }
+
+    /// Let all the peers regain some reputation, forgetting the ones back to neutral.
+    pub fn decay(&mut self) {
+        for score in self.scores.values_mut() {
+            *score = score.saturating_add(DECAY).min(0);
+        }
+        self.scores.retain(|_, score| *score < 0);
+    }
+
+    /// The number of peers with reputation below neutral.
+    pub fn penalized_count(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// The number of banned peers.
+    pub fn banned_count(&self) -> usize {
+        self.scores
+            .values()
+            .filter(|score| **score < BAN_THRESHOLD)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{Misbehavior, Reputation, BAN_THRESHOLD, DECAY};
+    use crate::sync::MockPeerId;
+
+    fn ban(reputation: &mut Reputation<MockPeerId>, peer: MockPeerId) {
+        while !reputation.report(peer, Misbehavior::InvalidJustification) {}
+    }
+
+    #[test]
+    fn new_peers_are_not_banned() {
+        let reputation = Reputation::<MockPeerId>::new();
+        assert!(!reputation.is_banned(&0));
+        assert_eq!(reputation.penalized_count(), 0);
+    }
+
+    #[test]
+    fn bans_repeatedly_misbehaving_peer() {
+        let mut reputation = Reputation::new();
+        assert!(!reputation.report(0, Misbehavior::InvalidHeader));
+        assert!(!reputation.is_banned(&0));
+        ban(&mut reputation, 0);
+        assert!(reputation.is_banned(&0));
+        assert!(!reputation.is_banned(&1));
+        assert_eq!(reputation.banned_count(), 1);
+        // already banned
+        assert!(!reputation.report(0, Misbehavior::InvalidHeader));
+    }
+
+    #[test]
+    fn filters_out_banned_peers() {
+        let mut reputation = Reputation::new();
+        ban(&mut reputation, 0);
+        reputation.report(1, Misbehavior::InvalidHeader);
+        assert_eq!(
+            reputation.filter(HashSet::from([0, 1, 2])),
+            HashSet::from([1, 2])
+        );
+    }
+
+    #[test]
+    fn banned_peer_recovers_after_decay() {
+        let mut reputation = Reputation::new();
+        ban(&mut reputation, 0);
+        let decays = (-BAN_THRESHOLD + Misbehavior::InvalidJustification.penalty()) / DECAY;
+        for _ in 0..decays {
+            reputation.decay();
+        }
+        assert!(!reputation.is_banned(&0));
+        assert_eq!(reputation.banned_count(), 0);
+        for _ in 0..decays {
+            reputation.decay();
+        }
+        assert_eq!(reputation.penalized_count(), 0);
+    }
+}
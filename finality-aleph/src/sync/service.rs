@@ -27,6 +27,7 @@ use crate::{
         handler::{Action, DatabaseIO, Error as HandlerError, HandleStateAction, Handler},
         message_limiter::{Error as MsgLimiterError, MsgLimiter},
         metrics::{Event, Metrics},
+        reputation::Reputation,
         task_queue::TaskQueue,
         tasks::{Action as TaskAction, RequestTask},
         ticker::Ticker,
@@ -146,6 +147,7 @@ where
     metrics: Metrics,
     slo_metrics: SloMetrics,
     favourite_block_request: mpsc::UnboundedReceiver<oneshot::Sender<J::Header>>,
+    reputation: Reputation<N::PeerId>,
 }
 
 impl<J: Justification> JustificationSubmissions<J> for mpsc::UnboundedSender<J::Unverified> {
@@ -219,6 +221,7 @@ where
                 metrics,
                 slo_metrics,
                 favourite_block_request,
+                reputation: Reputation::new(),
             },
             block_requests_for_sync,
         ))
@@ -258,6 +261,7 @@ where
 
     fn request_favourite_extension(&mut self, know_most: HashSet<N::PeerId>) {
         self.metrics.report_event(Event::SendExtensionRequest);
+        let know_most = self.reputation.filter(know_most);
         let data = match self.handler.state() {
             Ok(state) => NetworkData::ChainExtensionRequest(state),
             Err(e) => {
@@ -325,6 +329,7 @@ where
             }
         };
         let (request, peers) = pre_request.with_state(state);
+        let peers = self.reputation.filter(peers);
         trace!(target: LOG_TARGET, "Sending a request: {:?}", request);
         let data = NetworkData::Request(request);
 
@@ -348,6 +353,28 @@ where
         }
     }
 
+    fn report_reputation(&self) {
+        self.metrics.report_reputation(
+            self.reputation.penalized_count(),
+            self.reputation.banned_count(),
+        );
+    }
+
+    fn penalize(&mut self, error: &HandlerError<B, J, CS, V, F>, peer: N::PeerId) {
+        let misbehavior = match error.misbehavior() {
+            Some(misbehavior) => misbehavior,
+            None => return,
+        };
+        self.metrics.report_event(Event::PenalizePeer);
+        if self.reputation.report(peer.clone(), misbehavior) {
+            info!(
+                target: LOG_TARGET,
+                "Banning peer {:?} after repeated misbehavior, last one: {:?}.", peer, misbehavior
+            );
+        }
+        self.report_reputation();
+    }
+
     fn process_equivocation_proofs<I: IntoIterator<Item = V::EquivocationProof>>(&self, proofs: I) {
         for proof in proofs {
             warn!(target: LOG_TARGET, "Equivocation detected: {proof}");
@@ -377,6 +404,7 @@ where
             }
             Err(e) => {
                 self.metrics.report_event_error(Event::HandleState);
+                self.penalize(&e, peer.clone());
                 match e {
                     HandlerError::JustificationVerifier(e) => debug!(
                         target: LOG_TARGET,
@@ -412,6 +440,9 @@ where
         let (new_info, maybe_error) =
             self.handler
                 .handle_state_response(justification, maybe_justification, peer.clone());
+        if let Some(e) = &maybe_error {
+            self.penalize(e, peer.clone());
+        }
         match maybe_error {
             Some(HandlerError::JustificationVerifier(e)) => debug!(
                 target: LOG_TARGET,
@@ -479,6 +510,9 @@ where
         let (new_info, equivocation_proofs, maybe_error) = self
             .handler
             .handle_request_response(response_items, peer.clone());
+        if let Some(e) = &maybe_error {
+            self.penalize(e, peer.clone());
+        }
         match maybe_error {
             Some(HandlerError::JustificationVerifier(e)) => {
                 debug!(
@@ -552,6 +586,7 @@ where
             }
             Err(e) => {
                 self.metrics.report_event_error(Event::HandleRequest);
+                self.penalize(&e, peer.clone());
                 match e {
                     HandlerError::JustificationVerifier(e) => debug!(
                         target: LOG_TARGET,
@@ -678,6 +713,7 @@ where
             Err(e) => {
                 self.metrics
                     .report_event_error(Event::HandleExtensionRequest);
+                self.penalize(&e, peer.clone());
                 match e {
                     HandlerError::JustificationVerifier(e) => debug!(
                         target: LOG_TARGET,
@@ -698,6 +734,11 @@ where
 
     fn handle_network_data(&mut self, data: NetworkData<B, J>, peer: N::PeerId) {
         use NetworkData::*;
+        if self.reputation.is_banned(&peer) {
+            self.metrics.report_event(Event::IgnoreBannedPeer);
+            trace!(target: LOG_TARGET, "Ignoring data from banned peer {:?}.", peer);
+            return;
+        }
         match data {
             StateBroadcast(state) => self.handle_state(state, peer),
             StateBroadcastResponse(justification, maybe_justification) => {
@@ -801,6 +842,8 @@ where
 
                 _ = status_ticker.tick() => {
                     info!(target: LOG_TARGET, "{}", self.handler.status());
+                    self.reputation.decay();
+                    self.report_reputation();
                 },
             }
         }
@@ -10,7 +10,7 @@ use sp_core::H256;
 
 use crate::{
     aleph_primitives::{Block, Header},
-    block::{EquivocationProof, HeaderVerifier, VerifiedHeader},
+    block::{EquivocationProof, HeaderVerifier, VerificationError, VerifiedHeader},
 };
 
 pub type TBlock = Block;
@@ -43,6 +43,12 @@ impl Display for TestVerificationError {
     }
 }
 
+impl VerificationError for TestVerificationError {
+    fn proves_invalid(&self) -> bool {
+        true
+    }
+}
+
 impl HeaderVerifier<THeader> for TestVerifier {
     type EquivocationProof = TestEquivocationProof;
     type Error = TestVerificationError;
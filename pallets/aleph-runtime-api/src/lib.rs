@@ -2,22 +2,40 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use primitives::{
-    crypto::SignatureSet, AccountId, ApiError, AuthorityId, AuthoritySignature, Balance, Perbill,
-    Score, SessionAuthorityData, SessionCommittee, SessionIndex, SessionValidatorError, Version,
+    crypto::SignatureSet, AccountId, ApiError, AuthorityId, AuthoritySignature, Balance, BanReason,
+    BlockHash, BlockNumber, EmergencyFinalization, EquivocationProof, EraCommitteeInfo, EraIndex,
+    LegacySessionAuthorityData, Perbill, Score, SessionAuthorityData, SessionCommittee,
+    SessionIndex, SessionValidatorError, ValidatorEraPerformance, Version, VersionChange,
 };
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+    #[api_version(3)]
     pub trait AlephSessionApi {
         fn next_session_authorities() -> Result<Vec<AuthorityId>, ApiError>;
         fn authorities() -> Vec<AuthorityId>;
+        #[changed_in(2)]
+        fn next_session_authority_data() -> Result<LegacySessionAuthorityData, ApiError>;
+        /// Returns the authority data of the next session, with a threshold set of emergency
+        /// finalizers since version 2.
         fn next_session_authority_data() -> Result<SessionAuthorityData, ApiError>;
+        #[changed_in(2)]
+        fn authority_data() -> LegacySessionAuthorityData;
+        /// Returns the authority data of the current session, with a threshold set of emergency
+        /// finalizers since version 2.
         fn authority_data() -> SessionAuthorityData;
         fn session_period() -> u32;
         fn millisecs_per_block() -> u64;
         fn finality_version() -> Version;
         fn next_session_finality_version() -> Version;
+        /// Returns the scheduled finality version change, if any. Since version 3.
+        fn finality_version_change() -> Option<VersionChange>;
+        /// Returns all recorded finality version changes, ordered by session. Since version 3.
+        fn finality_version_history() -> Vec<VersionChange>;
+        /// Returns the authority data of the given session, if the session is recent enough to be
+        /// kept in the on-chain history. Since version 3.
+        fn authority_data_for_session(session: SessionIndex) -> Option<SessionAuthorityData>;
         /// Predict finality committee and block producers for the given session. `session` must be
         /// within the current era (current, in the staking context).
         ///
@@ -29,6 +47,16 @@ sp_api::decl_runtime_apis! {
             session: SessionIndex
         ) -> Result<SessionCommittee<AccountId>, SessionValidatorError>;
         fn next_session_aura_authorities() -> Vec<(AccountId, AuraId)>;
+        /// Returns validators and committee sizes of the current and the next era, together with
+        /// the thresholds used for banning validators. Since version 3.
+        fn era_committee_info() -> EraCommitteeInfo<AccountId>;
+        /// Returns block production summary of validators in the given era. Empty if the era is
+        /// older than the kept history. Since version 3.
+        fn era_performance_report(era: EraIndex) -> Vec<(AccountId, ValidatorEraPerformance)>;
+        /// Returns validators that are going to be removed from the committee, with reasons:
+        /// the ones already banned from the next era and the ones that would be banned if the
+        /// current session ended now. Since version 3.
+        fn projected_bans() -> Vec<(AccountId, BanReason)>;
         /// Returns owner (`AccountId`) corresponding to an AuthorityId (in some contexts referenced
         /// also as `aleph_key` - consensus engine's part of session keys) in the current session
         /// of AlephBFT (finalisation committee).
@@ -39,5 +67,21 @@ sp_api::decl_runtime_apis! {
         fn current_era_payout() -> (Balance, Balance);
         /// Submits score for a nonce in a session of performance of finality committee members.
         fn submit_abft_score(score: Score, signature: SignatureSet<AuthoritySignature>) -> Option<()>;
+        /// Submits a proof that an authority created two different units for the same round of the
+        /// current session. Since version 3.
+        fn submit_equivocation_report(proof: EquivocationProof<AuthoritySignature>) -> Option<()>;
+        /// Returns the blocks of the given session finalized by the emergency finalizer that were
+        /// noted on chain. Only recent sessions are kept. Since version 3.
+        fn emergency_finalizations(session: SessionIndex) -> Vec<(BlockHash, EmergencyFinalization<BlockNumber, AuthorityId>)>;
+        /// Submits the signatures of emergency finalizers over a block, so that it is noted on chain.
+        /// Since version 3.
+        fn submit_emergency_finalization(
+            hash: BlockHash,
+            number: BlockNumber,
+            signatures: SignatureSet<AuthoritySignature>,
+        ) -> Option<()>;
+        /// Returns the unit creation delay in milliseconds to be used in the next session, if set
+        /// on chain. Since version 3.
+        fn next_session_unit_creation_delay() -> Option<u64>;
     }
 }
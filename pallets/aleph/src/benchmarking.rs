@@ -0,0 +1,60 @@
+use frame_benchmarking::v2::*;
+use frame_support::sp_runtime::RuntimeAppPublic;
+use frame_system::RawOrigin;
+use parity_scale_codec::Encode;
+use primitives::EquivocationProof;
+use sp_runtime::traits::Hash;
+use sp_std::{vec, vec::Vec};
+
+use crate::{Authorities, Call, Config, Pallet, ReportedEquivocations};
+
+/// Encoding of a unit created by `creator` in the first round of `session`, with the same layout
+/// as AlephBFT units: the coordinates open the encoding and the session closes it.
+fn encoded_unit(creator: u64, session: u64, combined_hash: u8) -> Vec<u8> {
+    let mut unit = (creator, 0u16).encode();
+    unit.extend(vec![combined_hash; 32]);
+    unit.extend(session.encode());
+    unit
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn report_equivocation() {
+        let offender = T::AuthorityId::generate_pair(None);
+        Authorities::<T>::put(vec![offender.clone()]);
+        let session = Pallet::<T>::current_session().into();
+        let sign = |unit: &Vec<u8>| {
+            offender
+                .sign(&T::Hashing::hash(unit))
+                .expect("The key was generated in the keystore")
+        };
+        let first_unit = encoded_unit(0, session, 1);
+        let second_unit = encoded_unit(0, session, 2);
+        let proof = EquivocationProof {
+            first_signature: sign(&first_unit),
+            second_signature: sign(&second_unit),
+            first_unit,
+            second_unit,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::None, proof);
+
+        assert!(ReportedEquivocations::<T>::contains_key(0));
+    }
+
+    impl_benchmark_test_suite!(
+        Pallet,
+        {
+            let mut ext = crate::mock::new_test_ext(&[]);
+            ext.register_extension(sp_keystore::KeystoreExt::new(
+                sp_keystore::testing::MemoryKeystore::new(),
+            ));
+            ext
+        },
+        crate::mock::Test
+    );
+}
@@ -2,8 +2,9 @@ use primitives::{AbftScoresProvider, FinalityCommitteeManager, Score, SessionInd
 use sp_std::vec::Vec;
 
 use crate::{
-    AbftScores, Config, Event, FinalityScheduledVersionChange, FinalityVersion, LastScoreNonce,
-    NextFinalityCommittee, Pallet,
+    AbftScores, Config, Event, FinalityScheduledVersionChange, FinalityVersion,
+    FinalityVersionHistory, LastScoreNonce, NextFinalityCommittee, Pallet,
+    ScheduledUnitCreationDelay, UnitCreationDelay,
 };
 
 impl<T> pallet_session::SessionManager<T::AccountId> for Pallet<T>
@@ -25,6 +26,7 @@ where
     fn start_session(start_index: SessionIndex) {
         <T as Config>::SessionManager::start_session(start_index);
         Self::update_version_change_history();
+        Self::update_unit_creation_delay();
     }
 }
 
@@ -44,6 +46,7 @@ where
             // Record the scheduled version as the current version as it moves into the past.
             if scheduled_session == current_session {
                 <FinalityVersion<T>>::put(scheduled_version);
+                FinalityVersionHistory::<T>::insert(scheduled_session, scheduled_version);
 
                 // Reset the scheduled version.
                 <FinalityScheduledVersionChange<T>>::kill();
@@ -52,6 +55,14 @@ where
             }
         }
     }
+
+    // Apply the unit creation delay scheduled for the session that has just started.
+    fn update_unit_creation_delay() {
+        if let Some(delay) = <ScheduledUnitCreationDelay<T>>::take() {
+            <UnitCreationDelay<T>>::put(delay);
+            Self::deposit_event(Event::UnitCreationDelayChange(delay));
+        }
+    }
 }
 
 impl<T: Config> FinalityCommitteeManager<T::AccountId> for Pallet<T> {
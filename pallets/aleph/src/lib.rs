@@ -1,12 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
 mod impls;
+pub mod migrations;
 pub mod traits;
 
 use frame_support::{
@@ -16,14 +19,15 @@ use frame_support::{
 pub use pallet::*;
 use primitives::{
     crypto::{AuthorityVerifier, SignatureSet},
-    Balance, SessionIndex, Version, VersionChange, DEFAULT_FINALITY_VERSION,
+    Balance, BoundedVec, EmergencyFinalization, EmergencyFinalizerSet, EquivocationHandler,
+    EquivocationProof, SessionIndex, UnitCoord, Version, VersionChange, DEFAULT_FINALITY_VERSION,
     LEGACY_FINALITY_VERSION, TOKEN,
 };
 use sp_runtime::Perbill;
 use sp_std::prelude::*;
 
 /// The current storage version.
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 pub(crate) const LOG_TARGET: &str = "pallet-aleph";
 
 #[frame_support::pallet]
@@ -58,6 +62,19 @@ pub mod pallet {
         type SessionManager: SessionManager<<Self as frame_system::Config>::AccountId>;
         type NextSessionAuthorityProvider: NextSessionAuthorityProvider<Self>;
         type TotalIssuanceProvider: TotalIssuanceProvider;
+        type EquivocationHandler: EquivocationHandler<Self::AuthorityId>;
+        /// Number of recent sessions for which authorities are kept in
+        /// `SessionAuthoritiesHistory`. Has to be positive.
+        #[pallet::constant]
+        type SessionAuthoritiesHistoryDepth: Get<SessionIndex>;
+        /// Maximal unit creation delay in milliseconds that can be set with
+        /// `set_unit_creation_delay`.
+        #[pallet::constant]
+        type MaxUnitCreationDelay: Get<u64>;
+        /// Maximal number of keys in an emergency finalizer set. Values above
+        /// `primitives::MAX_EMERGENCY_FINALIZERS` are not supported.
+        #[pallet::constant]
+        type MaxEmergencyFinalizers: Get<u32>;
     }
 
     pub type Signature<T> = <<T as Config>::AuthorityId as RuntimeAppPublic>::Signature;
@@ -65,10 +82,30 @@ pub mod pallet {
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
-        ChangeEmergencyFinalizer(T::AuthorityId),
+        ChangeEmergencyFinalizer(EmergencyFinalizerSet<T::AuthorityId>),
+        ScheduleEmergencyFinalizerRotation(EmergencyFinalizerSet<T::AuthorityId>, SessionIndex),
+        ClearEmergencyFinalizer,
         ScheduleFinalityVersionChange(VersionChange),
+        CancelFinalityVersionChange(VersionChange),
         FinalityVersionChange(VersionChange),
         InflationParametersChange(Balance, u64),
+        EquivocationReported(T::AuthorityId, SessionIndex),
+        BlockEmergencyFinalized(T::Hash, SessionIndex, EmergencyFinalizerSet<T::AuthorityId>),
+        ScheduleUnitCreationDelayChange(u64),
+        UnitCreationDelayChange(u64),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Equivocation proof is malformed, stale or its signatures are not valid.
+        InvalidEquivocationProof,
+        /// Block was already noted as emergency finalized or it was not signed by enough
+        /// emergency finalizers.
+        InvalidEmergencyFinalization,
+        /// Emergency finalizer threshold is zero or exceeds the number of keys.
+        InvalidEmergencyFinalizerThreshold,
+        /// Emergency finalizer set has more keys than `primitives::MAX_EMERGENCY_FINALIZERS`.
+        TooManyEmergencyFinalizers,
     }
 
     #[pallet::pallet]
@@ -116,15 +153,22 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn emergency_finalizer)]
-    pub(super) type EmergencyFinalizer<T: Config> = StorageValue<_, T::AuthorityId, OptionQuery>;
+    pub(super) type EmergencyFinalizer<T: Config> =
+        StorageValue<_, EmergencyFinalizerSet<T::AuthorityId>, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn queued_emergency_finalizer)]
     pub(super) type QueuedEmergencyFinalizer<T: Config> =
-        StorageValue<_, T::AuthorityId, OptionQuery>;
+        StorageValue<_, EmergencyFinalizerSet<T::AuthorityId>, OptionQuery>;
 
     #[pallet::storage]
-    type NextEmergencyFinalizer<T: Config> = StorageValue<_, T::AuthorityId, OptionQuery>;
+    pub(super) type NextEmergencyFinalizer<T: Config> =
+        StorageValue<_, EmergencyFinalizerSet<T::AuthorityId>, OptionQuery>;
+
+    /// Emergency finalizers scheduled to be used from the given session onwards.
+    #[pallet::storage]
+    pub(super) type EmergencyFinalizerRotations<T: Config> =
+        StorageMap<_, Twox64Concat, SessionIndex, EmergencyFinalizerSet<T::AuthorityId>>;
 
     /// Current finality version.
     #[pallet::storage]
@@ -132,12 +176,28 @@ pub mod pallet {
     pub(super) type FinalityVersion<T: Config> =
         StorageValue<_, Version, ValueQuery, DefaultFinalityVersion>;
 
+    /// Finality versions keyed by the first session they were used in. Only contains versions
+    /// set since genesis or since this history started being recorded.
+    #[pallet::storage]
+    pub type FinalityVersionHistory<T: Config> = StorageMap<_, Twox64Concat, SessionIndex, Version>;
+
     /// Scheduled finality version change.
     #[pallet::storage]
     #[pallet::getter(fn finality_version_change)]
     pub(super) type FinalityScheduledVersionChange<T: Config> =
         StorageValue<_, VersionChange, OptionQuery>;
 
+    /// Unit creation delay in milliseconds used by AlephBFT in the current session. If not set,
+    /// nodes use the delay they were configured with.
+    #[pallet::storage]
+    #[pallet::getter(fn unit_creation_delay)]
+    pub(super) type UnitCreationDelay<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+    /// Unit creation delay in milliseconds that will be used from the next session onwards.
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_unit_creation_delay)]
+    pub(super) type ScheduledUnitCreationDelay<T: Config> = StorageValue<_, u64, OptionQuery>;
+
     // clear this storage on session end
     #[pallet::storage]
     #[pallet::getter(fn abft_scores)]
@@ -147,6 +207,36 @@ pub mod pallet {
     #[pallet::getter(fn last_score_nonce)]
     pub(super) type LastScoreNonce<T: Config> = StorageValue<_, ScoreNonce, ValueQuery>;
 
+    /// Authorities and emergency finalizers of the recent sessions, at most
+    /// `T::SessionAuthoritiesHistoryDepth` of them.
+    #[pallet::storage]
+    pub type SessionAuthoritiesHistory<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        SessionIndex,
+        (
+            Vec<T::AuthorityId>,
+            Option<EmergencyFinalizerSet<T::AuthorityId>>,
+        ),
+    >;
+
+    /// Blocks finalized by the emergency finalizer, keyed by their session. Kept for the same
+    /// sessions as `SessionAuthoritiesHistory`.
+    #[pallet::storage]
+    pub type EmergencyFinalizations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SessionIndex,
+        Twox64Concat,
+        T::Hash,
+        EmergencyFinalization<BlockNumberFor<T>, T::AuthorityId>,
+    >;
+
+    /// Indices of the current authorities already reported for equivocating in this session.
+    // clear this storage on session end
+    #[pallet::storage]
+    pub(super) type ReportedEquivocations<T: Config> = StorageMap<_, Twox64Concat, u64, ()>;
+
     impl<T: Config> Pallet<T> {
         pub(crate) fn initialize_authorities(
             authorities: &[T::AuthorityId],
@@ -193,20 +283,61 @@ pub mod pallet {
             <NextAuthorities<T>>::put(next_authorities);
         }
 
+        // Every value moves one step closer to being used, so clearing the next emergency
+        // finalizer disables emergency finalization two sessions later.
         pub(crate) fn update_emergency_finalizer() {
-            if let Some(emergency_finalizer) = <QueuedEmergencyFinalizer<T>>::get() {
-                <EmergencyFinalizer<T>>::put(emergency_finalizer)
+            <EmergencyFinalizer<T>>::set(<QueuedEmergencyFinalizer<T>>::get());
+            <QueuedEmergencyFinalizer<T>>::set(<NextEmergencyFinalizer<T>>::get());
+
+            let rotation_session = Self::current_session() + 2;
+            if let Some(emergency_finalizer) =
+                EmergencyFinalizerRotations::<T>::take(rotation_session)
+            {
+                Self::set_next_emergency_finalizer(emergency_finalizer.clone());
+                Self::deposit_event(Event::ChangeEmergencyFinalizer(emergency_finalizer));
             }
+        }
 
-            if let Some(emergency_finalizer) = <NextEmergencyFinalizer<T>>::get() {
-                <QueuedEmergencyFinalizer<T>>::put(emergency_finalizer)
+        pub(crate) fn record_session_authorities() {
+            let session = Self::current_session();
+            SessionAuthoritiesHistory::<T>::insert(
+                session,
+                (Self::authorities(), Self::emergency_finalizer()),
+            );
+            if let Some(expired) = session.checked_sub(T::SessionAuthoritiesHistoryDepth::get()) {
+                SessionAuthoritiesHistory::<T>::remove(expired);
+                let _ = EmergencyFinalizations::<T>::clear_prefix(expired, u32::MAX, None);
             }
         }
 
-        pub(crate) fn set_next_emergency_finalizer(emergency_finalizer: T::AuthorityId) {
+        /// Authorities and emergency finalizers of the given session, if it is recent enough.
+        pub fn authorities_for_session(
+            session: SessionIndex,
+        ) -> Option<(
+            Vec<T::AuthorityId>,
+            Option<EmergencyFinalizerSet<T::AuthorityId>>,
+        )> {
+            SessionAuthoritiesHistory::<T>::get(session)
+        }
+
+        pub(crate) fn set_next_emergency_finalizer(
+            emergency_finalizer: EmergencyFinalizerSet<T::AuthorityId>,
+        ) {
             <NextEmergencyFinalizer<T>>::put(emergency_finalizer);
         }
 
+        fn emergency_finalizer_set(
+            keys: BoundedVec<T::AuthorityId, T::MaxEmergencyFinalizers>,
+            threshold: u32,
+        ) -> Result<EmergencyFinalizerSet<T::AuthorityId>, Error<T>> {
+            ensure!(
+                threshold > 0 && threshold as usize <= keys.len(),
+                Error::<T>::InvalidEmergencyFinalizerThreshold
+            );
+            EmergencyFinalizerSet::new(keys.into_inner(), threshold)
+                .ok_or(Error::<T>::TooManyEmergencyFinalizers)
+        }
+
         pub(crate) fn current_session() -> u32 {
             T::SessionInfoProvider::current_session()
         }
@@ -237,6 +368,33 @@ pub mod pallet {
             Ok(())
         }
 
+        pub(crate) fn do_cancel_finality_version_change() -> Result<VersionChange, &'static str> {
+            let version_change = Self::finality_version_change()
+                .ok_or("There is no scheduled finality version change to cancel!")?;
+
+            if version_change.session < Self::current_session() + 2 {
+                return Err(
+                    "Tried to cancel a finality version change less than 2 sessions in advance!",
+                );
+            }
+
+            <FinalityScheduledVersionChange<T>>::kill();
+
+            Ok(version_change)
+        }
+
+        /// Recorded finality version changes, ordered by session.
+        pub fn finality_version_history() -> Vec<VersionChange> {
+            let mut history: Vec<_> = FinalityVersionHistory::<T>::iter()
+                .map(|(session, version_incoming)| VersionChange {
+                    version_incoming,
+                    session,
+                })
+                .collect();
+            history.sort_by_key(|version_change| version_change.session);
+            history
+        }
+
         pub fn next_session_finality_version() -> Version {
             let next_session = Self::current_session() + 1;
             let scheduled_version_change = Self::finality_version_change();
@@ -250,6 +408,11 @@ pub mod pallet {
             Self::finality_version()
         }
 
+        /// Unit creation delay that AlephBFT will use in the next session, if set on chain.
+        pub fn next_session_unit_creation_delay() -> Option<u64> {
+            Self::scheduled_unit_creation_delay().or_else(Self::unit_creation_delay)
+        }
+
         pub fn check_horizon_upper_bound(
             new_horizon: u64,
             current_horizon: u64,
@@ -352,6 +515,101 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Checks that the proof shows two different units created by the same current authority
+        /// for the same round of the current session, and that this authority was not reported
+        /// already. Returns the index and key of the offender.
+        pub fn check_equivocation(
+            proof: &EquivocationProof<Signature<T>>,
+        ) -> Result<(u64, T::AuthorityId), TransactionValidityError> {
+            let coord = UnitCoord::from_encoded_unit(&proof.first_unit)
+                .ok_or(InvalidTransaction::BadProof)?;
+            if Some(coord) != UnitCoord::from_encoded_unit(&proof.second_unit)
+                || proof.first_unit == proof.second_unit
+            {
+                return Err(InvalidTransaction::BadProof.into());
+            }
+            let session =
+                SessionIndex::try_from(coord.session).map_err(|_| InvalidTransaction::BadProof)?;
+            Self::check_session_id(session)?;
+            if ReportedEquivocations::<T>::contains_key(coord.creator) {
+                return Err(InvalidTransaction::Stale.into());
+            }
+
+            let offender = Self::authorities()
+                .get(coord.creator as usize)
+                .cloned()
+                .ok_or(InvalidTransaction::BadProof)?;
+            let signed_units = [
+                (&proof.first_unit, &proof.first_signature),
+                (&proof.second_unit, &proof.second_signature),
+            ];
+            for (unit, signature) in signed_units {
+                if !offender.verify(&T::Hashing::hash(unit), signature) {
+                    return Err(InvalidTransaction::BadProof.into());
+                }
+            }
+
+            Ok((coord.creator, offender))
+        }
+
+        /// Checks that the block with the given number is a recent block of this chain which was
+        /// not noted yet, and that it was signed by enough of the emergency finalizers of the
+        /// session it belongs to. Returns the session and the emergency finalizers.
+        pub fn check_emergency_finalization(
+            block_hash: &T::Hash,
+            block_number: BlockNumberFor<T>,
+            signatures: &SignatureSet<Signature<T>>,
+        ) -> Result<(SessionIndex, EmergencyFinalizerSet<T::AuthorityId>), TransactionValidityError>
+        {
+            if frame_system::Pallet::<T>::block_hash(block_number) != *block_hash {
+                return Err(InvalidTransaction::BadProof.into());
+            }
+            let session = T::SessionInfoProvider::session_of_block(block_number);
+            if EmergencyFinalizations::<T>::contains_key(session, block_hash) {
+                return Err(InvalidTransaction::Stale.into());
+            }
+            let (_, finalizers) =
+                Self::authorities_for_session(session).ok_or(InvalidTransaction::Stale)?;
+            let finalizers = finalizers.ok_or(InvalidTransaction::BadProof)?;
+            if !finalizers.is_complete(&block_hash.encode(), signatures) {
+                return Err(InvalidTransaction::BadProof.into());
+            }
+
+            Ok((session, finalizers))
+        }
+
+        /// Blocks of the given session finalized by the emergency finalizer.
+        pub fn emergency_finalizations(
+            session: SessionIndex,
+        ) -> Vec<(
+            T::Hash,
+            EmergencyFinalization<BlockNumberFor<T>, T::AuthorityId>,
+        )> {
+            EmergencyFinalizations::<T>::iter_prefix(session).collect()
+        }
+
+        pub fn submit_emergency_finalization(
+            block_hash: T::Hash,
+            block_number: BlockNumberFor<T>,
+            signatures: SignatureSet<Signature<T>>,
+        ) -> Option<()> {
+            use frame_system::offchain::SubmitTransaction;
+
+            let call = Call::note_emergency_finalization {
+                block_hash,
+                block_number,
+                signatures,
+            };
+            SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).ok()
+        }
+
+        pub fn submit_equivocation_report(proof: EquivocationProof<Signature<T>>) -> Option<()> {
+            use frame_system::offchain::SubmitTransaction;
+
+            let call = Call::report_equivocation { proof };
+            SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).ok()
+        }
+
         pub fn submit_abft_score(
             score: Score,
             signature: SignatureSet<Signature<T>>,
@@ -365,17 +623,68 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Sets the emergency finalization key. If called in session `N` the key can be used to
-        /// finalize blocks from session `N+2` onwards, until it gets overridden.
+        /// Sets the emergency finalization keys, `threshold` of which have to sign a block to
+        /// finalize it. If called in session `N` the keys can be used to finalize blocks from
+        /// session `N+2` onwards, until they get overridden.
         #[pallet::call_index(0)]
         #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
         pub fn set_emergency_finalizer(
             origin: OriginFor<T>,
-            emergency_finalizer: T::AuthorityId,
+            emergency_finalizers: BoundedVec<T::AuthorityId, T::MaxEmergencyFinalizers>,
+            threshold: u32,
         ) -> DispatchResult {
             ensure_root(origin)?;
-            Self::set_next_emergency_finalizer(emergency_finalizer.clone());
-            Self::deposit_event(Event::ChangeEmergencyFinalizer(emergency_finalizer));
+            let emergency_finalizers =
+                Self::emergency_finalizer_set(emergency_finalizers, threshold)?;
+            Self::set_next_emergency_finalizer(emergency_finalizers.clone());
+            Self::deposit_event(Event::ChangeEmergencyFinalizer(emergency_finalizers));
+            Ok(())
+        }
+
+        /// Schedules the emergency finalization keys to be used from the given session onwards,
+        /// which has to be at least 2 sessions in the future. Replaces any rotation already
+        /// scheduled for the same session.
+        #[pallet::call_index(7)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn schedule_emergency_finalizer_rotation(
+            origin: OriginFor<T>,
+            emergency_finalizers: BoundedVec<T::AuthorityId, T::MaxEmergencyFinalizers>,
+            threshold: u32,
+            session: SessionIndex,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let emergency_finalizers =
+                Self::emergency_finalizer_set(emergency_finalizers, threshold)?;
+
+            let current_session = Self::current_session();
+            if session < current_session + 2 {
+                return Err(DispatchError::Other(
+                    "Tried to schedule an emergency finalizer rotation less than 2 sessions in advance!",
+                ));
+            }
+
+            if session == current_session + 2 {
+                Self::set_next_emergency_finalizer(emergency_finalizers.clone());
+            } else {
+                EmergencyFinalizerRotations::<T>::insert(session, emergency_finalizers.clone());
+            }
+            Self::deposit_event(Event::ScheduleEmergencyFinalizerRotation(
+                emergency_finalizers,
+                session,
+            ));
+            Ok(())
+        }
+
+        /// Disables emergency finalization and cancels all scheduled rotations. If called in
+        /// session `N`, blocks from session `N+2` onwards cannot be emergency finalized, until
+        /// new keys are set.
+        #[pallet::call_index(8)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn clear_emergency_finalizer(origin: OriginFor<T>) -> DispatchResult {
+            ensure_root(origin)?;
+            <NextEmergencyFinalizer<T>>::kill();
+            let _ = EmergencyFinalizerRotations::<T>::clear(u32::MAX, None);
+            Self::deposit_event(Event::ClearEmergencyFinalizer);
             Ok(())
         }
 
@@ -383,8 +692,7 @@ pub mod pallet {
         /// version is already set, it is replaced with the provided one.
         /// Any rescheduling of a future version change needs to occur at least 2 sessions in
         /// advance of the provided session of the version change.
-        /// In order to cancel a scheduled version change, a new version change should be scheduled
-        /// with the same version as the current one.
+        /// In order to cancel a scheduled version change, use `cancel_finality_version_change`.
         #[pallet::call_index(1)]
         #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
         pub fn schedule_finality_version_change(
@@ -407,6 +715,42 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Cancels the scheduled finality version change. Same as rescheduling, this needs to occur
+        /// at least 2 sessions in advance of the session of the version change.
+        #[pallet::call_index(6)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn cancel_finality_version_change(origin: OriginFor<T>) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let version_change =
+                Self::do_cancel_finality_version_change().map_err(DispatchError::Other)?;
+
+            Self::deposit_event(Event::CancelFinalityVersionChange(version_change));
+            Ok(())
+        }
+
+        /// Sets the unit creation delay in milliseconds that AlephBFT will use from the next
+        /// session onwards. Replaces any delay already scheduled for the next session. The delay
+        /// cannot exceed `MaxUnitCreationDelay`.
+        #[pallet::call_index(9)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn set_unit_creation_delay(origin: OriginFor<T>, delay: u64) -> DispatchResult {
+            ensure_root(origin)?;
+            if delay == 0 {
+                return Err(DispatchError::Other(
+                    "Unit creation delay has to be positive!",
+                ));
+            }
+            if delay > T::MaxUnitCreationDelay::get() {
+                return Err(DispatchError::Other(
+                    "Unit creation delay cannot exceed MaxUnitCreationDelay!",
+                ));
+            }
+            <ScheduledUnitCreationDelay<T>>::put(delay);
+            Self::deposit_event(Event::ScheduleUnitCreationDelayChange(delay));
+            Ok(())
+        }
+
         /// Sets the values of inflation parameters.
         #[pallet::call_index(2)]
         #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
@@ -460,6 +804,62 @@ pub mod pallet {
 
             Ok(Pays::No.into())
         }
+
+        /// Reports an authority that created two different units for the same round of the
+        /// current session. The offender is passed to `T::EquivocationHandler` to be punished, at
+        /// most once per session.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::BlockWeights::get().max_block * Perbill::from_percent(10))]
+        pub fn report_equivocation(
+            origin: OriginFor<T>,
+            proof: EquivocationProof<Signature<T>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+
+            let (index, offender) = Self::check_equivocation(&proof)
+                .map_err(|_| Error::<T>::InvalidEquivocationProof)?;
+            let session = Self::current_session();
+
+            ReportedEquivocations::<T>::insert(index, ());
+            T::EquivocationHandler::on_equivocation(&offender, session);
+            Self::deposit_event(Event::EquivocationReported(offender, session));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Records that the block was finalized with the emergency finalizers, which is proven by
+        /// the signatures of at least threshold emergency finalizers of the session of the block
+        /// over its hash, indexed by the position of their keys in the set. The block has to be
+        /// one of the recent blocks of this chain.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::BlockWeights::get().max_block * Perbill::from_percent(10))]
+        pub fn note_emergency_finalization(
+            origin: OriginFor<T>,
+            block_hash: T::Hash,
+            block_number: BlockNumberFor<T>,
+            signatures: SignatureSet<Signature<T>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+
+            let (session, finalizers) =
+                Self::check_emergency_finalization(&block_hash, block_number, &signatures)
+                    .map_err(|_| Error::<T>::InvalidEmergencyFinalization)?;
+
+            EmergencyFinalizations::<T>::insert(
+                session,
+                block_hash,
+                EmergencyFinalization {
+                    block_number,
+                    session,
+                    finalizers: finalizers.clone(),
+                },
+            );
+            Self::deposit_event(Event::BlockEmergencyFinalized(
+                block_hash, session, finalizers,
+            ));
+
+            Ok(Pays::No.into())
+        }
     }
 
     #[pallet::validate_unsigned]
@@ -467,15 +867,39 @@ pub mod pallet {
         type Call = Call<T>;
 
         fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-            if let Call::unsigned_submit_abft_score { score, signature } = call {
-                Self::check_score(score, signature)?;
-                ValidTransaction::with_tag_prefix("AbftScore")
-                    .priority(score.nonce as u64) // this ensures that later nonces are first in tx queue
-                    .longevity(TransactionLongevity::MAX) // consider restricting longevity
-                    .propagate(true)
-                    .build()
-            } else {
-                InvalidTransaction::Call.into()
+            match call {
+                Call::unsigned_submit_abft_score { score, signature } => {
+                    Self::check_score(score, signature)?;
+                    ValidTransaction::with_tag_prefix("AbftScore")
+                        .priority(score.nonce as u64) // this ensures that later nonces are first in tx queue
+                        .longevity(TransactionLongevity::MAX) // consider restricting longevity
+                        .propagate(true)
+                        .build()
+                }
+                Call::report_equivocation { proof } => {
+                    let (index, _) = Self::check_equivocation(proof)?;
+                    ValidTransaction::with_tag_prefix("AlephEquivocation")
+                        .priority(TransactionPriority::MAX)
+                        // one report per offender per session is enough
+                        .and_provides((Self::current_session(), index))
+                        .longevity(TransactionLongevity::MAX)
+                        .propagate(true)
+                        .build()
+                }
+                Call::note_emergency_finalization {
+                    block_hash,
+                    block_number,
+                    signatures,
+                } => {
+                    Self::check_emergency_finalization(block_hash, *block_number, signatures)?;
+                    ValidTransaction::with_tag_prefix("AlephEmergencyFinalization")
+                        .priority(TransactionPriority::MAX)
+                        .and_provides(block_hash)
+                        .longevity(TransactionLongevity::MAX)
+                        .propagate(true)
+                        .build()
+                }
+                _ => InvalidTransaction::Call.into(),
             }
         }
     }
@@ -495,6 +919,7 @@ pub mod pallet {
             let (_, authorities): (Vec<_>, Vec<_>) = validators.unzip();
             // it is guaranteed that the first validator set will also be used in the next session
             Self::initialize_authorities(authorities.as_slice(), authorities.as_slice());
+            Self::record_session_authorities();
         }
 
         fn on_new_session<'a, I>(changed: bool, _: I, queued_validators: I)
@@ -503,9 +928,11 @@ pub mod pallet {
             T::AccountId: 'a,
         {
             Self::update_emergency_finalizer();
+            let _ = ReportedEquivocations::<T>::clear(u32::MAX, None);
             if changed {
                 Self::update_authorities(queued_validators.collect());
             }
+            Self::record_session_authorities();
         }
 
         fn on_disabled(_validator_index: u32) {}
@@ -530,6 +957,7 @@ pub mod pallet {
     impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
         fn build(&self) {
             <FinalityVersion<T>>::put(self.finality_version);
+            FinalityVersionHistory::<T>::insert(0, self.finality_version);
         }
     }
 }
@@ -0,0 +1,88 @@
+//! Storage migrations of the pallet.
+
+pub mod v3 {
+    use frame_support::{
+        pallet_prelude::StorageVersion, traits::OnRuntimeUpgrade, weights::Weight,
+    };
+    use primitives::EmergencyFinalizerSet;
+    use sp_std::marker::PhantomData;
+
+    use crate::{
+        Config, EmergencyFinalizer, NextEmergencyFinalizer, Pallet, QueuedEmergencyFinalizer,
+        LOG_TARGET,
+    };
+
+    /// Replaces every single emergency finalizer key with a set containing only this key, with
+    /// threshold one.
+    pub struct Migration<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != StorageVersion::new(2) {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Skipping emergency finalizer migration, storage version is not 2."
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let to_set = |key: Option<T::AuthorityId>| {
+                key.map(EmergencyFinalizerSet::<T::AuthorityId>::single)
+            };
+            if EmergencyFinalizer::<T>::translate(to_set).is_err()
+                || QueuedEmergencyFinalizer::<T>::translate(to_set).is_err()
+                || NextEmergencyFinalizer::<T>::translate(to_set).is_err()
+            {
+                log::error!(
+                    target: LOG_TARGET,
+                    "Failed to decode some of the emergency finalizer keys."
+                );
+            }
+            StorageVersion::new(3).put::<Pallet<T>>();
+            log::info!(target: LOG_TARGET, "Migrated emergency finalizers to storage version 3.");
+
+            T::DbWeight::get().reads_writes(4, 4)
+        }
+    }
+}
+
+pub mod v4 {
+    use frame_support::{
+        pallet_prelude::StorageVersion, traits::OnRuntimeUpgrade, weights::Weight,
+    };
+    use primitives::SessionInfoProvider;
+    use sp_std::marker::PhantomData;
+
+    use crate::{Config, FinalityVersion, FinalityVersionHistory, Pallet, LOG_TARGET};
+
+    /// Seeds `FinalityVersionHistory` with the current finality version at the current session,
+    /// so that chains started before the history was recorded know the version in use.
+    pub struct Migration<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != StorageVersion::new(3) {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Skipping finality version history migration, storage version is not 3."
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let session = T::SessionInfoProvider::current_session();
+            let version = FinalityVersion::<T>::get();
+            if !FinalityVersionHistory::<T>::contains_key(session) {
+                FinalityVersionHistory::<T>::insert(session, version);
+            }
+            StorageVersion::new(4).put::<Pallet<T>>();
+            log::info!(
+                target: LOG_TARGET,
+                "Recorded finality version {} at session {}, migrated to storage version 4.",
+                version,
+                session
+            );
+
+            T::DbWeight::get().reads_writes(4, 2)
+        }
+    }
+}
@@ -1,20 +1,23 @@
+use std::cell::RefCell;
+
 use frame_support::{
     construct_runtime,
     pallet_prelude::ConstU32,
     parameter_types,
-    traits::{EstimateNextSessionRotation, OnFinalize, OnInitialize},
+    traits::{EstimateNextSessionRotation, Get, OnFinalize, OnInitialize},
     weights::{RuntimeDbWeight, Weight},
 };
 use frame_system::pallet_prelude::BlockNumberFor;
 use primitives::{
-    AuthorityId, SessionInfoProvider, TotalIssuanceProvider as TotalIssuanceProviderT,
+    AuthorityId, EquivocationHandler, SessionInfoProvider,
+    TotalIssuanceProvider as TotalIssuanceProviderT,
 };
-use sp_core::H256;
+use sp_core::{ConstU64, H256};
 use sp_runtime::{
     impl_opaque_keys,
     testing::{TestXt, UintAuthorityId},
     traits::{ConvertInto, IdentityLookup, OpaqueKeys},
-    BuildStorage,
+    BoundedVec, BuildStorage,
 };
 
 use super::*;
@@ -116,6 +119,9 @@ impl SessionInfoProvider<BlockNumberFor<Test>> for SessionInfoImpl {
         )
         .0
     }
+    fn session_of_block(block: BlockNumberFor<Test>) -> SessionIndex {
+        (block / Period::get()) as SessionIndex
+    }
 }
 
 impl pallet_session::Config for Test {
@@ -156,6 +162,18 @@ impl TotalIssuanceProviderT for TotalIssuanceProvider {
     }
 }
 
+thread_local! {
+    pub static EQUIVOCATIONS: RefCell<Vec<(AuthorityId, SessionIndex)>> = RefCell::new(Vec::new());
+}
+
+pub struct EquivocationHandlerMock;
+impl EquivocationHandler<AuthorityId> for EquivocationHandlerMock {
+    fn on_equivocation(offender: &AuthorityId, session: SessionIndex) {
+        EQUIVOCATIONS
+            .with(|equivocations| equivocations.borrow_mut().push((offender.clone(), session)));
+    }
+}
+
 impl Config for Test {
     type AuthorityId = AuthorityId;
     type RuntimeEvent = RuntimeEvent;
@@ -163,6 +181,10 @@ impl Config for Test {
     type SessionManager = ();
     type NextSessionAuthorityProvider = Session;
     type TotalIssuanceProvider = TotalIssuanceProvider;
+    type EquivocationHandler = EquivocationHandlerMock;
+    type SessionAuthoritiesHistoryDepth = ConstU32<3>;
+    type MaxUnitCreationDelay = ConstU64<1000>;
+    type MaxEmergencyFinalizers = ConstU32<3>;
 }
 
 pub fn to_authority(id: &u64) -> AuthorityId {
@@ -173,6 +195,10 @@ pub fn to_authorities(authorities: &[u64]) -> Vec<AuthorityId> {
     authorities.iter().map(to_authority).collect()
 }
 
+pub fn bounded_authorities<S: Get<u32>>(authorities: &[u64]) -> BoundedVec<AuthorityId, S> {
+    BoundedVec::truncate_from(to_authorities(authorities))
+}
+
 pub fn new_session_validators(validators: &[u64]) -> impl Iterator<Item = (&u64, AuthorityId)> {
     validators.iter().zip(to_authorities(validators))
 }
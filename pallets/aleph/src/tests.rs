@@ -1,11 +1,38 @@
-use frame_support::{storage_alias, traits::OneSessionHandler};
-use primitives::VersionChange;
-
-use crate::{mock::*, NextFinalityCommittee};
+use frame_support::{
+    assert_noop, assert_ok, storage_alias,
+    traits::{OnRuntimeUpgrade, OneSessionHandler, StorageVersion},
+};
+use parity_scale_codec::Encode;
+use primitives::{
+    crypto::{IndexedSignature, SignatureSet},
+    AuthorityId, AuthorityPair, AuthoritySignature, EmergencyFinalization, EmergencyFinalizerSet,
+    EquivocationProof, VersionChange,
+};
+use sp_core::Pair;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+use crate::{
+    migrations, mock::*, Authorities, EmergencyFinalizer, Error, FinalityVersion,
+    FinalityVersionHistory, NextEmergencyFinalizer, NextFinalityCommittee,
+    QueuedEmergencyFinalizer, SessionAuthoritiesHistory,
+};
 
 #[storage_alias]
 type SessionForValidatorsChange = StorageValue<Aleph, u32>;
 
+mod v2 {
+    use frame_support::storage_alias;
+    use primitives::AuthorityId;
+
+    use crate::mock::Aleph;
+
+    #[storage_alias]
+    pub type EmergencyFinalizer = StorageValue<Aleph, AuthorityId>;
+
+    #[storage_alias]
+    pub type QueuedEmergencyFinalizer = StorageValue<Aleph, AuthorityId>;
+}
+
 #[storage_alias]
 type Validators<T> = StorageValue<Aleph, Vec<<T as frame_system::Config>::AccountId>>;
 
@@ -106,22 +133,123 @@ fn test_emergency_signer() {
 
         run_session(1);
 
-        Aleph::set_next_emergency_finalizer(to_authority(&21));
+        Aleph::set_next_emergency_finalizer(EmergencyFinalizerSet::single(to_authority(&21)));
 
         assert_eq!(Aleph::emergency_finalizer(), None);
         assert_eq!(Aleph::queued_emergency_finalizer(), None);
 
         run_session(2);
 
-        Aleph::set_next_emergency_finalizer(to_authority(&37));
+        Aleph::set_next_emergency_finalizer(EmergencyFinalizerSet::single(to_authority(&37)));
 
         assert_eq!(Aleph::emergency_finalizer(), None);
-        assert_eq!(Aleph::queued_emergency_finalizer(), Some(to_authority(&21)));
+        assert_eq!(
+            Aleph::queued_emergency_finalizer(),
+            Some(EmergencyFinalizerSet::single(to_authority(&21)))
+        );
 
         run_session(3);
 
-        assert_eq!(Aleph::emergency_finalizer(), Some(to_authority(&21)));
-        assert_eq!(Aleph::queued_emergency_finalizer(), Some(to_authority(&37)));
+        assert_eq!(
+            Aleph::emergency_finalizer(),
+            Some(EmergencyFinalizerSet::single(to_authority(&21)))
+        );
+        assert_eq!(
+            Aleph::queued_emergency_finalizer(),
+            Some(EmergencyFinalizerSet::single(to_authority(&37)))
+        );
+    })
+}
+
+#[test]
+fn test_emergency_finalizer_rotation() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(1);
+
+        let rotate_to = |id, session| {
+            Aleph::schedule_emergency_finalizer_rotation(
+                RuntimeOrigin::root(),
+                bounded_authorities(&[id]),
+                1,
+                session,
+            )
+        };
+        assert!(rotate_to(21, 2).is_err());
+        assert_ok!(rotate_to(21, 3));
+        assert_ok!(rotate_to(37, 5));
+
+        run_session(2);
+        assert_eq!(Aleph::emergency_finalizer(), None);
+        run_session(3);
+        assert_eq!(
+            Aleph::emergency_finalizer(),
+            Some(EmergencyFinalizerSet::single(to_authority(&21)))
+        );
+        run_session(4);
+        assert_eq!(
+            Aleph::emergency_finalizer(),
+            Some(EmergencyFinalizerSet::single(to_authority(&21)))
+        );
+        run_session(5);
+        assert_eq!(
+            Aleph::emergency_finalizer(),
+            Some(EmergencyFinalizerSet::single(to_authority(&37)))
+        );
+    })
+}
+
+#[test]
+fn test_clear_emergency_finalizer() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(1);
+        Aleph::set_next_emergency_finalizer(EmergencyFinalizerSet::single(to_authority(&21)));
+        run_session(3);
+        assert_ok!(Aleph::schedule_emergency_finalizer_rotation(
+            RuntimeOrigin::root(),
+            bounded_authorities(&[37]),
+            1,
+            6
+        ));
+
+        assert_ok!(Aleph::clear_emergency_finalizer(RuntimeOrigin::root()));
+
+        run_session(4);
+        assert_eq!(
+            Aleph::emergency_finalizer(),
+            Some(EmergencyFinalizerSet::single(to_authority(&21)))
+        );
+        assert_eq!(Aleph::queued_emergency_finalizer(), None);
+        run_session(6);
+        assert_eq!(Aleph::emergency_finalizer(), None);
+    })
+}
+
+#[test]
+fn test_unit_creation_delay_scheduling() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+
+        run_session(1);
+
+        assert_eq!(Aleph::unit_creation_delay(), None);
+        assert_eq!(Aleph::next_session_unit_creation_delay(), None);
+
+        assert!(Aleph::set_unit_creation_delay(RuntimeOrigin::root(), 0).is_err());
+        assert!(Aleph::set_unit_creation_delay(RuntimeOrigin::root(), 1001).is_err());
+        assert_ok!(Aleph::set_unit_creation_delay(RuntimeOrigin::root(), 1000));
+        assert_ok!(Aleph::set_unit_creation_delay(RuntimeOrigin::root(), 300));
+
+        // the new delay is only applied when the next session starts
+        assert_eq!(Aleph::unit_creation_delay(), None);
+        assert_eq!(Aleph::next_session_unit_creation_delay(), Some(300));
+
+        run_session(2);
+
+        assert_eq!(Aleph::unit_creation_delay(), Some(300));
+        assert_eq!(Aleph::scheduled_unit_creation_delay(), None);
+        assert_eq!(Aleph::next_session_unit_creation_delay(), Some(300));
     })
 }
 
@@ -161,3 +289,373 @@ fn test_finality_version_scheduling() {
         assert!(scheduling_result.is_err());
     })
 }
+
+#[test]
+fn test_finality_version_history() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(1);
+
+        let first_change = VersionChange {
+            version_incoming: 1,
+            session: 3,
+        };
+        let second_change = VersionChange {
+            version_incoming: 2,
+            session: 6,
+        };
+
+        assert_ok!(Aleph::do_schedule_finality_version_change(
+            first_change.clone()
+        ));
+        run_session(4);
+        assert_ok!(Aleph::do_schedule_finality_version_change(
+            second_change.clone()
+        ));
+        assert_eq!(
+            Aleph::finality_version_history(),
+            vec![first_change.clone()]
+        );
+
+        run_session(6);
+        assert_eq!(
+            Aleph::finality_version_history(),
+            vec![first_change, second_change]
+        );
+    })
+}
+
+#[test]
+fn test_cancel_finality_version_change() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(1);
+
+        assert!(Aleph::cancel_finality_version_change(RuntimeOrigin::root()).is_err());
+
+        let version_change = VersionChange {
+            version_incoming: 1,
+            session: 4,
+        };
+        assert_ok!(Aleph::do_schedule_finality_version_change(
+            version_change.clone()
+        ));
+        assert_ok!(Aleph::cancel_finality_version_change(RuntimeOrigin::root()));
+        assert_eq!(Aleph::finality_version_change(), None);
+
+        assert_ok!(Aleph::do_schedule_finality_version_change(version_change));
+        run_session(3);
+        assert!(Aleph::cancel_finality_version_change(RuntimeOrigin::root()).is_err());
+        assert!(Aleph::finality_version_change().is_some());
+    })
+}
+
+#[test]
+fn test_session_authorities_history() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        assert_eq!(
+            Aleph::authorities_for_session(0),
+            Some((to_authorities(&[1, 2]), None))
+        );
+
+        Aleph::set_next_emergency_finalizer(EmergencyFinalizerSet::single(to_authority(&21)));
+        run_session(4);
+
+        assert_eq!(
+            Aleph::authorities_for_session(4),
+            Some((
+                Aleph::authorities(),
+                Some(EmergencyFinalizerSet::single(to_authority(&21)))
+            ))
+        );
+        // the emergency finalizer is used from the second session after setting it
+        assert_eq!(
+            Aleph::authorities_for_session(2).map(|(_, emergency_finalizers)| emergency_finalizers),
+            Some(Some(EmergencyFinalizerSet::single(to_authority(&21))))
+        );
+        // history depth in the mock is 3
+        assert!(Aleph::authorities_for_session(3).is_some());
+        assert!(Aleph::authorities_for_session(1).is_none());
+        assert!(Aleph::authorities_for_session(0).is_none());
+    })
+}
+
+fn signed_unit(
+    pair: &AuthorityPair,
+    creator: u64,
+    round: u16,
+    data: u32,
+    session: u64,
+) -> (Vec<u8>, AuthoritySignature) {
+    let mut unit = (creator, round, data).encode();
+    unit.extend(session.encode());
+    let signature = pair.sign(BlakeTwo256::hash(&unit).as_ref());
+    (unit, signature)
+}
+
+fn equivocation_proof(
+    (first_unit, first_signature): (Vec<u8>, AuthoritySignature),
+    (second_unit, second_signature): (Vec<u8>, AuthoritySignature),
+) -> EquivocationProof<AuthoritySignature> {
+    EquivocationProof {
+        first_unit,
+        first_signature,
+        second_unit,
+        second_signature,
+    }
+}
+
+fn authority_pairs(count: u8) -> Vec<AuthorityPair> {
+    (0..count)
+        .map(|seed| AuthorityPair::from_seed(&[seed; 32]))
+        .collect()
+}
+
+#[test]
+fn test_report_equivocation() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        let pairs = authority_pairs(2);
+        Authorities::<Test>::put(pairs.iter().map(|pair| pair.public()).collect::<Vec<_>>());
+
+        let proof = equivocation_proof(
+            signed_unit(&pairs[1], 1, 3, 7, 0),
+            signed_unit(&pairs[1], 1, 3, 8, 0),
+        );
+
+        assert_ok!(Aleph::report_equivocation(
+            RuntimeOrigin::none(),
+            proof.clone()
+        ));
+        assert_eq!(
+            EQUIVOCATIONS.with(|equivocations| equivocations.borrow().clone()),
+            vec![(pairs[1].public(), 0)]
+        );
+        assert_noop!(
+            Aleph::report_equivocation(RuntimeOrigin::none(), proof),
+            Error::<Test>::InvalidEquivocationProof
+        );
+    })
+}
+
+#[test]
+fn test_invalid_equivocation_proofs() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(1);
+        let pairs = authority_pairs(2);
+        Authorities::<Test>::put(pairs.iter().map(|pair| pair.public()).collect::<Vec<_>>());
+
+        let invalid_proofs = [
+            // the same unit twice
+            equivocation_proof(
+                signed_unit(&pairs[0], 0, 3, 7, 1),
+                signed_unit(&pairs[0], 0, 3, 7, 1),
+            ),
+            // different rounds
+            equivocation_proof(
+                signed_unit(&pairs[0], 0, 3, 7, 1),
+                signed_unit(&pairs[0], 0, 4, 8, 1),
+            ),
+            // signed by somebody else than the creator
+            equivocation_proof(
+                signed_unit(&pairs[1], 0, 3, 7, 1),
+                signed_unit(&pairs[1], 0, 3, 8, 1),
+            ),
+            // past session
+            equivocation_proof(
+                signed_unit(&pairs[0], 0, 3, 7, 0),
+                signed_unit(&pairs[0], 0, 3, 8, 0),
+            ),
+        ];
+
+        for proof in invalid_proofs {
+            assert!(Aleph::check_equivocation(&proof).is_err());
+        }
+        assert!(EQUIVOCATIONS.with(|equivocations| equivocations.borrow().is_empty()));
+    })
+}
+
+fn signature_set(
+    signers: &[(u64, &AuthorityPair)],
+    msg: &[u8],
+) -> SignatureSet<AuthoritySignature> {
+    SignatureSet(
+        signers
+            .iter()
+            .map(|(index, pair)| IndexedSignature {
+                index: *index,
+                signature: pair.sign(msg),
+            })
+            .collect(),
+    )
+}
+
+fn public_keys(pairs: &[AuthorityPair]) -> Vec<AuthorityId> {
+    pairs.iter().map(|pair| pair.public()).collect()
+}
+
+#[test]
+fn test_set_emergency_finalizer_checks_threshold() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        let keys = bounded_authorities(&[21, 37, 42]);
+
+        for threshold in [0, 4] {
+            assert_noop!(
+                Aleph::set_emergency_finalizer(RuntimeOrigin::root(), keys.clone(), threshold),
+                Error::<Test>::InvalidEmergencyFinalizerThreshold
+            );
+        }
+        assert_ok!(Aleph::set_emergency_finalizer(
+            RuntimeOrigin::root(),
+            keys.clone(),
+            2
+        ));
+        assert_eq!(
+            NextEmergencyFinalizer::<Test>::get(),
+            EmergencyFinalizerSet::new(keys.into_inner(), 2)
+        );
+    })
+}
+
+#[test]
+fn test_migrates_single_emergency_finalizer_to_set() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        StorageVersion::new(2).put::<Aleph>();
+        v2::EmergencyFinalizer::put(to_authority(&21));
+        v2::QueuedEmergencyFinalizer::put(to_authority(&37));
+
+        migrations::v3::Migration::<Test>::on_runtime_upgrade();
+
+        assert_eq!(StorageVersion::get::<Aleph>(), StorageVersion::new(3));
+        assert_eq!(
+            EmergencyFinalizer::<Test>::get(),
+            Some(EmergencyFinalizerSet::single(to_authority(&21)))
+        );
+        assert_eq!(
+            QueuedEmergencyFinalizer::<Test>::get(),
+            Some(EmergencyFinalizerSet::single(to_authority(&37)))
+        );
+        assert_eq!(NextEmergencyFinalizer::<Test>::get(), None);
+    })
+}
+
+#[test]
+fn test_seeds_finality_version_history() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(3);
+        StorageVersion::new(3).put::<Aleph>();
+        let _ = FinalityVersionHistory::<Test>::clear(u32::MAX, None);
+        FinalityVersion::<Test>::put(2);
+
+        migrations::v4::Migration::<Test>::on_runtime_upgrade();
+
+        assert_eq!(StorageVersion::get::<Aleph>(), StorageVersion::new(4));
+        assert_eq!(
+            Aleph::finality_version_history(),
+            vec![VersionChange {
+                version_incoming: 2,
+                session: 3,
+            }]
+        );
+    })
+}
+
+#[test]
+fn test_note_emergency_finalization() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(3);
+        let pairs = authority_pairs(3);
+        let finalizers = EmergencyFinalizerSet::new(public_keys(&pairs), 2).unwrap();
+        SessionAuthoritiesHistory::<Test>::insert(2, (Vec::new(), Some(finalizers.clone())));
+
+        let block_hash = System::block_hash(2);
+        let signatures = signature_set(&[(0, &pairs[0]), (2, &pairs[2])], &block_hash.encode());
+
+        // the number does not match the hash
+        assert_noop!(
+            Aleph::note_emergency_finalization(
+                RuntimeOrigin::none(),
+                block_hash,
+                1,
+                signatures.clone()
+            ),
+            Error::<Test>::InvalidEmergencyFinalization
+        );
+        assert_ok!(Aleph::note_emergency_finalization(
+            RuntimeOrigin::none(),
+            block_hash,
+            2,
+            signatures.clone()
+        ));
+        assert_eq!(
+            Aleph::emergency_finalizations(2),
+            vec![(
+                block_hash,
+                EmergencyFinalization {
+                    block_number: 2,
+                    session: 2,
+                    finalizers,
+                }
+            )]
+        );
+        assert!(Aleph::emergency_finalizations(3).is_empty());
+        assert_noop!(
+            Aleph::note_emergency_finalization(RuntimeOrigin::none(), block_hash, 2, signatures),
+            Error::<Test>::InvalidEmergencyFinalization
+        );
+
+        // removed together with the authorities of the session
+        run_session(5);
+        assert!(Aleph::emergency_finalizations(2).is_empty());
+    })
+}
+
+#[test]
+fn test_emergency_finalization_requires_threshold_of_signatures() {
+    new_test_ext(&[(1u64, 1u64), (2u64, 2u64)]).execute_with(|| {
+        initialize_session();
+        run_session(3);
+        let pairs = authority_pairs(4);
+        let block_hash = System::block_hash(2);
+        let msg = block_hash.encode();
+
+        // no emergency finalizers set in the session of the block
+        assert!(Aleph::check_emergency_finalization(
+            &block_hash,
+            2,
+            &signature_set(&[(0, &pairs[0]), (1, &pairs[1])], &msg)
+        )
+        .is_err());
+
+        SessionAuthoritiesHistory::<Test>::insert(
+            2,
+            (
+                Vec::new(),
+                EmergencyFinalizerSet::new(public_keys(&pairs[..3]), 2),
+            ),
+        );
+        // finalizers of a different session than the current one are used
+        EmergencyFinalizer::<Test>::put(
+            EmergencyFinalizerSet::new(public_keys(&pairs), 1).unwrap(),
+        );
+        let invalid_signatures = [
+            // not enough signatures
+            signature_set(&[(0, &pairs[0])], &msg),
+            // the same signer twice
+            signature_set(&[(0, &pairs[0]), (0, &pairs[0])], &msg),
+            // signed by a key outside of the set
+            signature_set(&[(0, &pairs[0]), (3, &pairs[3])], &msg),
+            // signature not matching the index
+            signature_set(&[(0, &pairs[0]), (1, &pairs[2])], &msg),
+        ];
+        for signatures in invalid_signatures {
+            assert!(Aleph::check_emergency_finalization(&block_hash, 2, &signatures).is_err());
+        }
+        assert!(Aleph::emergency_finalizations(2).is_empty());
+    })
+}
@@ -2,8 +2,9 @@ use frame_support::pallet_prelude::Get;
 use log::info;
 use parity_scale_codec::Encode;
 use primitives::{
-    AbftScoresProvider, BanHandler, BanInfo, BanReason, BannedValidators, CommitteeSeats,
-    EraValidators, SessionCommittee, SessionValidatorError, SessionValidators, ValidatorProvider,
+    AbftScoresProvider, BanHandler, BanInfo, BanReason, BannedValidators, BlockCount,
+    CommitteeSeats, EraValidators, SessionCommittee, SessionValidatorError, SessionValidators,
+    ValidatorEraPerformance, ValidatorProvider,
 };
 use rand::{seq::SliceRandom, SeedableRng};
 use rand_pcg::Pcg32;
@@ -17,12 +18,13 @@ use sp_std::{
 
 use crate::{
     pallet::{
-        Banned, Config, CurrentAndNextSessionValidatorsStorage, Event, Pallet,
-        SessionValidatorBlockCount, UnderperformedFinalizerSessionCount,
-        UnderperformedValidatorSessionCount, ValidatorEraTotalReward,
+        Banned, Config, CurrentAndNextSessionValidatorsStorage, EraValidatorPerformance, Event,
+        NonReservedSelectionCursor, Pallet, SessionValidatorBlockCount,
+        UnderperformedFinalizerSessionCount, UnderperformedValidatorSessionCount,
+        ValidatorEraTotalReward, ValidatorMinimalExpectedPerformance,
     },
     traits::{EraInfoProvider, ValidatorRewardsHandler},
-    CurrentAndNextSessionValidators, LenientThreshold, ProductionBanConfigStruct,
+    CurrentAndNextSessionValidators, LenientThreshold, ProductionBanConfigStruct, SelectionCursor,
     ValidatorExtractor, ValidatorTotalRewards, LOG_TARGET,
 };
 
@@ -43,12 +45,26 @@ impl<T: Config> BannedValidators for Pallet<T> {
 }
 
 fn choose_for_session<T: Clone>(validators: &[T], count: usize, session: usize) -> Option<Vec<T>> {
+    choose_from_cursor(
+        validators,
+        count,
+        session as SessionIndex,
+        &SelectionCursor::default(),
+    )
+}
+
+fn choose_from_cursor<T: Clone>(
+    validators: &[T],
+    count: usize,
+    session: SessionIndex,
+    cursor: &SelectionCursor,
+) -> Option<Vec<T>> {
     if validators.is_empty() || count == 0 {
         return None;
     }
 
     let validators_len = validators.len();
-    let first_index = session.saturating_mul(count) % validators_len;
+    let first_index = cursor.first_index(session, count, validators_len);
     let mut chosen = Vec::new();
 
     for i in 0..count.min(validators_len) {
@@ -95,16 +111,22 @@ fn select_committee_inner<AccountId: Clone + PartialEq>(
     non_reserved_finality_seats: usize,
     reserved: &[AccountId],
     non_reserved: &[AccountId],
+    non_reserved_cursor: &SelectionCursor,
 ) -> Option<SessionCommittee<AccountId>> {
     // The validators for the committee at the session `n` are chosen as follow:
     // 1. `reserved_seats` validators are chosen from the reserved set while `non_reserved_seats` from the non_reserved set.
-    // 2. Given a set of validators the chosen ones are from the range:
-    // `n * seats` to `(n + 1) * seats` where seats is equal to reserved_seats(non_reserved_seats) for reserved(non_reserved) validators.
-    // 3. Finality committee is filled first with reserved_seats and then a subsample of non_reserved_seats equal to non_reserved_finality_seats
+    // 2. Given a set of reserved validators the chosen ones are from the range:
+    // `n * reserved_seats` to `(n + 1) * reserved_seats`.
+    // 3. Non reserved validators are chosen round robin, continuing from the `non_reserved_cursor`.
+    // 4. Finality committee is filled first with reserved_seats and then a subsample of non_reserved_seats equal to non_reserved_finality_seats
 
     let reserved_committee = choose_for_session(reserved, reserved_seats, current_session as usize);
-    let non_reserved_committee =
-        choose_for_session(non_reserved, non_reserved_seats, current_session as usize);
+    let non_reserved_committee = choose_from_cursor(
+        non_reserved,
+        non_reserved_seats,
+        current_session,
+        non_reserved_cursor,
+    );
 
     let mut finalizers = choose_finality_committee(
         &reserved_committee,
@@ -253,7 +275,7 @@ impl<T: Config> Pallet<T> {
 
         let lenient_threshold = LenientThreshold::<T>::get();
 
-        let rewards = Self::reward_for_session_non_committee(
+        let rewards: Vec<_> = Self::reward_for_session_non_committee(
             non_committee,
             nr_of_sessions,
             blocks_per_session,
@@ -267,11 +289,40 @@ impl<T: Config> Pallet<T> {
             blocks_per_session,
             &validator_total_rewards,
             lenient_threshold,
-        ));
+        ))
+        .collect();
+
+        for (validator, points) in rewards.iter() {
+            Self::mutate_era_performance(validator, |performance| {
+                performance.reward_points = performance.reward_points.saturating_add(*points);
+            });
+        }
 
         T::ValidatorRewardsHandler::add_rewards(rewards);
     }
 
+    fn mutate_era_performance(
+        validator: &T::AccountId,
+        f: impl FnOnce(&mut ValidatorEraPerformance),
+    ) {
+        let active_era = T::EraInfoProvider::active_era().unwrap_or(0);
+        EraValidatorPerformance::<T>::mutate(active_era, validator, f);
+    }
+
+    /// Removes performance reports that are older than [`Config::PerformanceReportHistoryDepth`]
+    /// eras, as seen from `active_era`.
+    pub(crate) fn prune_era_performance(active_era: EraIndex) {
+        let depth = T::PerformanceReportHistoryDepth::get();
+        if let Some(era) = active_era.checked_sub(depth) {
+            let _result = EraValidatorPerformance::<T>::clear_prefix(era, u32::MAX, None);
+        }
+    }
+
+    /// Performance report of all validators active in `era`, if it is still kept.
+    pub fn era_performance_report(era: EraIndex) -> Vec<(T::AccountId, ValidatorEraPerformance)> {
+        EraValidatorPerformance::<T>::iter_prefix(era).collect()
+    }
+
     fn store_session_validators(
         producers: &[T::AccountId],
         finalizers: &[T::AccountId],
@@ -302,6 +353,7 @@ impl<T: Config> Pallet<T> {
         era_validators: &EraValidators<T::AccountId>,
         committee_seats: CommitteeSeats,
         current_session: SessionIndex,
+        non_reserved_cursor: &SelectionCursor,
     ) -> Option<SessionCommittee<T::AccountId>> {
         let EraValidators {
             reserved,
@@ -321,9 +373,30 @@ impl<T: Config> Pallet<T> {
             non_reserved_finality_seats as usize,
             reserved,
             non_reserved,
+            non_reserved_cursor,
         )
     }
 
+    /// Moves the cursor past the non reserved validators chosen in `session`.
+    fn advance_non_reserved_cursor(
+        cursor: &SelectionCursor,
+        session: SessionIndex,
+        non_reserved_seats: u32,
+        non_reserved_len: usize,
+    ) {
+        let position = match non_reserved_len {
+            0 => 0,
+            len => {
+                let first_index = cursor.first_index(session, non_reserved_seats as usize, len);
+                ((first_index + non_reserved_seats as usize % len) % len) as u32
+            }
+        };
+        NonReservedSelectionCursor::<T>::put(SelectionCursor {
+            session: session + 1,
+            position,
+        });
+    }
+
     pub(crate) fn rotate_committee(
         current_session: SessionIndex,
     ) -> Option<SessionCommittee<T::AccountId>>
@@ -333,9 +406,18 @@ impl<T: Config> Pallet<T> {
         let era_validators = T::ValidatorProvider::current_era_validators();
         let committee_seats = T::ValidatorProvider::current_era_committee_size();
 
-        let committee = Self::select_committee(&era_validators, committee_seats, current_session);
+        let cursor = NonReservedSelectionCursor::<T>::get();
+
+        let committee =
+            Self::select_committee(&era_validators, committee_seats, current_session, &cursor);
 
         if let Some(c) = &committee {
+            Self::advance_non_reserved_cursor(
+                &cursor,
+                current_session,
+                committee_seats.non_reserved_seats,
+                era_validators.non_reserved.len(),
+            );
             Self::store_session_validators(
                 &c.producers,
                 &c.finalizers,
@@ -389,19 +471,86 @@ impl<T: Config> Pallet<T> {
         } = CurrentAndNextSessionValidatorsStorage::<T>::get();
         let expected_blocks_per_validator = Self::blocks_to_produce_per_session();
         for validator in producers {
-            let underperformance = match SessionValidatorBlockCount::<T>::try_get(&validator) {
-                Ok(block_count) => {
-                    Perbill::from_rational(block_count, expected_blocks_per_validator)
-                        <= thresholds.minimal_expected_performance
+            let block_count = SessionValidatorBlockCount::<T>::try_get(&validator).ok();
+            let underperformance = Self::is_underperforming_producer(
+                &validator,
+                block_count,
+                &thresholds,
+                expected_blocks_per_validator,
+            );
+            Self::mutate_era_performance(&validator, |performance| {
+                performance.blocks_produced = performance
+                    .blocks_produced
+                    .saturating_add(block_count.unwrap_or_default());
+                performance.blocks_expected = performance
+                    .blocks_expected
+                    .saturating_add(expected_blocks_per_validator);
+                if underperformance {
+                    performance.underperformed_sessions += 1;
                 }
-                Err(_) => true,
-            };
+            });
             if underperformance {
                 Self::mark_validator_underperformance(&thresholds, &validator);
             }
         }
     }
 
+    fn is_underperforming_producer(
+        validator: &T::AccountId,
+        block_count: Option<BlockCount>,
+        thresholds: &ProductionBanConfigStruct,
+        expected_blocks_per_validator: BlockCount,
+    ) -> bool {
+        let minimal_expected_performance = ValidatorMinimalExpectedPerformance::<T>::get(validator)
+            .unwrap_or(thresholds.minimal_expected_performance);
+        match block_count {
+            Some(block_count) => {
+                Perbill::from_rational(block_count, expected_blocks_per_validator)
+                    <= minimal_expected_performance
+            }
+            None => true,
+        }
+    }
+
+    /// Validators that are going to be removed from the committee, together with the reasons.
+    /// These are the validators already banned from the next era, and the block producers of the
+    /// current session that would be banned if the session ended now, judging by the blocks they
+    /// have produced so far. Does not modify the storage.
+    pub fn projected_bans() -> Vec<(T::AccountId, BanReason)> {
+        let active_era = T::EraInfoProvider::active_era().unwrap_or(0);
+        let mut bans: Vec<_> = Banned::<T>::iter()
+            .filter(|(_, info)| info.start > active_era)
+            .map(|(validator, info)| (validator, info.reason))
+            .collect();
+
+        let thresholds = Self::production_ban_config();
+        let CurrentAndNextSessionValidators {
+            current: SessionValidators { producers, .. },
+            ..
+        } = CurrentAndNextSessionValidatorsStorage::<T>::get();
+        let expected_blocks_per_validator = Self::blocks_to_produce_per_session();
+        for validator in producers {
+            if Banned::<T>::contains_key(&validator) || !T::BanHandler::can_ban(&validator) {
+                continue;
+            }
+            let block_count = SessionValidatorBlockCount::<T>::try_get(&validator).ok();
+            if !Self::is_underperforming_producer(
+                &validator,
+                block_count,
+                &thresholds,
+                expected_blocks_per_validator,
+            ) {
+                continue;
+            }
+            let counter = UnderperformedValidatorSessionCount::<T>::get(&validator) + 1;
+            if counter >= thresholds.underperformed_session_count_threshold {
+                bans.push((validator, BanReason::InsufficientUptime(counter)));
+            }
+        }
+
+        bans
+    }
+
     pub(crate) fn mark_validator_underperformance(
         thresholds: &ProductionBanConfigStruct,
         validator: &T::AccountId,
@@ -410,6 +559,10 @@ impl<T: Config> Pallet<T> {
             *count += 1;
             *count
         });
+        Self::deposit_event(Event::ValidatorUnderperformedSession(
+            validator.clone(),
+            counter,
+        ));
         if counter >= thresholds.underperformed_session_count_threshold {
             let reason = BanReason::InsufficientUptime(counter);
             Self::ban_validator(validator, reason);
@@ -432,13 +585,23 @@ impl<T: Config> Pallet<T> {
 
     pub fn clear_expired_bans(active_era: EraIndex) {
         let ban_period = Self::production_ban_config().ban_period;
-        let unban = Banned::<T>::iter().filter_map(|(v, ban_info)| {
-            if ban_expired(ban_info.start, ban_period, active_era) {
-                return Some(v);
-            }
-            None
-        });
-        unban.for_each(Banned::<T>::remove);
+        let unban = Banned::<T>::iter()
+            .filter_map(|(v, ban_info)| {
+                if ban_expired(ban_info.start, ban_period, active_era) {
+                    return Some(v);
+                }
+                None
+            })
+            .collect::<Vec<_>>();
+        if unban.is_empty() {
+            return;
+        }
+        unban.iter().for_each(Banned::<T>::remove);
+        info!(
+            target: LOG_TARGET,
+            "Bans expired in era {}: {:?}", active_era, unban
+        );
+        Self::deposit_event(Event::UnbanValidators(unban));
     }
 
     pub fn ban_validator(validator: &T::AccountId, reason: BanReason) {
@@ -495,7 +658,8 @@ impl<T: Config> Pallet<T> {
 
         let era_validators = T::ValidatorProvider::current_era_validators();
         let committee_seats = T::ValidatorProvider::current_era_committee_size();
-        Self::select_committee(&era_validators, committee_seats, session)
+        let cursor = NonReservedSelectionCursor::<T>::get();
+        Self::select_committee(&era_validators, committee_seats, session, &cursor)
             .ok_or_else(|| SessionValidatorError::Other("Internal error".encode()))
     }
 }
@@ -506,9 +670,12 @@ mod tests {
 
     use sp_runtime::Perquintill;
 
-    use crate::impls::{
-        calculate_adjusted_session_points, compute_validator_scaled_total_rewards,
-        select_committee_inner, MAX_REWARD,
+    use crate::{
+        impls::{
+            calculate_adjusted_session_points, compute_validator_scaled_total_rewards,
+            select_committee_inner, MAX_REWARD,
+        },
+        SelectionCursor,
     };
 
     const THRESHOLD: Perquintill = Perquintill::from_percent(90);
@@ -645,6 +812,7 @@ mod tests {
                     non_reserved_seats + non_reserved_seats,
                     &reserved,
                     &non_reserved,
+                    &SelectionCursor::default(),
                 )
                 .expect("Expected non-empty rotated committee!")
                 .producers,
@@ -653,4 +821,21 @@ mod tests {
             assert_eq!(expected_committee, committee,);
         }
     }
+
+    #[test]
+    fn selection_cursor_continues_round_robin_from_its_position() {
+        let cursor = SelectionCursor {
+            session: 10,
+            position: 3,
+        };
+
+        assert_eq!(cursor.first_index(10, 4, 7), 3);
+        assert_eq!(cursor.first_index(11, 4, 7), 0);
+        assert_eq!(cursor.first_index(12, 4, 7), 4);
+        assert_eq!(cursor.first_index(9, 4, 7), 6);
+        // Position is reduced modulo the number of validators, which may have changed.
+        assert_eq!(cursor.first_index(10, 4, 2), 1);
+        // Default cursor chooses as if starting from `session * seats`.
+        assert_eq!(SelectionCursor::default().first_index(5, 3, 7), 15 % 7);
+    }
 }
@@ -21,6 +21,7 @@ use primitives::{
 };
 use scale_info::TypeInfo;
 use sp_runtime::Perquintill;
+use sp_staking::SessionIndex;
 use sp_std::{collections::btree_map::BTreeMap, default::Default};
 pub use traits::*;
 
@@ -43,6 +44,37 @@ impl<T> Default for CurrentAndNextSessionValidators<T> {
     }
 }
 
+/// Round robin position of the selection of non reserved validators to the committee.
+///
+/// In the session `session` the non reserved part of the committee is chosen starting from the
+/// validator at index `position` of the current era's non reserved validators, and every following
+/// session starts where the previous one stopped. The default value corresponds to choosing
+/// starting from index `session * non_reserved_seats`.
+#[derive(Decode, Encode, TypeInfo, Debug, Default, Clone, PartialEq, Eq)]
+pub struct SelectionCursor {
+    /// First session that has not been planned yet.
+    pub session: SessionIndex,
+    /// Index of the first non reserved validator chosen in `session`.
+    pub position: u32,
+}
+
+impl SelectionCursor {
+    /// Index of the first of `len` validators chosen in `session` when `seats` validators are
+    /// chosen per session. `len` must be positive.
+    pub(crate) fn first_index(&self, session: SessionIndex, seats: usize, len: usize) -> usize {
+        let (len, seats) = (len as u64, seats as u64);
+        let position = self.position as u64 % len;
+        let shift = (self.session.abs_diff(session) as u64).saturating_mul(seats) % len;
+        let index = if session >= self.session {
+            position + shift
+        } else {
+            position + len - shift
+        };
+
+        (index % len) as usize
+    }
+}
+
 pub struct DefaultLenientThreshold;
 
 impl Get<Perquintill> for DefaultLenientThreshold {
@@ -63,7 +95,7 @@ pub mod pallet {
     use frame_system::{ensure_root, pallet_prelude::OriginFor};
     use primitives::{
         AbftScoresProvider, BanHandler, BanReason, BlockCount, FinalityCommitteeManager,
-        SessionCount, SessionValidators, ValidatorProvider,
+        SessionCount, SessionValidators, ValidatorEraPerformance, ValidatorProvider,
     };
     use sp_runtime::{Perbill, Perquintill};
     use sp_staking::EraIndex;
@@ -72,7 +104,8 @@ pub mod pallet {
     use crate::{
         traits::{EraInfoProvider, ValidatorRewardsHandler},
         BanInfo, CurrentAndNextSessionValidators, DefaultLenientThreshold, FinalityBanConfigStruct,
-        ProductionBanConfigStruct, ValidatorExtractor, ValidatorTotalRewards, STORAGE_VERSION,
+        ProductionBanConfigStruct, SelectionCursor, ValidatorExtractor, ValidatorTotalRewards,
+        STORAGE_VERSION,
     };
 
     #[pallet::config]
@@ -93,6 +126,13 @@ pub mod pallet {
         /// Nr of blocks in the session.
         #[pallet::constant]
         type SessionPeriod: Get<u32>;
+        /// Origin allowed to override block production performance thresholds of validators.
+        type PerformanceThresholdOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Origin allowed to cancel bans that have not taken effect yet.
+        type BanAppealOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Nr of eras for which validators' performance reports are kept.
+        #[pallet::constant]
+        type PerformanceReportHistoryDepth: Get<EraIndex>;
     }
 
     #[pallet::pallet]
@@ -140,11 +180,33 @@ pub mod pallet {
     pub type UnderperformedFinalizerSessionCount<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, SessionCount, ValueQuery>;
 
+    /// Per validator overrides of [`ProductionBanConfigStruct::minimal_expected_performance`].
+    #[pallet::storage]
+    pub type ValidatorMinimalExpectedPerformance<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, Perbill>;
+
     /// Current era config for ban functionality related to block finality.
     #[pallet::storage]
     #[pallet::getter(fn finality_ban_config)]
     pub type FinalityBanConfig<T> = StorageValue<_, FinalityBanConfigStruct, ValueQuery>;
 
+    /// Where the round robin selection of non reserved validators continues from.
+    #[pallet::storage]
+    pub type NonReservedSelectionCursor<T> = StorageValue<_, SelectionCursor, ValueQuery>;
+
+    /// Summary of validators' block production in the last
+    /// [`Config::PerformanceReportHistoryDepth`] eras, accumulated session by session.
+    #[pallet::storage]
+    pub type EraValidatorPerformance<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::AccountId,
+        ValidatorEraPerformance,
+        ValueQuery,
+    >;
+
     #[pallet::error]
     pub enum Error<T> {
         /// Raised in any scenario [`ProductionBanConfig`] is invalid
@@ -159,6 +221,12 @@ pub mod pallet {
 
         /// Lenient threshold not in [0-100] range
         InvalidLenientThreshold,
+
+        /// Validator is not banned, or its ban has already taken effect
+        NoPendingBan,
+
+        /// Minimal expected performance has to be at most 100 percent
+        InvalidPerformanceThreshold,
     }
 
     #[pallet::event]
@@ -173,8 +241,25 @@ pub mod pallet {
         /// Validators have been banned from the committee
         BanValidators(Vec<(T::AccountId, BanInfo)>),
 
+        /// Validators' bans have expired or have been cancelled
+        UnbanValidators(Vec<T::AccountId>),
+
         /// Validator is underperforimg in finality committee
         ValidatorUnderperforming(T::AccountId),
+
+        /// Block production performance threshold of a validator has been overridden, or the
+        /// override has been removed if `None`
+        SetValidatorMinimalExpectedPerformance(T::AccountId, Option<Perbill>),
+
+        /// Validator underperformed in block production in a session, with the number of such
+        /// sessions counted so far
+        ValidatorUnderperformedSession(T::AccountId, SessionCount),
+
+        /// Underperformance counters of a validator have been cleared
+        UnderperformanceCountersCleared(T::AccountId),
+
+        /// Ban of a validator has been cancelled on appeal before taking effect
+        BanAppealed(T::AccountId, BanInfo),
     }
 
     #[pallet::call]
@@ -254,7 +339,9 @@ pub mod pallet {
         #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
         pub fn cancel_ban(origin: OriginFor<T>, banned: T::AccountId) -> DispatchResult {
             ensure_root(origin)?;
-            Banned::<T>::remove(banned);
+            if Banned::<T>::take(&banned).is_some() {
+                Self::deposit_event(Event::UnbanValidators(vec![banned]));
+            }
 
             Ok(())
         }
@@ -320,6 +407,70 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Override the minimal expected block production performance of a single validator, or
+        /// remove the override with `None`. It has an immediate effect.
+        #[pallet::call_index(6)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn set_validator_minimal_expected_performance(
+            origin: OriginFor<T>,
+            validator: T::AccountId,
+            minimal_expected_performance: Option<u8>,
+        ) -> DispatchResult {
+            T::PerformanceThresholdOrigin::ensure_origin(origin)?;
+
+            let minimal_expected_performance = match minimal_expected_performance {
+                Some(performance) => {
+                    ensure!(performance <= 100, Error::<T>::InvalidPerformanceThreshold);
+                    Some(Perbill::from_percent(performance as u32))
+                }
+                None => None,
+            };
+            ValidatorMinimalExpectedPerformance::<T>::set(&validator, minimal_expected_performance);
+            Self::deposit_event(Event::SetValidatorMinimalExpectedPerformance(
+                validator,
+                minimal_expected_performance,
+            ));
+
+            Ok(())
+        }
+
+        /// Clear block production and finality underperformance counters of a validator, e.g.
+        /// after its hardware has been fixed
+        #[pallet::call_index(7)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn clear_underperformance_counters(
+            origin: OriginFor<T>,
+            validator: T::AccountId,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            UnderperformedValidatorSessionCount::<T>::remove(&validator);
+            UnderperformedFinalizerSessionCount::<T>::remove(&validator);
+            Self::deposit_event(Event::UnderperformanceCountersCleared(validator));
+
+            Ok(())
+        }
+
+        /// Cancel a ban that has not taken effect yet, e.g. when it was caused by a false
+        /// positive underperformance, and reset the validator's underperformance counter. Note
+        /// that the validator has been chilled when banned, so it has to declare the intention to
+        /// validate again.
+        #[pallet::call_index(8)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn appeal_ban(origin: OriginFor<T>, banned: T::AccountId) -> DispatchResult {
+            T::BanAppealOrigin::ensure_origin(origin)?;
+            let active_era = T::EraInfoProvider::active_era().unwrap_or(0);
+            let ban_info = Banned::<T>::get(&banned)
+                .filter(|info| info.start > active_era)
+                .ok_or(Error::<T>::NoPendingBan)?;
+
+            Banned::<T>::remove(&banned);
+            UnderperformedValidatorSessionCount::<T>::remove(&banned);
+            Self::deposit_event(Event::UnbanValidators(vec![banned.clone()]));
+            Self::deposit_event(Event::BanAppealed(banned, ban_info));
+
+            Ok(())
+        }
     }
 
     #[pallet::genesis_config]
@@ -16,9 +16,11 @@ use crate::{
 /// 1. Block `B` initialized
 /// 2. `end_session(S)` is called
 /// *  Based on block count we might mark the session for a given validator as underperformed
-/// *  We update rewards and clear block count for the session `S`.
+/// *  We update rewards, record validators' performance in the era `E` and clear block count for
+///    the session `S`.
 /// 3. `start_session(S + 1)` is called.
-/// *  if session `S+1` starts new era we populate totals and unban all validators whose ban expired.
+/// *  if session `S+1` starts new era we populate totals, unban all validators whose ban expired
+///    and prune performance reports older than `PerformanceReportHistoryDepth` eras.
 /// *  if session `S+1` % `clean_session_counter_delay` == 0, we clean up underperformed session counter.
 /// * `clean_session_counter_delay` is read from pallet's storage
 /// 4. `new_session(S + 2)` is called.
@@ -154,6 +156,7 @@ where
         if let Some(era) = Self::session_starts_era(start_index) {
             Pallet::<C>::update_validator_total_rewards(era);
             Pallet::<C>::clear_expired_bans(era);
+            Pallet::<C>::prune_era_performance(era);
         }
     }
 }
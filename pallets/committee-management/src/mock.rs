@@ -9,10 +9,10 @@ use frame_system::pallet_prelude::BlockNumberFor;
 use pallet_staking::{ExposureOf, Forcing};
 use primitives::{
     AuthorityId, CommitteeSeats, SessionIndex, SessionInfoProvider,
-    TotalIssuanceProvider as TotalIssuanceProviderT, DEFAULT_MAX_WINNERS, DEFAULT_SESSIONS_PER_ERA,
-    DEFAULT_SESSION_PERIOD,
+    TotalIssuanceProvider as TotalIssuanceProviderT, DEFAULT_MAX_VALIDATORS, DEFAULT_MAX_WINNERS,
+    DEFAULT_SESSIONS_PER_ERA, DEFAULT_SESSION_PERIOD,
 };
-use sp_core::{ConstU64, H256};
+use sp_core::{ConstU128, ConstU64, H256};
 use sp_runtime::{
     impl_opaque_keys,
     testing::{TestXt, UintAuthorityId},
@@ -169,6 +169,9 @@ impl SessionInfoProvider<BlockNumberFor<TestRuntime>> for SessionInfoImpl {
         )
         .0
     }
+    fn session_of_block(block: BlockNumberFor<TestRuntime>) -> SessionIndex {
+        (block / u64::from(SessionPeriod::get())) as SessionIndex
+    }
 }
 
 parameter_types! {
@@ -200,6 +203,10 @@ impl pallet_aleph::Config for TestRuntime {
     >;
     type NextSessionAuthorityProvider = Session;
     type TotalIssuanceProvider = TotalIssuanceProvider;
+    type EquivocationHandler = ();
+    type SessionAuthoritiesHistoryDepth = ConstU32<3>;
+    type MaxUnitCreationDelay = ConstU64<1000>;
+    type MaxEmergencyFinalizers = ConstU32<3>;
 }
 
 impl<C> frame_system::offchain::SendTransactionTypes<C> for TestRuntime
@@ -228,12 +235,30 @@ parameter_types! {
     pub static MaxWinners: u32 = DEFAULT_MAX_WINNERS;
 }
 
+pub struct NoCandidates;
+
+impl pallet_elections::CandidateInfoProvider for NoCandidates {
+    type AccountId = AccountId;
+
+    fn bonded(_who: &AccountId) -> Balance {
+        0
+    }
+
+    fn has_session_keys(_who: &AccountId) -> bool {
+        false
+    }
+}
+
 impl pallet_elections::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
     type DataProvider = Staking;
     type ValidatorProvider = Staking;
     type MaxWinners = MaxWinners;
     type BannedValidators = CommitteeManagement;
+    type CandidateInfoProvider = NoCandidates;
+    type MinimalCandidateBond = ConstU128<0>;
+    type MaxValidators = ConstU32<DEFAULT_MAX_VALIDATORS>;
+    type MaxCandidates = ConstU32<DEFAULT_MAX_VALIDATORS>;
 }
 
 impl Config for TestRuntime {
@@ -246,6 +271,9 @@ impl Config for TestRuntime {
     type FinalityCommitteeManager = Aleph;
     type SessionPeriod = SessionPeriod;
     type AbftScoresProvider = Aleph;
+    type PerformanceThresholdOrigin = frame_system::EnsureRoot<AccountId>;
+    type BanAppealOrigin = frame_system::EnsureRoot<AccountId>;
+    type PerformanceReportHistoryDepth = ConstU32<2>;
 }
 
 pub fn active_era() -> EraIndex {
@@ -1,15 +1,20 @@
 use std::collections::BTreeSet;
 
+use frame_support::{assert_noop, assert_ok};
 use pallet_aleph::AbftScores;
 use primitives::{BanInfo, BannedValidators, Score};
+use sp_runtime::{DispatchError, Perbill};
 
 use crate::{
     mock::{
         active_era, advance_era, committee_management_events, start_session, AccountId,
-        CommitteeManagement, Elections, SessionPeriod, TestBuilderConfig, TestExtBuilder,
-        TestRuntime,
+        CommitteeManagement, Elections, RuntimeOrigin, SessionPeriod, TestBuilderConfig,
+        TestExtBuilder, TestRuntime,
     },
-    CurrentAndNextSessionValidatorsStorage, Event, ProductionBanConfig, SessionValidatorBlockCount,
+    Banned, CurrentAndNextSessionValidatorsStorage, EraValidatorPerformance, Error, Event,
+    NonReservedSelectionCursor, ProductionBanConfig, SessionValidatorBlockCount,
+    UnderperformedFinalizerSessionCount, UnderperformedValidatorSessionCount,
+    ValidatorMinimalExpectedPerformance,
 };
 
 fn gen_config() -> TestBuilderConfig {
@@ -150,6 +155,35 @@ fn ban_underperforming_producers() {
         assert_eq!(CommitteeManagement::banned(), banned);
         advance_era();
         assert_eq!(CommitteeManagement::banned(), Vec::<AccountId>::new());
+        assert!(committee_management_events().contains(&Event::UnbanValidators(banned)));
+    })
+}
+
+#[test]
+fn cancelling_ban_emits_event() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let banned = 10;
+        assert_ok!(CommitteeManagement::ban_from_committee(
+            RuntimeOrigin::root(),
+            banned,
+            b"reason".to_vec()
+        ));
+        assert_ok!(CommitteeManagement::cancel_ban(
+            RuntimeOrigin::root(),
+            banned
+        ));
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::UnbanValidators(vec![banned])
+        );
+
+        // Cancelling a ban that does not exist is a no-op.
+        let events_count = committee_management_events().len();
+        assert_ok!(CommitteeManagement::cancel_ban(
+            RuntimeOrigin::root(),
+            banned
+        ));
+        assert_eq!(committee_management_events().len(), events_count);
     })
 }
 
@@ -201,3 +235,256 @@ fn ban_underperforming_finalizers() {
         );
     })
 }
+
+#[test]
+fn validator_minimal_expected_performance_overrides_global_threshold() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let (overridden, regular) = (10, 11);
+        CurrentAndNextSessionValidatorsStorage::<TestRuntime>::mutate(|sv| {
+            sv.current.producers = vec![overridden, regular];
+        });
+        // Both produced a bit less than half of the expected blocks.
+        let produced = CommitteeManagement::blocks_to_produce_per_session() * 2 / 5;
+        SessionValidatorBlockCount::<TestRuntime>::insert(overridden, produced);
+        SessionValidatorBlockCount::<TestRuntime>::insert(regular, produced);
+
+        assert_noop!(
+            CommitteeManagement::set_validator_minimal_expected_performance(
+                RuntimeOrigin::signed(overridden),
+                overridden,
+                Some(50)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            CommitteeManagement::set_validator_minimal_expected_performance(
+                RuntimeOrigin::root(),
+                overridden,
+                Some(101)
+            ),
+            Error::<TestRuntime>::InvalidPerformanceThreshold
+        );
+        assert_ok!(
+            CommitteeManagement::set_validator_minimal_expected_performance(
+                RuntimeOrigin::root(),
+                overridden,
+                Some(50)
+            )
+        );
+        assert_eq!(
+            ValidatorMinimalExpectedPerformance::<TestRuntime>::get(overridden),
+            Some(Perbill::from_percent(50))
+        );
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::SetValidatorMinimalExpectedPerformance(
+                overridden,
+                Some(Perbill::from_percent(50))
+            )
+        );
+
+        CommitteeManagement::calculate_underperforming_validators();
+        assert_eq!(
+            UnderperformedValidatorSessionCount::<TestRuntime>::get(overridden),
+            1
+        );
+        assert_eq!(
+            UnderperformedValidatorSessionCount::<TestRuntime>::get(regular),
+            0
+        );
+
+        assert_ok!(
+            CommitteeManagement::set_validator_minimal_expected_performance(
+                RuntimeOrigin::root(),
+                overridden,
+                None
+            )
+        );
+        CommitteeManagement::calculate_underperforming_validators();
+        assert_eq!(
+            UnderperformedValidatorSessionCount::<TestRuntime>::get(overridden),
+            1
+        );
+    })
+}
+
+#[test]
+fn underperformance_is_announced_and_counters_can_be_cleared() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let underperformer = 10;
+        CurrentAndNextSessionValidatorsStorage::<TestRuntime>::mutate(|sv| {
+            sv.current.producers = vec![underperformer];
+        });
+        SessionValidatorBlockCount::<TestRuntime>::insert(underperformer, 0);
+
+        CommitteeManagement::calculate_underperforming_validators();
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::ValidatorUnderperformedSession(underperformer, 1)
+        );
+        UnderperformedFinalizerSessionCount::<TestRuntime>::insert(underperformer, 1);
+
+        assert_noop!(
+            CommitteeManagement::clear_underperformance_counters(
+                RuntimeOrigin::signed(underperformer),
+                underperformer
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(CommitteeManagement::clear_underperformance_counters(
+            RuntimeOrigin::root(),
+            underperformer
+        ));
+        assert_eq!(
+            UnderperformedValidatorSessionCount::<TestRuntime>::get(underperformer),
+            0
+        );
+        assert_eq!(
+            UnderperformedFinalizerSessionCount::<TestRuntime>::get(underperformer),
+            0
+        );
+        assert_eq!(
+            *committee_management_events().last().unwrap(),
+            Event::UnderperformanceCountersCleared(underperformer)
+        );
+    })
+}
+
+#[test]
+fn era_performance_is_recorded_and_pruned() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let underperformer = 10;
+        let era = active_era();
+        CurrentAndNextSessionValidatorsStorage::<TestRuntime>::mutate(|sv| {
+            sv.current.producers = vec![underperformer];
+        });
+        SessionValidatorBlockCount::<TestRuntime>::insert(underperformer, 0);
+
+        CommitteeManagement::calculate_underperforming_validators();
+        let performance = EraValidatorPerformance::<TestRuntime>::get(era, underperformer);
+        assert_eq!(performance.blocks_produced, 0);
+        assert_eq!(
+            performance.blocks_expected,
+            CommitteeManagement::blocks_to_produce_per_session()
+        );
+        assert_eq!(performance.underperformed_sessions, 1);
+        assert!(CommitteeManagement::era_performance_report(era)
+            .iter()
+            .any(|(validator, _)| *validator == underperformer));
+
+        advance_era();
+        advance_era();
+        assert!(CommitteeManagement::era_performance_report(era).is_empty());
+    })
+}
+
+#[test]
+fn non_reserved_selection_cursor_follows_planned_sessions() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        start_session(2);
+        let cursor = NonReservedSelectionCursor::<TestRuntime>::get();
+        // Session `n + 1` is planned when session `n` starts.
+        assert_eq!(cursor.session, 4);
+
+        let non_reserved_len = Elections::current_era_validators().non_reserved.len();
+        let seats = gen_config().non_reserved_seats as usize;
+        start_session(3);
+        let next_cursor = NonReservedSelectionCursor::<TestRuntime>::get();
+        assert_eq!(next_cursor.session, 5);
+        assert_eq!(
+            next_cursor.position as usize,
+            (cursor.position as usize + seats) % non_reserved_len
+        );
+    })
+}
+
+#[test]
+fn projected_bans_do_not_modify_storage() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let underperformer = 10;
+        let threshold =
+            CommitteeManagement::production_ban_config().underperformed_session_count_threshold;
+        CurrentAndNextSessionValidatorsStorage::<TestRuntime>::mutate(|sv| {
+            sv.current.producers = vec![underperformer];
+        });
+        SessionValidatorBlockCount::<TestRuntime>::insert(underperformer, 0);
+        UnderperformedValidatorSessionCount::<TestRuntime>::insert(underperformer, threshold - 2);
+        assert!(CommitteeManagement::projected_bans().is_empty());
+
+        UnderperformedValidatorSessionCount::<TestRuntime>::insert(underperformer, threshold - 1);
+        assert_eq!(
+            CommitteeManagement::projected_bans(),
+            vec![(
+                underperformer,
+                primitives::BanReason::InsufficientUptime(threshold)
+            )]
+        );
+        assert_eq!(
+            UnderperformedValidatorSessionCount::<TestRuntime>::get(underperformer),
+            threshold - 1
+        );
+        assert_eq!(CommitteeManagement::banned(), Vec::<AccountId>::new());
+
+        CommitteeManagement::calculate_underperforming_validators();
+        assert_eq!(CommitteeManagement::banned(), vec![underperformer]);
+        assert_eq!(
+            CommitteeManagement::projected_bans(),
+            vec![(
+                underperformer,
+                primitives::BanReason::InsufficientUptime(threshold)
+            )]
+        );
+    })
+}
+
+#[test]
+fn pending_ban_can_be_appealed() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let banned = 10;
+        assert_ok!(CommitteeManagement::ban_from_committee(
+            RuntimeOrigin::root(),
+            banned,
+            b"reason".to_vec()
+        ));
+        let ban_info = Banned::<TestRuntime>::get(banned).unwrap();
+        UnderperformedValidatorSessionCount::<TestRuntime>::insert(banned, 3);
+
+        assert_noop!(
+            CommitteeManagement::appeal_ban(RuntimeOrigin::signed(banned), banned),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(CommitteeManagement::appeal_ban(
+            RuntimeOrigin::root(),
+            banned
+        ));
+        assert!(!Banned::<TestRuntime>::contains_key(banned));
+        assert!(!UnderperformedValidatorSessionCount::<TestRuntime>::contains_key(banned));
+        assert!(committee_management_events().ends_with(&[
+            Event::UnbanValidators(vec![banned]),
+            Event::BanAppealed(banned, ban_info)
+        ]));
+        assert_noop!(
+            CommitteeManagement::appeal_ban(RuntimeOrigin::root(), banned),
+            Error::<TestRuntime>::NoPendingBan
+        );
+    })
+}
+
+#[test]
+fn ban_in_effect_cannot_be_appealed() {
+    TestExtBuilder::new(gen_config()).build().execute_with(|| {
+        let banned = 10;
+        assert_ok!(CommitteeManagement::ban_from_committee(
+            RuntimeOrigin::root(),
+            banned,
+            b"reason".to_vec()
+        ));
+        advance_era();
+
+        assert_noop!(
+            CommitteeManagement::appeal_ban(RuntimeOrigin::root(), banned),
+            Error::<TestRuntime>::NoPendingBan
+        );
+        assert!(Banned::<TestRuntime>::contains_key(banned));
+    })
+}
@@ -0,0 +1,71 @@
+use frame_benchmarking::{account, v2::*};
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::RawOrigin;
+use primitives::CommitteeSeats;
+use sp_std::vec::Vec;
+
+use crate::{
+    Call, CandidateInfoProvider, CandidateQueue, Config, NextEraCommitteeSize,
+    NextEraNonReservedValidators, NextEraReservedValidators, Pallet,
+};
+
+/// Fills the non reserved validators list up to its bound and returns the last of them, which is
+/// the most expensive one to remove.
+fn fill_non_reserved_validators<T: Config>() -> T::AccountId {
+    let validators: Vec<T::AccountId> = (0..T::MaxValidators::get())
+        .map(|i| account("validator", i, 0))
+        .collect();
+    let last = validators
+        .last()
+        .cloned()
+        .expect("MaxValidators is positive");
+    NextEraReservedValidators::<T>::put(BoundedVec::default());
+    NextEraNonReservedValidators::<T>::put(
+        BoundedVec::try_from(validators).expect("there are MaxValidators of them"),
+    );
+    NextEraCommitteeSize::<T>::put(CommitteeSeats {
+        reserved_seats: 0,
+        non_reserved_seats: 1,
+        non_reserved_finality_seats: 1,
+    });
+    last
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn chill() {
+        let validator = fill_non_reserved_validators::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(validator.clone()));
+
+        assert!(!NextEraNonReservedValidators::<T>::get().contains(&validator));
+    }
+
+    #[benchmark]
+    fn enqueue_candidate() {
+        fill_non_reserved_validators::<T>();
+        let queue: Vec<T::AccountId> = (1..T::MaxCandidates::get())
+            .map(|i| account("candidate", i, 0))
+            .collect();
+        CandidateQueue::<T>::put(
+            BoundedVec::try_from(queue).expect("there are fewer than MaxCandidates of them"),
+        );
+        let candidate: T::AccountId = account("candidate", 0, 0);
+        T::CandidateInfoProvider::make_eligible(&candidate);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(candidate.clone()));
+
+        assert!(CandidateQueue::<T>::get().contains(&candidate));
+    }
+
+    impl_benchmark_test_suite!(
+        Pallet,
+        crate::mock::TestExtBuilder::new(Vec::new(), Vec::new()).build(),
+        crate::mock::Test
+    );
+}
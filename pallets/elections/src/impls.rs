@@ -1,3 +1,4 @@
+use frame_support::BoundedVec;
 use primitives::{CommitteeSeats, EraValidators};
 use rand::{seq::SliceRandom, SeedableRng};
 use rand_pcg::Pcg32;
@@ -5,8 +6,9 @@ use sp_staking::EraIndex;
 use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
 
 use crate::{
-    traits::ValidatorProvider, CommitteeSize, Config, CurrentEraValidators, NextEraCommitteeSize,
-    NextEraNonReservedValidators, NextEraReservedValidators, Pallet,
+    traits::ValidatorProvider, BoundedEraValidators, CommitteeSize, Config, CurrentEraValidators,
+    NextEraCommitteeSize, NextEraElectedNonReservedValidators, NextEraNonReservedValidators,
+    NextEraReservedValidators, Pallet,
 };
 
 impl<T> Pallet<T>
@@ -17,21 +19,27 @@ where
         let mut rng = Pcg32::seed_from_u64(era as u64);
         let elected_committee = BTreeSet::from_iter(T::ValidatorProvider::elected_validators(era));
 
-        let mut retain_shuffle_elected = |vals: Vec<T::AccountId>| -> Vec<T::AccountId> {
-            let mut vals: Vec<_> = vals
-                .into_iter()
-                .filter(|v| elected_committee.contains(v))
-                .collect();
-            vals.shuffle(&mut rng);
+        let mut retain_shuffle_elected =
+            |vals: BoundedVec<T::AccountId, T::MaxValidators>| -> BoundedVec<_, _> {
+                let mut vals: Vec<_> = vals
+                    .into_iter()
+                    .filter(|v| elected_committee.contains(v))
+                    .collect();
+                vals.shuffle(&mut rng);
 
-            vals
-        };
+                // Filtering can only shorten the list, so nothing gets truncated here.
+                BoundedVec::truncate_from(vals)
+            };
 
         let reserved_validators = NextEraReservedValidators::<T>::get();
-        let non_reserved_validators = NextEraNonReservedValidators::<T>::get();
+        // Validators removed from the non reserved ones after the elections do not join the
+        // committee, even if they were elected.
+        let approved_non_reserved = BTreeSet::from_iter(NextEraNonReservedValidators::<T>::get());
+        let mut non_reserved_validators = NextEraElectedNonReservedValidators::<T>::get();
+        non_reserved_validators.retain(|v| approved_non_reserved.contains(v));
         let committee_size = NextEraCommitteeSize::<T>::get();
 
-        CurrentEraValidators::<T>::put(EraValidators {
+        CurrentEraValidators::<T>::put(BoundedEraValidators {
             reserved: retain_shuffle_elected(reserved_validators),
             non_reserved: retain_shuffle_elected(non_reserved_validators),
         });
@@ -55,7 +63,7 @@ impl<T: Config> primitives::BanHandler for Pallet<T> {
 impl<T: Config + pallet_staking::Config> primitives::ValidatorProvider for Pallet<T> {
     type AccountId = T::AccountId;
     fn current_era_validators() -> EraValidators<Self::AccountId> {
-        CurrentEraValidators::<T>::get()
+        Pallet::<T>::current_era_validators()
     }
     fn current_era_committee_size() -> CommitteeSeats {
         CommitteeSize::<T>::get()
@@ -1,43 +1,79 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod impls;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 mod traits;
 
-use frame_support::traits::StorageVersion;
+use frame_support::{
+    traits::{Get, StorageVersion},
+    BoundedVec,
+};
 pub use pallet::*;
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 pub use primitives::EraValidators;
 use scale_info::TypeInfo;
 use sp_std::{
+    cmp::Reverse,
     collections::{btree_map::BTreeMap, btree_set::BTreeSet},
     prelude::*,
 };
+pub use traits::CandidateInfoProvider;
 
 pub type TotalReward = u32;
 
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+pub(crate) const LOG_TARGET: &str = "pallet-elections";
 
 #[derive(Decode, Encode, TypeInfo)]
 pub struct ValidatorTotalRewards<T>(pub BTreeMap<T, TotalReward>);
 
+/// Bounded version of [`EraValidators`] kept in storage, both are encoded the same way.
+#[derive(Decode, Encode, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(S))]
+#[codec(mel_bound(AccountId: MaxEncodedLen))]
+pub struct BoundedEraValidators<AccountId, S: Get<u32>> {
+    pub reserved: BoundedVec<AccountId, S>,
+    pub non_reserved: BoundedVec<AccountId, S>,
+}
+
+impl<AccountId, S: Get<u32>> Default for BoundedEraValidators<AccountId, S> {
+    fn default() -> Self {
+        Self {
+            reserved: BoundedVec::new(),
+            non_reserved: BoundedVec::new(),
+        }
+    }
+}
+
+impl<AccountId, S: Get<u32>> From<BoundedEraValidators<AccountId, S>> for EraValidators<AccountId> {
+    fn from(validators: BoundedEraValidators<AccountId, S>) -> Self {
+        Self {
+            reserved: validators.reserved.into_inner(),
+            non_reserved: validators.non_reserved.into_inner(),
+        }
+    }
+}
+
 #[frame_support::pallet]
 #[pallet_doc("../README.md")]
 pub mod pallet {
     use frame_election_provider_support::{
         BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-        ElectionProviderBase, Support, Supports,
+        ElectionProviderBase, Support, Supports, VoterOf,
     };
     use frame_support::{pallet_prelude::*, traits::Get};
     use frame_system::{
-        ensure_root,
+        ensure_root, ensure_signed,
         pallet_prelude::{BlockNumberFor, OriginFor},
     };
-    use primitives::{BannedValidators, CommitteeSeats, ElectionOpenness};
+    use primitives::{Balance, BannedValidators, CommitteeSeats, ElectionMode, ElectionOpenness};
 
     use super::*;
     use crate::traits::ValidatorProvider;
@@ -58,6 +94,18 @@ pub mod pallet {
         #[pallet::constant]
         type MaxWinners: Get<u32>;
         type BannedValidators: BannedValidators<AccountId = Self::AccountId>;
+        /// Something that provides information about accounts enqueuing themselves as candidates.
+        type CandidateInfoProvider: CandidateInfoProvider<AccountId = Self::AccountId>;
+        /// Minimal amount an account has to bond to enqueue itself as a candidate.
+        #[pallet::constant]
+        type MinimalCandidateBond: Get<Balance>;
+        /// Maximal length of each of the reserved and non reserved validators lists.
+        #[pallet::constant]
+        type MaxValidators: Get<u32>;
+        /// Maximal length of the candidate queue. Anyone with a sufficient bond can enqueue
+        /// themselves, so it is kept separate from the bound on the validators lists.
+        #[pallet::constant]
+        type MaxCandidates: Get<u32>;
     }
 
     #[pallet::event]
@@ -65,11 +113,23 @@ pub mod pallet {
     pub enum Event<T: Config> {
         /// Committee for the next era has changed
         ChangeValidators(Vec<T::AccountId>, Vec<T::AccountId>, CommitteeSeats),
+        /// Elections mode has changed, it is used from the next elections onwards
+        ChangeElectionMode(ElectionMode),
+        /// Non reserved validator has removed itself from the next era's validators
+        ValidatorChilled(T::AccountId),
+        /// Account has enqueued itself as a candidate for a non reserved validator
+        CandidateEnqueued(T::AccountId),
+        /// Candidate has been approved and added to the next era's non reserved validators
+        CandidateApproved(T::AccountId),
+        /// Candidate has been denied and removed from the queue
+        CandidateDenied(T::AccountId),
+        /// Number of eligible non reserved candidates left out of the elections, as there were
+        /// more of them than [`Config::MaxValidators`]
+        NonReservedCandidatesDropped(u32),
     }
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// Desirable size of a committee, see [`CommitteeSeats`].
@@ -88,18 +148,24 @@ pub mod pallet {
 
     /// Next era's list of reserved validators.
     #[pallet::storage]
-    pub type NextEraReservedValidators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+    pub type NextEraReservedValidators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
 
     /// Current era's list of reserved validators.
     #[pallet::storage]
-    #[pallet::getter(fn current_era_validators)]
     pub type CurrentEraValidators<T: Config> =
-        StorageValue<_, EraValidators<T::AccountId>, ValueQuery>;
+        StorageValue<_, BoundedEraValidators<T::AccountId, T::MaxValidators>, ValueQuery>;
 
     /// Next era's list of non reserved validators.
     #[pallet::storage]
     pub type NextEraNonReservedValidators<T: Config> =
-        StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+    /// Non reserved validators chosen out of `NextEraNonReservedValidators` in the last elections.
+    /// In the DelegatedProofOfStake ElectionMode these are only the ones with the highest backing.
+    #[pallet::storage]
+    pub type NextEraElectedNonReservedValidators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
 
     /// Default value for elections openness.
     #[pallet::type_value]
@@ -112,6 +178,23 @@ pub mod pallet {
     #[pallet::storage]
     pub type Openness<T> = StorageValue<_, ElectionOpenness, ValueQuery, DefaultOpenness<T>>;
 
+    /// Default value for elections mode.
+    #[pallet::type_value]
+    pub fn DefaultMode<T: Config>() -> ElectionMode {
+        ElectionMode::ProofOfAuthority
+    }
+
+    /// Mode of the elections, whether all eligible non reserved candidates are elected or only
+    /// those with the highest total backing
+    #[pallet::storage]
+    pub type Mode<T> = StorageValue<_, ElectionMode, ValueQuery, DefaultMode<T>>;
+
+    /// Accounts waiting for approval to become non reserved validators, in the order they
+    /// enqueued themselves.
+    #[pallet::storage]
+    pub type CandidateQueue<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxCandidates>, ValueQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
@@ -124,19 +207,21 @@ pub mod pallet {
         ) -> DispatchResult {
             ensure_root(origin)?;
             let committee_size = committee_size.unwrap_or_else(NextEraCommitteeSize::<T>::get);
-            let reserved_validators =
-                reserved_validators.unwrap_or_else(NextEraReservedValidators::<T>::get);
-            let non_reserved_validators =
-                non_reserved_validators.unwrap_or_else(NextEraNonReservedValidators::<T>::get);
+            let reserved_validators = reserved_validators
+                .unwrap_or_else(|| NextEraReservedValidators::<T>::get().into_inner());
+            let non_reserved_validators = non_reserved_validators
+                .unwrap_or_else(|| NextEraNonReservedValidators::<T>::get().into_inner());
 
             Self::ensure_validators_are_ok(
                 reserved_validators.clone(),
                 non_reserved_validators.clone(),
                 committee_size,
             )?;
+            let bounded_reserved_validators = Self::bounded(reserved_validators.clone())?;
+            let bounded_non_reserved_validators = Self::bounded(non_reserved_validators.clone())?;
 
-            NextEraNonReservedValidators::<T>::put(non_reserved_validators.clone());
-            NextEraReservedValidators::<T>::put(reserved_validators.clone());
+            NextEraNonReservedValidators::<T>::put(bounded_non_reserved_validators);
+            NextEraReservedValidators::<T>::put(bounded_reserved_validators);
             NextEraCommitteeSize::<T>::put(committee_size);
 
             Self::deposit_event(Event::ChangeValidators(
@@ -161,13 +246,111 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Set mode of the elections, effective from the next elections
+        #[pallet::call_index(5)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn set_election_mode(origin: OriginFor<T>, mode: ElectionMode) -> DispatchResult {
+            ensure_root(origin)?;
+
+            Mode::<T>::set(mode);
+            Self::deposit_event(Event::ChangeElectionMode(mode));
+
+            Ok(())
+        }
+
+        /// Remove the calling non reserved validator from the validators of the next era. Fails
+        /// if there would not be enough validators left to fill the committee. Not available in
+        /// permissionless elections, where validators leave by chilling in staking instead.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+        pub fn chill(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Openness::<T>::get() == ElectionOpenness::Permissioned,
+                Error::<T>::ChillInPermissionlessElections
+            );
+            let mut non_reserved_validators = NextEraNonReservedValidators::<T>::get();
+            let position = non_reserved_validators
+                .iter()
+                .position(|v| v == &who)
+                .ok_or(Error::<T>::NotNonReservedValidator)?;
+            non_reserved_validators.remove(position);
+
+            Self::ensure_validators_are_ok(
+                NextEraReservedValidators::<T>::get().into_inner(),
+                non_reserved_validators.clone().into_inner(),
+                NextEraCommitteeSize::<T>::get(),
+            )?;
+
+            NextEraNonReservedValidators::<T>::put(non_reserved_validators);
+            Self::deposit_event(Event::ValidatorChilled(who));
+
+            Ok(())
+        }
+
+        /// Enqueue the calling account as a candidate for a non reserved validator. The account
+        /// has to bond at least [`Config::MinimalCandidateBond`] and set its session keys first.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(5, 1))]
+        pub fn enqueue_candidate(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut queue = CandidateQueue::<T>::get();
+            ensure!(!queue.contains(&who), Error::<T>::AlreadyCandidate);
+            Self::ensure_candidate_is_eligible(&who)?;
+
+            queue
+                .try_push(who.clone())
+                .map_err(|_| Error::<T>::TooManyCandidates)?;
+            CandidateQueue::<T>::put(queue);
+            Self::deposit_event(Event::CandidateEnqueued(who));
+
+            Ok(())
+        }
+
+        /// Approve a queued candidate, adding it to the next era's non reserved validators. The
+        /// candidate has to still fulfil the requirements checked when it was enqueued.
+        #[pallet::call_index(8)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn approve_candidate(origin: OriginFor<T>, candidate: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::remove_from_candidate_queue(&candidate)?;
+            Self::ensure_candidate_is_eligible(&candidate)?;
+
+            let mut non_reserved_validators = NextEraNonReservedValidators::<T>::get();
+            non_reserved_validators
+                .try_push(candidate.clone())
+                .map_err(|_| Error::<T>::TooManyValidators)?;
+            Self::ensure_validators_are_ok(
+                NextEraReservedValidators::<T>::get().into_inner(),
+                non_reserved_validators.clone().into_inner(),
+                NextEraCommitteeSize::<T>::get(),
+            )?;
+
+            NextEraNonReservedValidators::<T>::put(non_reserved_validators);
+            Self::deposit_event(Event::CandidateApproved(candidate));
+
+            Ok(())
+        }
+
+        /// Deny a queued candidate, removing it from the queue
+        #[pallet::call_index(9)]
+        #[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+        pub fn deny_candidate(origin: OriginFor<T>, candidate: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::remove_from_candidate_queue(&candidate)?;
+
+            Self::deposit_event(Event::CandidateDenied(candidate));
+
+            Ok(())
+        }
     }
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         #[cfg(feature = "try-runtime")]
         fn try_state(_n: BlockNumberFor<T>) -> Result<(), DispatchError> {
-            let current_validators = CurrentEraValidators::<T>::get();
+            let current_validators = Self::current_era_validators();
             Self::ensure_validators_are_ok(
                 current_validators.reserved,
                 current_validators.non_reserved,
@@ -175,8 +358,8 @@ pub mod pallet {
             )?;
 
             Self::ensure_validators_are_ok(
-                NextEraReservedValidators::<T>::get(),
-                NextEraNonReservedValidators::<T>::get(),
+                NextEraReservedValidators::<T>::get().into_inner(),
+                NextEraNonReservedValidators::<T>::get().into_inner(),
                 NextEraCommitteeSize::<T>::get(),
             )?;
 
@@ -195,18 +378,42 @@ pub mod pallet {
     #[pallet::genesis_build]
     impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
         fn build(&self) {
+            let reserved_validators: BoundedVec<_, T::MaxValidators> = self
+                .reserved_validators
+                .clone()
+                .try_into()
+                .expect("Too many reserved validators");
+            let non_reserved_validators: BoundedVec<_, T::MaxValidators> = self
+                .non_reserved_validators
+                .clone()
+                .try_into()
+                .expect("Too many non reserved validators");
             <CommitteeSize<T>>::put(self.committee_seats);
             <NextEraCommitteeSize<T>>::put(self.committee_seats);
-            <NextEraNonReservedValidators<T>>::put(&self.non_reserved_validators);
-            <NextEraReservedValidators<T>>::put(&self.reserved_validators);
-            <CurrentEraValidators<T>>::put(&EraValidators {
-                reserved: self.reserved_validators.clone(),
-                non_reserved: self.non_reserved_validators.clone(),
+            <NextEraNonReservedValidators<T>>::put(&non_reserved_validators);
+            <NextEraElectedNonReservedValidators<T>>::put(&non_reserved_validators);
+            <NextEraReservedValidators<T>>::put(&reserved_validators);
+            <CurrentEraValidators<T>>::put(&BoundedEraValidators {
+                reserved: reserved_validators,
+                non_reserved: non_reserved_validators,
             });
         }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Current era's reserved and non reserved validators.
+        pub fn current_era_validators() -> EraValidators<T::AccountId> {
+            CurrentEraValidators::<T>::get().into()
+        }
+
+        fn bounded(
+            validators: Vec<T::AccountId>,
+        ) -> Result<BoundedVec<T::AccountId, T::MaxValidators>, Error<T>> {
+            validators
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyValidators)
+        }
+
         fn ensure_validators_are_ok(
             reserved_validators: Vec<T::AccountId>,
             non_reserved_validators: Vec<T::AccountId>,
@@ -254,6 +461,94 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Checks that the account is not a validator yet, has bonded enough and has set its
+        /// session keys.
+        fn ensure_candidate_is_eligible(who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                !NextEraReservedValidators::<T>::get().contains(who)
+                    && !NextEraNonReservedValidators::<T>::get().contains(who),
+                Error::<T>::AlreadyValidator
+            );
+            ensure!(
+                T::CandidateInfoProvider::bonded(who) >= T::MinimalCandidateBond::get(),
+                Error::<T>::InsufficientCandidateBond
+            );
+            ensure!(
+                T::CandidateInfoProvider::has_session_keys(who),
+                Error::<T>::CandidateSessionKeysNotSet
+            );
+
+            Ok(())
+        }
+
+        fn remove_from_candidate_queue(candidate: &T::AccountId) -> DispatchResult {
+            CandidateQueue::<T>::try_mutate(|queue| -> DispatchResult {
+                let position = queue
+                    .iter()
+                    .position(|c| c == candidate)
+                    .ok_or(Error::<T>::NotCandidate)?;
+                queue.remove(position);
+                Ok(())
+            })
+        }
+
+        /// Splits the stake of every voter equally between the targets it nominated that have an
+        /// entry in `supports`. The remainder of the division goes to the first of these targets,
+        /// so the whole stake is attributed.
+        fn distribute_votes(
+            voters: &[VoterOf<T::DataProvider>],
+            supports: &mut BTreeMap<T::AccountId, Support<T::AccountId>>,
+        ) {
+            for (voter, vote, targets) in voters {
+                let backed: Vec<_> = targets
+                    .iter()
+                    .filter(|target| supports.contains_key(*target))
+                    .collect();
+                let backed_count = backed.len() as u128;
+                if backed_count == 0 {
+                    continue;
+                }
+                let vote = *vote as u128;
+                let share = vote / backed_count;
+                let remainder = vote % backed_count;
+                for (index, target) in backed.into_iter().enumerate() {
+                    let stake = if index == 0 { share + remainder } else { share };
+                    if let Some(support) = supports.get_mut(target) {
+                        support.total += stake;
+                        support.voters.push((voter.clone(), stake));
+                    }
+                }
+            }
+        }
+
+        /// Leaves out the candidates beyond the first [`Config::MaxValidators`] ones, so that the
+        /// elected validators fit into the bounded lists.
+        fn drop_excess_candidates(candidates: &mut Vec<T::AccountId>) {
+            let max_validators = T::MaxValidators::get() as usize;
+            if candidates.len() > max_validators {
+                let dropped = candidates.split_off(max_validators).len() as u32;
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Leaving {} eligible non reserved candidates out of the elections, as there are more than {} of them.",
+                    dropped,
+                    max_validators
+                );
+                Self::deposit_event(Event::NonReservedCandidatesDropped(dropped));
+            }
+        }
+
+        /// Number of non reserved validators elected in the DPoS mode: as many as staking desires
+        /// on top of the reserved ones, but never less than the non reserved committee seats.
+        fn dpos_non_reserved_count(reserved_count: u32) -> Result<usize, ElectionError> {
+            let desired_targets =
+                T::DataProvider::desired_targets().map_err(ElectionError::DataProvider)?;
+            let non_reserved_seats = NextEraCommitteeSize::<T>::get().non_reserved_seats;
+
+            Ok(desired_targets
+                .saturating_sub(reserved_count)
+                .max(non_reserved_seats) as usize)
+        }
     }
 
     #[derive(Debug)]
@@ -272,6 +567,15 @@ pub mod pallet {
         NotEnoughNonReservedValidators,
         NonUniqueListOfValidators,
         NonReservedFinalitySeatsLargerThanNonReservedSeats,
+        NotNonReservedValidator,
+        AlreadyValidator,
+        AlreadyCandidate,
+        NotCandidate,
+        InsufficientCandidateBond,
+        CandidateSessionKeysNotSet,
+        TooManyValidators,
+        TooManyCandidates,
+        ChillInPermissionlessElections,
     }
 
     impl<T: Config> ElectionProviderBase for Pallet<T> {
@@ -290,6 +594,10 @@ pub mod pallet {
         /// We calculate the supports for each validator. The external validators are chosen as:
         /// 1) "`NextEraNonReservedValidators` that are staking and are not banned" in case of Permissioned ElectionOpenness
         /// 2) "All staking and not banned validators" in case of Permissionless ElectionOpenness
+        /// In the DelegatedProofOfStake ElectionMode only the ones with the highest total backing
+        /// are kept out of these. At most `MaxValidators` candidates are considered: the ones with
+        /// the highest total backing in the DelegatedProofOfStake ElectionMode, the first ones by
+        /// `AccountId` otherwise.
         fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
             let staking_validators =
                 Self::DataProvider::electable_targets(DataProviderBounds::default())
@@ -312,21 +620,23 @@ pub mod pallet {
                 })
                 .collect::<BTreeSet<_>>();
 
-            let new_non_reserved_validators: Vec<_> = match Openness::<T>::get() {
+            let mut non_reserved_candidates: Vec<_> = match Openness::<T>::get() {
                 ElectionOpenness::Permissioned => old_non_reserved_validators
                     .filter(|v| eligible_non_reserved.contains(v))
                     .collect(),
                 ElectionOpenness::Permissionless => eligible_non_reserved.into_iter().collect(),
             };
-            // We store new list here to ensure that validators that end up in the result of the elect
-            // method are a disjoint union of NextEraReservedValidators and NextEraNonReservedValidators.
-            // This condition is important since results of elect ends up in pallet staking while the above lists
-            // are used in our session manager, so we have to ensure consistency between them.
-            NextEraNonReservedValidators::<T>::put(new_non_reserved_validators.clone());
+            let mode = Mode::<T>::get();
+            // In the DPoS mode the candidates are ranked by their backing before being cut.
+            if mode == ElectionMode::ProofOfAuthority {
+                Self::drop_excess_candidates(&mut non_reserved_candidates);
+            }
+            let reserved_count = staking_reserved_validators.len() as u32;
 
             let eligible_validators = staking_reserved_validators
-                .into_iter()
-                .chain(new_non_reserved_validators);
+                .iter()
+                .cloned()
+                .chain(non_reserved_candidates.iter().cloned());
             let mut supports = eligible_validators
                 .into_iter()
                 .map(|id| {
@@ -344,15 +654,43 @@ pub mod pallet {
 
             let voters = Self::DataProvider::electing_voters(DataProviderBounds::default())
                 .map_err(Self::Error::DataProvider)?;
-            for (voter, vote, targets) in voters {
-                // The parameter `Staking::MAX_NOMINATIONS` is set to 1 which guarantees that
-                // `len(targets) == 1`.
-                let member = &targets[0];
-                if let Some(support) = supports.get_mut(member) {
-                    support.total += vote as u128;
-                    support.voters.push((voter, vote as u128));
+            Self::distribute_votes(&voters, &mut supports);
+
+            let elected_non_reserved_validators = match mode {
+                ElectionMode::ProofOfAuthority => non_reserved_candidates.clone(),
+                ElectionMode::DelegatedProofOfStake => {
+                    let mut winners = non_reserved_candidates.clone();
+                    // Stable sort, so ties are resolved by the order of the candidates.
+                    winners.sort_by_key(|v| Reverse(supports.get(v).map(|s| s.total)));
+                    Self::drop_excess_candidates(&mut winners);
+                    let considered = winners.iter().cloned().collect::<BTreeSet<_>>();
+                    non_reserved_candidates.retain(|v| considered.contains(v));
+                    winners.truncate(Self::dpos_non_reserved_count(reserved_count)?);
+                    let winners_set = winners.iter().cloned().collect::<BTreeSet<_>>();
+                    supports.retain(|v, _| {
+                        staking_reserved_validators.contains(v) || winners_set.contains(v)
+                    });
+                    // Stake that was split towards candidates that lost goes to the elected
+                    // targets of the same voters.
+                    supports
+                        .values_mut()
+                        .for_each(|support| *support = Default::default());
+                    Self::distribute_votes(&voters, &mut supports);
+                    winners
                 }
-            }
+            };
+            // We store new lists here to ensure that validators that end up in the result of the elect
+            // method are a disjoint union of NextEraReservedValidators and NextEraElectedNonReservedValidators.
+            // This condition is important since results of elect ends up in pallet staking while the above lists
+            // are used in our session manager, so we have to ensure consistency between them.
+            // The candidates stay in NextEraNonReservedValidators, whether they were elected or not.
+            // There are at most `MaxValidators` of them, as the candidates were bounded above.
+            NextEraNonReservedValidators::<T>::put(BoundedVec::truncate_from(
+                non_reserved_candidates,
+            ));
+            NextEraElectedNonReservedValidators::<T>::put(BoundedVec::truncate_from(
+                elected_non_reserved_validators,
+            ));
 
             supports
                 .into_iter()
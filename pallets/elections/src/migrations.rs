@@ -0,0 +1,133 @@
+//! Storage migrations of the pallet.
+
+pub mod v6 {
+    #[cfg(feature = "try-runtime")]
+    use frame_support::ensure;
+    use frame_support::{
+        pallet_prelude::{StorageVersion, ValueQuery},
+        storage_alias,
+        traits::{Get, OnRuntimeUpgrade},
+        weights::Weight,
+        BoundedVec,
+    };
+    use primitives::EraValidators;
+    #[cfg(feature = "try-runtime")]
+    use sp_runtime::TryRuntimeError;
+    use sp_std::{marker::PhantomData, vec::Vec};
+
+    use crate::{
+        BoundedEraValidators, CandidateQueue, Config, CurrentEraValidators,
+        NextEraElectedNonReservedValidators, NextEraNonReservedValidators,
+        NextEraReservedValidators, Pallet, LOG_TARGET,
+    };
+
+    type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+    #[storage_alias]
+    type NextEraReservedValidatorsV5<T: Config> =
+        StorageValue<Pallet<T>, Vec<AccountIdOf<T>>, ValueQuery>;
+
+    #[storage_alias]
+    type NextEraNonReservedValidatorsV5<T: Config> =
+        StorageValue<Pallet<T>, Vec<AccountIdOf<T>>, ValueQuery>;
+
+    #[storage_alias]
+    type CurrentEraValidatorsV5<T: Config> =
+        StorageValue<Pallet<T>, EraValidators<AccountIdOf<T>>, ValueQuery>;
+
+    #[storage_alias]
+    type CandidateQueueV5<T: Config> = StorageValue<Pallet<T>, Vec<AccountIdOf<T>>, ValueQuery>;
+
+    /// Bounds the validators lists by `Config::MaxValidators` and the candidate queue by
+    /// `Config::MaxCandidates`. Lists that are too long would not decode anymore and would be read
+    /// as empty, so they are truncated instead, which is reported as an error. The `try-runtime`
+    /// checks fail in that case, so that it is caught before the upgrade. The elected non reserved
+    /// validators start as all the non reserved ones, as elected before the upgrade.
+    pub struct Migration<T>(PhantomData<T>);
+
+    fn bounded<T: Config, S: Get<u32>>(
+        name: &str,
+        accounts: Vec<AccountIdOf<T>>,
+    ) -> BoundedVec<AccountIdOf<T>, S> {
+        if accounts.len() > S::get() as usize {
+            log::error!(
+                target: LOG_TARGET,
+                "{} holds {} accounts, truncating it to {}.",
+                name,
+                accounts.len(),
+                S::get()
+            );
+        }
+        BoundedVec::truncate_from(accounts)
+    }
+
+    impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != StorageVersion::new(5) {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Skipping validators lists bounding migration, storage version is not 5."
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            NextEraReservedValidators::<T>::put(bounded::<T, T::MaxValidators>(
+                "NextEraReservedValidators",
+                NextEraReservedValidatorsV5::<T>::get(),
+            ));
+            let non_reserved = bounded::<T, T::MaxValidators>(
+                "NextEraNonReservedValidators",
+                NextEraNonReservedValidatorsV5::<T>::get(),
+            );
+            NextEraNonReservedValidators::<T>::put(&non_reserved);
+            NextEraElectedNonReservedValidators::<T>::put(non_reserved);
+            let current = CurrentEraValidatorsV5::<T>::get();
+            CurrentEraValidators::<T>::put(BoundedEraValidators {
+                reserved: bounded::<T, T::MaxValidators>(
+                    "CurrentEraValidators.reserved",
+                    current.reserved,
+                ),
+                non_reserved: bounded::<T, T::MaxValidators>(
+                    "CurrentEraValidators.non_reserved",
+                    current.non_reserved,
+                ),
+            });
+            CandidateQueue::<T>::put(bounded::<T, T::MaxCandidates>(
+                "CandidateQueue",
+                CandidateQueueV5::<T>::get(),
+            ));
+            StorageVersion::new(6).put::<Pallet<T>>();
+            log::info!(target: LOG_TARGET, "Bounded validators lists, migrated to storage version 6.");
+
+            T::DbWeight::get().reads_writes(5, 6)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+            pallets_support::ensure_storage_version::<Pallet<T>>(5)?;
+
+            let max_validators = T::MaxValidators::get() as usize;
+            let current = CurrentEraValidatorsV5::<T>::get();
+            ensure!(
+                NextEraReservedValidatorsV5::<T>::get().len() <= max_validators
+                    && NextEraNonReservedValidatorsV5::<T>::get().len() <= max_validators
+                    && current.reserved.len() <= max_validators
+                    && current.non_reserved.len() <= max_validators,
+                "A validators list is longer than MaxValidators"
+            );
+            ensure!(
+                CandidateQueueV5::<T>::get().len() <= T::MaxCandidates::get() as usize,
+                "The candidate queue is longer than MaxCandidates"
+            );
+
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            pallets_support::ensure_storage_version::<Pallet<T>>(6)?;
+
+            Ok(())
+        }
+    }
+}
@@ -108,6 +108,16 @@ thread_local! {
     static CURRENT_ERA: RefCell<EraIndex> = RefCell::new(Default::default());
     static ELECTED_VALIDATORS: RefCell<BTreeMap<EraIndex, Vec<AccountId>>> = RefCell::new(Default::default());
     static BANNNED_VALIDATORS: RefCell<Vec<AccountId>> = RefCell::new(Default::default());
+    static BONDED: RefCell<BTreeMap<AccountId, Balance>> = RefCell::new(Default::default());
+    static WITH_SESSION_KEYS: RefCell<BTreeSet<AccountId>> = RefCell::new(Default::default());
+}
+
+pub fn with_bonded(who: AccountId, amount: Balance) {
+    BONDED.with(|b| b.borrow_mut().insert(who, amount));
+}
+
+pub fn with_session_keys(who: AccountId) {
+    WITH_SESSION_KEYS.with(|k| k.borrow_mut().insert(who));
 }
 
 impl ValidatorProvider for MockProvider {
@@ -126,21 +136,50 @@ impl BannedValidators for MockProvider {
     }
 }
 
+impl CandidateInfoProvider for MockProvider {
+    type AccountId = AccountId;
+
+    fn bonded(who: &Self::AccountId) -> Balance {
+        BONDED.with(|b| b.borrow().get(who).copied().unwrap_or_default())
+    }
+
+    fn has_session_keys(who: &Self::AccountId) -> bool {
+        WITH_SESSION_KEYS.with(|k| k.borrow().contains(who))
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn make_eligible(who: &Self::AccountId) {
+        with_bonded(*who, MinimalCandidateBond::get());
+        with_session_keys(*who);
+    }
+}
+
+parameter_types! {
+    pub const MinimalCandidateBond: Balance = 100;
+    pub const MaxValidators: u32 = 10;
+    pub const MaxCandidates: u32 = 3;
+}
+
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type DataProvider = StakingMock;
     type ValidatorProvider = MockProvider;
     type MaxWinners = ConstU32<DEFAULT_MAX_WINNERS>;
     type BannedValidators = MockProvider;
+    type CandidateInfoProvider = MockProvider;
+    type MinimalCandidateBond = MinimalCandidateBond;
+    type MaxValidators = MaxValidators;
+    type MaxCandidates = MaxCandidates;
 }
 
-type MaxVotesPerVoter = ConstU32<1>;
+type MaxVotesPerVoter = ConstU32<16>;
 type AccountIdBoundedVec = BoundedVec<AccountId, MaxVotesPerVoter>;
 type Vote = (AccountId, VoteWeight, AccountIdBoundedVec);
 
 thread_local! {
     static ELECTABLE_TARGETS: RefCell<Vec<AccountId>> = RefCell::new(Default::default());
     static ELECTING_VOTERS: RefCell<Vec<Vote>> = RefCell::new(Default::default());
+    static DESIRED_TARGETS: RefCell<u32> = RefCell::new(Default::default());
 }
 
 pub fn with_electable_targets(targets: Vec<AccountId>) {
@@ -151,6 +190,10 @@ pub fn with_electing_voters(voters: Vec<Vote>) {
     ELECTING_VOTERS.with(|ev| *ev.borrow_mut() = voters);
 }
 
+pub fn with_desired_targets(targets: u32) {
+    DESIRED_TARGETS.with(|dt| *dt.borrow_mut() = targets);
+}
+
 pub struct StakingMock;
 impl ElectionDataProvider for StakingMock {
     type AccountId = AccountId;
@@ -168,7 +211,7 @@ impl ElectionDataProvider for StakingMock {
     }
 
     fn desired_targets() -> data_provider::Result<u32> {
-        Ok(0)
+        DESIRED_TARGETS.with(|dt| Ok(*dt.borrow()))
     }
 
     fn next_election_prediction(_now: u64) -> u64 {
@@ -1,14 +1,23 @@
 use frame_election_provider_support::{ElectionProvider, Support};
-use primitives::CommitteeSeats;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::unhashed,
+    traits::{OnRuntimeUpgrade, StorageVersion},
+};
+use primitives::{CommitteeSeats, ElectionMode, ElectionOpenness};
 use sp_core::bounded_vec;
+use sp_runtime::DispatchError;
 
 use crate::{
+    migrations,
     mock::{
-        with_electable_targets, with_electing_voters, AccountId, Balance, Elections, Test,
-        TestExtBuilder,
+        with_bonded, with_desired_targets, with_electable_targets, with_electing_voters,
+        with_session_keys, AccountId, Balance, Elections, RuntimeEvent, RuntimeOrigin, System,
+        Test, TestExtBuilder,
     },
-    CommitteeSize, CurrentEraValidators, NextEraCommitteeSize, NextEraNonReservedValidators,
-    NextEraReservedValidators,
+    CandidateQueue, CommitteeSize, Error, Event, Mode, NextEraCommitteeSize,
+    NextEraElectedNonReservedValidators, NextEraNonReservedValidators, NextEraReservedValidators,
+    Openness, Pallet,
 };
 
 fn no_support() -> Support<AccountId> {
@@ -35,11 +44,14 @@ fn storage_is_initialized_already_in_genesis() {
         .execute_with(|| {
             assert_eq!(CommitteeSize::<Test>::get(), COMMITTEE_SEATS);
             assert_eq!(NextEraCommitteeSize::<Test>::get(), COMMITTEE_SEATS);
-            assert_eq!(NextEraReservedValidators::<Test>::get(), RESERVED);
-            assert_eq!(NextEraNonReservedValidators::<Test>::get(), NON_RESERVED);
-            assert_eq!(CurrentEraValidators::<Test>::get().reserved, RESERVED);
+            assert_eq!(NextEraReservedValidators::<Test>::get(), RESERVED.to_vec());
+            assert_eq!(
+                NextEraNonReservedValidators::<Test>::get(),
+                NON_RESERVED.to_vec()
+            );
+            assert_eq!(Elections::current_era_validators().reserved, RESERVED);
             assert_eq!(
-                CurrentEraValidators::<Test>::get().non_reserved,
+                Elections::current_era_validators().non_reserved,
                 NON_RESERVED
             );
             // We do not expect SessionValidatorBlockCount and ValidatorEraTotalReward to be
@@ -80,3 +92,458 @@ fn validators_are_elected_only_when_staking() {
             );
         });
 }
+
+#[test]
+fn stake_of_multi_target_nominations_is_split_between_elected_targets() {
+    TestExtBuilder::new(vec![1], vec![2, 3])
+        .build()
+        .execute_with(|| {
+            with_electable_targets(vec![1, 2, 3]);
+            with_electing_voters(vec![
+                (4, 31, bounded_vec![2, 3]),
+                (5, 10, bounded_vec![3, 9]),
+                (6, 5, bounded_vec![8, 9]),
+            ]);
+
+            let elected =
+                <Elections as ElectionProvider>::elect().expect("`elect()` should succeed");
+
+            assert_eq!(
+                elected.into_inner(),
+                &[
+                    (1, no_support()),
+                    (2, support(16, vec![(4, 16)])),
+                    (3, support(25, vec![(4, 15), (5, 10)])),
+                ]
+            );
+        });
+}
+
+#[test]
+fn dpos_moves_split_stake_to_elected_targets() {
+    TestExtBuilder::new(vec![1], vec![2, 3, 4])
+        .with_committee_seats(CommitteeSeats {
+            reserved_seats: 1,
+            non_reserved_seats: 1,
+            non_reserved_finality_seats: 1,
+        })
+        .build()
+        .execute_with(|| {
+            assert_ok!(Elections::set_election_mode(
+                RuntimeOrigin::root(),
+                ElectionMode::DelegatedProofOfStake
+            ));
+            with_desired_targets(2);
+            with_electable_targets(vec![1, 2, 3, 4]);
+            with_electing_voters(vec![
+                (2, 30, bounded_vec![2]),
+                (5, 20, bounded_vec![2, 3]),
+                (6, 12, bounded_vec![3, 4]),
+            ]);
+
+            let elected =
+                <Elections as ElectionProvider>::elect().expect("`elect()` should succeed");
+
+            assert_eq!(
+                elected.into_inner(),
+                &[(1, no_support()), (2, support(50, vec![(2, 30), (5, 20)])),]
+            );
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), vec![2]);
+        });
+}
+
+#[test]
+fn election_mode_can_be_changed_only_by_root() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            assert_eq!(Mode::<Test>::get(), ElectionMode::ProofOfAuthority);
+            assert_noop!(
+                Elections::set_election_mode(
+                    RuntimeOrigin::signed(1),
+                    ElectionMode::DelegatedProofOfStake
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Elections::set_election_mode(
+                RuntimeOrigin::root(),
+                ElectionMode::DelegatedProofOfStake
+            ));
+            assert_eq!(Mode::<Test>::get(), ElectionMode::DelegatedProofOfStake);
+        });
+}
+
+#[test]
+fn dpos_elects_non_reserved_validators_with_highest_backing() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4, 5, 6])
+        .with_committee_seats(CommitteeSeats {
+            reserved_seats: 2,
+            non_reserved_seats: 2,
+            non_reserved_finality_seats: 2,
+        })
+        .build()
+        .execute_with(|| {
+            assert_ok!(Elections::set_election_mode(
+                RuntimeOrigin::root(),
+                ElectionMode::DelegatedProofOfStake
+            ));
+            with_desired_targets(5);
+            with_electable_targets(vec![1, 2, 3, 4, 5, 6]);
+            with_electing_voters(vec![
+                (3, 10, bounded_vec![3]),
+                (4, 30, bounded_vec![4]),
+                (5, 20, bounded_vec![5]),
+                (7, 15, bounded_vec![3]),
+            ]);
+
+            let elected =
+                <Elections as ElectionProvider>::elect().expect("`elect()` should succeed");
+
+            assert_eq!(
+                elected.into_inner(),
+                &[
+                    (1, no_support()),
+                    (2, no_support()),
+                    (3, support(25, vec![(3, 10), (7, 15)])),
+                    (4, support(30, vec![(4, 30)])),
+                    (5, support(20, vec![(5, 20)])),
+                ]
+            );
+            assert_eq!(
+                NextEraElectedNonReservedValidators::<Test>::get(),
+                vec![4, 3, 5]
+            );
+            assert_eq!(
+                NextEraNonReservedValidators::<Test>::get(),
+                vec![3, 4, 5, 6]
+            );
+        });
+}
+
+#[test]
+fn dpos_elects_at_least_non_reserved_seats() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4, 5, 6])
+        .with_committee_seats(CommitteeSeats {
+            reserved_seats: 2,
+            non_reserved_seats: 2,
+            non_reserved_finality_seats: 2,
+        })
+        .build()
+        .execute_with(|| {
+            assert_ok!(Elections::set_election_mode(
+                RuntimeOrigin::root(),
+                ElectionMode::DelegatedProofOfStake
+            ));
+            with_desired_targets(0);
+            with_electable_targets(vec![1, 2, 3, 4, 5, 6]);
+            with_electing_voters(vec![(5, 20, bounded_vec![5]), (6, 10, bounded_vec![6])]);
+
+            <Elections as ElectionProvider>::elect().expect("`elect()` should succeed");
+
+            assert_eq!(
+                NextEraElectedNonReservedValidators::<Test>::get(),
+                vec![5, 6]
+            );
+            assert_eq!(
+                NextEraNonReservedValidators::<Test>::get(),
+                vec![3, 4, 5, 6]
+            );
+        });
+}
+
+#[test]
+fn non_reserved_validator_can_chill() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4, 5])
+        .with_committee_seats(CommitteeSeats {
+            reserved_seats: 2,
+            non_reserved_seats: 2,
+            non_reserved_finality_seats: 2,
+        })
+        .build()
+        .execute_with(|| {
+            assert_ok!(Elections::chill(RuntimeOrigin::signed(4)));
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), vec![3, 5]);
+            assert_eq!(
+                Elections::current_era_validators().non_reserved,
+                vec![3, 4, 5]
+            );
+        });
+}
+
+#[test]
+fn only_non_reserved_validators_can_chill() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4, 5])
+        .with_committee_seats(CommitteeSeats {
+            reserved_seats: 2,
+            non_reserved_seats: 2,
+            non_reserved_finality_seats: 2,
+        })
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Elections::chill(RuntimeOrigin::signed(1)),
+                Error::<Test>::NotNonReservedValidator
+            );
+            assert_noop!(
+                Elections::chill(RuntimeOrigin::signed(6)),
+                Error::<Test>::NotNonReservedValidator
+            );
+        });
+}
+
+#[test]
+fn cannot_chill_in_permissionless_elections() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4, 5])
+        .with_committee_seats(CommitteeSeats {
+            reserved_seats: 2,
+            non_reserved_seats: 2,
+            non_reserved_finality_seats: 2,
+        })
+        .build()
+        .execute_with(|| {
+            Openness::<Test>::put(ElectionOpenness::Permissionless);
+            assert_noop!(
+                Elections::chill(RuntimeOrigin::signed(4)),
+                Error::<Test>::ChillInPermissionlessElections
+            );
+        });
+}
+
+#[test]
+fn chilling_cannot_leave_committee_unfilled() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Elections::chill(RuntimeOrigin::signed(3)),
+                Error::<Test>::NotEnoughValidators
+            );
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), vec![3, 4]);
+        });
+}
+
+#[test]
+fn candidate_has_to_bond_and_set_session_keys_to_enqueue() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Elections::enqueue_candidate(RuntimeOrigin::signed(5)),
+                Error::<Test>::InsufficientCandidateBond
+            );
+            with_bonded(5, 100);
+            assert_noop!(
+                Elections::enqueue_candidate(RuntimeOrigin::signed(5)),
+                Error::<Test>::CandidateSessionKeysNotSet
+            );
+            with_session_keys(5);
+            assert_ok!(Elections::enqueue_candidate(RuntimeOrigin::signed(5)));
+            assert_eq!(CandidateQueue::<Test>::get(), vec![5]);
+
+            assert_noop!(
+                Elections::enqueue_candidate(RuntimeOrigin::signed(5)),
+                Error::<Test>::AlreadyCandidate
+            );
+        });
+}
+
+#[test]
+fn validators_cannot_enqueue_as_candidates() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            for validator in [1, 3] {
+                with_bonded(validator, 100);
+                with_session_keys(validator);
+                assert_noop!(
+                    Elections::enqueue_candidate(RuntimeOrigin::signed(validator)),
+                    Error::<Test>::AlreadyValidator
+                );
+            }
+        });
+}
+
+#[test]
+fn root_approves_and_denies_candidates() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            for candidate in [5, 6] {
+                with_bonded(candidate, 100);
+                with_session_keys(candidate);
+                assert_ok!(Elections::enqueue_candidate(RuntimeOrigin::signed(
+                    candidate
+                )));
+            }
+
+            assert_noop!(
+                Elections::approve_candidate(RuntimeOrigin::signed(1), 5),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Elections::approve_candidate(RuntimeOrigin::root(), 5));
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), vec![3, 4, 5]);
+            assert_eq!(CandidateQueue::<Test>::get(), vec![6]);
+
+            assert_ok!(Elections::deny_candidate(RuntimeOrigin::root(), 6));
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), vec![3, 4, 5]);
+            assert!(CandidateQueue::<Test>::get().is_empty());
+
+            assert_noop!(
+                Elections::deny_candidate(RuntimeOrigin::root(), 6),
+                Error::<Test>::NotCandidate
+            );
+        });
+}
+
+#[test]
+fn approved_candidates_have_to_remain_eligible() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            with_bonded(5, 100);
+            with_session_keys(5);
+            assert_ok!(Elections::enqueue_candidate(RuntimeOrigin::signed(5)));
+
+            with_bonded(5, 99);
+            assert_noop!(
+                Elections::approve_candidate(RuntimeOrigin::root(), 5),
+                Error::<Test>::InsufficientCandidateBond
+            );
+
+            with_bonded(5, 100);
+            assert_ok!(Elections::change_validators(
+                RuntimeOrigin::root(),
+                None,
+                Some(vec![3, 4, 5]),
+                None
+            ));
+            assert_noop!(
+                Elections::approve_candidate(RuntimeOrigin::root(), 5),
+                Error::<Test>::AlreadyValidator
+            );
+            assert_eq!(CandidateQueue::<Test>::get(), vec![5]);
+        });
+}
+
+#[test]
+fn candidate_queue_is_bounded() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            for candidate in 5..9 {
+                with_bonded(candidate, 100);
+                with_session_keys(candidate);
+            }
+            for candidate in 5..8 {
+                assert_ok!(Elections::enqueue_candidate(RuntimeOrigin::signed(
+                    candidate
+                )));
+            }
+            assert_noop!(
+                Elections::enqueue_candidate(RuntimeOrigin::signed(8)),
+                Error::<Test>::TooManyCandidates
+            );
+        });
+}
+
+#[test]
+fn validators_lists_are_bounded() {
+    TestExtBuilder::new(vec![1, 2], vec![3, 4])
+        .build()
+        .execute_with(|| {
+            assert_noop!(
+                Elections::change_validators(
+                    RuntimeOrigin::root(),
+                    None,
+                    Some((3..14).collect()),
+                    None
+                ),
+                Error::<Test>::TooManyValidators
+            );
+            assert_ok!(Elections::change_validators(
+                RuntimeOrigin::root(),
+                None,
+                Some((3..13).collect()),
+                None
+            ));
+            assert_eq!(NextEraNonReservedValidators::<Test>::get().len(), 10);
+        });
+}
+
+#[test]
+fn eligible_candidates_over_max_validators_are_reported() {
+    TestExtBuilder::new(vec![1], vec![2])
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            Openness::<Test>::put(ElectionOpenness::Permissionless);
+            with_electable_targets((1..14).collect());
+
+            let elected =
+                <Elections as ElectionProvider>::elect().expect("`elect()` should succeed");
+
+            assert_eq!(elected.len(), 11);
+            System::assert_last_event(RuntimeEvent::Elections(
+                Event::NonReservedCandidatesDropped(2),
+            ));
+        });
+}
+
+#[test]
+fn dpos_ranks_candidates_before_leaving_them_out() {
+    TestExtBuilder::new(vec![1], vec![2])
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            Openness::<Test>::put(ElectionOpenness::Permissionless);
+            assert_ok!(Elections::set_election_mode(
+                RuntimeOrigin::root(),
+                ElectionMode::DelegatedProofOfStake
+            ));
+            with_desired_targets(20);
+            with_electable_targets((1..14).collect());
+            with_electing_voters(vec![(12, 20, bounded_vec![12]), (13, 30, bounded_vec![13])]);
+
+            <Elections as ElectionProvider>::elect().expect("`elect()` should succeed");
+
+            let mut elected = vec![13, 12];
+            elected.extend(2..10);
+            assert_eq!(NextEraElectedNonReservedValidators::<Test>::get(), elected);
+            let mut candidates: Vec<AccountId> = (2..10).collect();
+            candidates.extend([12, 13]);
+            assert_eq!(NextEraNonReservedValidators::<Test>::get(), candidates);
+            System::assert_last_event(RuntimeEvent::Elections(
+                Event::NonReservedCandidatesDropped(2),
+            ));
+        });
+}
+
+#[test]
+fn migration_bounds_validators_lists() {
+    TestExtBuilder::new(vec![1], vec![2])
+        .build()
+        .execute_with(|| {
+            let non_reserved: Vec<AccountId> = (2..14).collect();
+            unhashed::put(
+                &NextEraNonReservedValidators::<Test>::hashed_key(),
+                &non_reserved,
+            );
+            StorageVersion::new(5).put::<Pallet<Test>>();
+
+            migrations::v6::Migration::<Test>::on_runtime_upgrade();
+
+            assert_eq!(
+                StorageVersion::get::<Pallet<Test>>(),
+                StorageVersion::new(6)
+            );
+            assert_eq!(
+                NextEraNonReservedValidators::<Test>::get(),
+                non_reserved[..10].to_vec()
+            );
+            assert_eq!(
+                NextEraElectedNonReservedValidators::<Test>::get(),
+                non_reserved[..10].to_vec()
+            );
+            assert_eq!(NextEraReservedValidators::<Test>::get(), vec![1]);
+        });
+}
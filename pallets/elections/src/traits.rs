@@ -1,6 +1,19 @@
+use primitives::Balance;
 use sp_staking::EraIndex;
 use sp_std::vec::Vec;
 
+/// Information about accounts that want to become validators.
+pub trait CandidateInfoProvider {
+    type AccountId;
+    /// Amount the account has actively bonded in staking.
+    fn bonded(who: &Self::AccountId) -> Balance;
+    /// Whether the account has set its session keys.
+    fn has_session_keys(who: &Self::AccountId) -> bool;
+    /// Makes the account bond enough and set its session keys, for benchmarking.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn make_eligible(_who: &Self::AccountId) {}
+}
+
 pub trait ValidatorProvider {
     type AccountId;
     fn elected_validators(era: EraIndex) -> Vec<Self::AccountId>;
@@ -0,0 +1,93 @@
+use frame_benchmarking::v2::*;
+use frame_system::{ConsumerLimits, RawOrigin};
+use parity_scale_codec::Decode;
+use sp_runtime::traits::TrailingZeroInput;
+use sp_std::vec::Vec;
+
+use crate::{Call, Config, Pallet, MAX_ACCOUNTS_IN_BATCH};
+
+const SEED: u32 = 0;
+
+/// Existing account, with `consumers` consumer references and next session keys
+fn setup_account<T>(name: &'static str, index: u32, consumers: u32) -> T::AccountId
+where
+    T: Config + pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
+{
+    let who: T::AccountId = account(name, index, SEED);
+    frame_system::Pallet::<T>::inc_providers(&who);
+    for _ in 0..consumers {
+        frame_system::Pallet::<T>::inc_consumers_without_limit(&who)
+            .expect("Account has a provider");
+    }
+    let keys =
+        T::Keys::decode(&mut TrailingZeroInput::zeroes()).expect("Keys should decode from zeroes");
+    pallet_session::NextKeys::<T>::insert(&who, keys);
+
+    who
+}
+
+fn consumers<T: Config>(who: &T::AccountId) -> u32 {
+    frame_system::Pallet::<T>::consumers(who)
+}
+
+#[benchmarks(
+    where T: pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>
+)]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn fix_accounts_consumers_counter() {
+        let caller = whitelisted_caller();
+        // account is not bonded, so next session keys do not hold a consumer reference
+        let who = setup_account::<T>("who", 0, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), who.clone());
+
+        assert_eq!(consumers::<T>(&who), 0);
+    }
+
+    #[benchmark]
+    fn remove_orphaned_next_keys() {
+        let caller = whitelisted_caller();
+        let who = setup_account::<T>("who", 0, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), who.clone());
+
+        assert!(pallet_session::NextKeys::<T>::get(&who).is_none());
+        assert_eq!(consumers::<T>(&who), 0);
+    }
+
+    #[benchmark]
+    fn fix_accounts_consumers_overflow() {
+        let caller = whitelisted_caller();
+        let max_consumers = <T as frame_system::Config>::MaxConsumers::max_consumers();
+        let who = setup_account::<T>("who", 0, max_consumers);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), who.clone());
+
+        assert_eq!(consumers::<T>(&who), 0);
+    }
+
+    #[benchmark]
+    fn fix_accounts_consumers_counter_batch(n: Linear<1, MAX_ACCOUNTS_IN_BATCH>) {
+        let caller = whitelisted_caller();
+        let accounts: Vec<_> = (0..n).map(|i| setup_account::<T>("who", i, 1)).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), accounts.clone());
+
+        for who in accounts {
+            assert_eq!(consumers::<T>(&who), 0);
+        }
+    }
+
+    impl_benchmark_test_suite!(
+        Pallet,
+        crate::tests::setup::new_test_ext(&[(1, true, 1000)]),
+        crate::tests::setup::TestRuntime
+    );
+}
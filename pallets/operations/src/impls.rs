@@ -1,12 +1,14 @@
 #![allow(clippy::nonminimal_bool)]
 
-use frame_support::dispatch::DispatchResult;
+use frame_support::{dispatch::DispatchResult, ensure, storage::with_storage_layer};
+use frame_system::ConsumerLimits;
 use parity_scale_codec::Encode;
 use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
 
 use crate::{
-    pallet::{Config, Event, Pallet},
+    pallet::{Config, Error, Event, Pallet},
     traits::{
         AccountInfoProvider, BalancesProvider, BondedStashProvider, ContractInfoProvider,
         NextKeysSessionProvider,
@@ -19,20 +21,7 @@ impl<T: Config> Pallet<T> {
     /// counter is not as expected, increment or decrement current counter
     pub fn fix_consumer_counter(who: T::AccountId) -> DispatchResult {
         let current_consumers = T::AccountInfoProvider::get_consumers(&who);
-        let mut expected_consumers: u32 = 0;
-
-        if Self::reserved_or_frozen_non_zero(&who) {
-            expected_consumers += 1;
-        }
-        if Self::is_contract_account(&who) {
-            expected_consumers += 1;
-        }
-        if Self::is_bonded(&who) {
-            expected_consumers += 1;
-        }
-        if Self::has_next_session_keys_and_account_is_controller(&who) {
-            expected_consumers += 1;
-        }
+        let expected_consumers = Self::expected_consumers(&who);
 
         #[allow(clippy::comparison_chain)]
         if current_consumers < expected_consumers {
@@ -58,6 +47,87 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Fix consumers counter of every account in `accounts`, reverting changes made for an
+    /// account if fixing it fails
+    pub fn fix_consumer_counters(accounts: Vec<T::AccountId>) {
+        for who in accounts {
+            if let Err(error) = with_storage_layer(|| Self::fix_consumer_counter(who.clone())) {
+                log::debug!(
+                    target: LOG_TARGET,
+                    "Fixing consumers counter of account {:?} failed: {:?}",
+                    HexDisplay::from(&who.encode()), error);
+                Self::deposit_event(Event::ConsumersCounterFixFailed { who, error });
+            }
+        }
+    }
+
+    /// Decrement consumers counter of a `who` account down to the expected one, by at most
+    /// `MaxConsumers` in a single call
+    pub fn fix_consumer_overflow(who: T::AccountId) -> DispatchResult {
+        let current_consumers = T::AccountInfoProvider::get_consumers(&who);
+        let expected_consumers = Self::expected_consumers(&who);
+        ensure!(
+            current_consumers > expected_consumers,
+            Error::<T>::NoConsumersOverflow
+        );
+
+        let delta = (current_consumers - expected_consumers)
+            .min(<T as frame_system::Config>::MaxConsumers::max_consumers());
+        for _ in 0..delta {
+            frame_system::Pallet::<T>::dec_consumers(&who);
+        }
+        log::debug!(
+            target: LOG_TARGET,
+            "Account {:?} had consumers overflow: current({}) > expected ({}), decremented by {}",
+            HexDisplay::from(&who.encode()), current_consumers, expected_consumers, delta);
+        Self::deposit_event(Event::ConsumersCounterOverflowFixed { who, delta });
+
+        Ok(())
+    }
+
+    /// Expected consumers counter of a `who` account, see
+    /// [`Pallet::fix_accounts_consumers_counter`]
+    fn expected_consumers(who: &T::AccountId) -> u32 {
+        let mut expected_consumers: u32 = 0;
+
+        if Self::reserved_or_frozen_non_zero(who) {
+            expected_consumers += 1;
+        }
+        if Self::is_contract_account(who) {
+            expected_consumers += 1;
+        }
+        if Self::is_bonded(who) {
+            expected_consumers += 1;
+        }
+        if Self::has_next_session_keys_and_account_is_controller(who) {
+            expected_consumers += 1;
+        }
+
+        expected_consumers
+    }
+
+    /// Remove next session keys of `who` if it is neither a stash nor a controller, and release
+    /// the consumer reference the keys were holding
+    pub fn remove_orphaned_keys(who: T::AccountId) -> DispatchResult {
+        ensure!(
+            T::NextKeysSessionProvider::has_next_session_keys(&who)
+                && !Self::is_bonded(&who)
+                && T::BondedStashProvider::get_stash(&who).is_none(),
+            Error::<T>::NotOrphanedNextKeys
+        );
+
+        T::NextKeysSessionProvider::remove_next_session_keys(&who);
+        log::debug!(
+            target: LOG_TARGET,
+            "Removed orphaned next session keys of account {:?}",
+            HexDisplay::from(&who.encode())
+        );
+        Self::deposit_event(Event::OrphanedNextKeysRemoved { who: who.clone() });
+        Self::decrement_consumers(&who);
+
+        Ok(())
+    }
+
     fn reserved_or_frozen_non_zero(who: &T::AccountId) -> bool {
         !T::BalancesProvider::is_reserved_zero(who) || !T::BalancesProvider::is_frozen_zero(who)
     }
@@ -3,6 +3,8 @@
 
 extern crate core;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod impls;
 mod traits;
 
@@ -16,6 +18,8 @@ pub const LOG_TARGET: &str = "pallet-operations";
 // harcoding as those consts are not public in substrate
 pub const STAKING_ID: LockIdentifier = *b"staking ";
 pub const VESTING_ID: LockIdentifier = *b"vesting ";
+/// Maximal number of accounts fixed in a single `fix_accounts_consumers_counter_batch` call
+pub const MAX_ACCOUNTS_IN_BATCH: u32 = 100;
 
 pub use pallet::*;
 
@@ -24,13 +28,14 @@ pub use pallet::*;
 pub mod pallet {
     use frame_support::{pallet_prelude::*, weights::constants::WEIGHT_REF_TIME_PER_MILLIS};
     use frame_system::{ensure_signed, pallet_prelude::OriginFor};
+    use sp_std::vec::Vec;
 
     use crate::{
         traits::{
             AccountInfoProvider, BalancesProvider, BondedStashProvider, ContractInfoProvider,
             NextKeysSessionProvider,
         },
-        STORAGE_VERSION,
+        MAX_ACCOUNTS_IN_BATCH, STORAGE_VERSION,
     };
 
     #[pallet::config]
@@ -61,6 +66,31 @@ pub mod pallet {
 
         /// A consumers counter was decremented for an account
         ConsumersCounterDecremented { who: T::AccountId },
+
+        /// Next session keys of an account that is not bonded were removed
+        OrphanedNextKeysRemoved { who: T::AccountId },
+
+        /// A consumers counter of an account was decremented by `delta` to fix its overflow
+        ConsumersCounterOverflowFixed { who: T::AccountId, delta: u32 },
+
+        /// Fixing a consumers counter of an account in a batch failed
+        ConsumersCounterFixFailed {
+            who: T::AccountId,
+            error: DispatchError,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Account has no next session keys, or is bonded so its keys can be purged in pallet
+        /// session
+        NotOrphanedNextKeys,
+
+        /// Account's consumers counter is not greater than the expected one
+        NoConsumersOverflow,
+
+        /// More than [`MAX_ACCOUNTS_IN_BATCH`] accounts were given
+        TooManyAccounts,
     }
 
     #[pallet::call]
@@ -95,5 +125,78 @@ pub mod pallet {
             Self::fix_consumer_counter(who)?;
             Ok(())
         }
+
+        /// An account that is not bonded anymore can still be present in `session.next_keys`.
+        /// Pallet session cannot purge such keys, as it requires the account to be a bonded
+        /// stash, so the entry and the consumer reference it holds stay forever.
+        ///
+        /// `remove_orphaned_next_keys` removes next session keys of such an account together
+        /// with their ownership entries, and decrements its consumers counter.
+        ///
+        /// - `origin`: Must be `Signed`.
+        /// - `who`: An account with orphaned next session keys
+        ///
+        #[pallet::call_index(1)]
+        #[pallet::weight(
+        Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS.saturating_mul(8), 0)
+        )]
+        pub fn remove_orphaned_next_keys(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::remove_orphaned_keys(who)
+        }
+
+        /// Accounts with a spuriously high `consumers` counter, e.g. left by historic bugs,
+        /// cannot be reaped. `fix_accounts_consumers_overflow` calculates expected consumers
+        /// counter the same way as `fix_accounts_consumers_counter` does, and decrements current
+        /// one down to it at once, instead of by one.
+        ///
+        /// The counter is decremented by at most `MaxConsumers` in a single call, you might want
+        /// to call this extrinsic more than once for bigger overflows.
+        ///
+        /// - `origin`: Must be `Signed`.
+        /// - `who`: An account to be fixed
+        ///
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+        Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS.saturating_mul(8), 0)
+        )]
+        pub fn fix_accounts_consumers_overflow(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            Self::fix_consumer_overflow(who)
+        }
+
+        /// Batched version of `fix_accounts_consumers_counter`, fixing up to
+        /// [`MAX_ACCOUNTS_IN_BATCH`] accounts.
+        ///
+        /// Accounts are fixed independently, a failure of one of them is reported with
+        /// `ConsumersCounterFixFailed` event and does not affect the others. Successful fixes
+        /// emit the same events as `fix_accounts_consumers_counter` does.
+        ///
+        /// - `origin`: Must be `Signed`.
+        /// - `accounts`: Accounts to be fixed
+        ///
+        #[pallet::call_index(3)]
+        #[pallet::weight(
+        Weight::from_parts(WEIGHT_REF_TIME_PER_MILLIS.saturating_mul(8), 0)
+            .saturating_mul(accounts.len() as u64)
+        )]
+        pub fn fix_accounts_consumers_counter_batch(
+            origin: OriginFor<T>,
+            accounts: Vec<T::AccountId>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            ensure!(
+                accounts.len() <= MAX_ACCOUNTS_IN_BATCH as usize,
+                Error::<T>::TooManyAccounts
+            );
+            Self::fix_consumer_counters(accounts);
+            Ok(())
+        }
     }
 }
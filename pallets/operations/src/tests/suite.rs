@@ -1,13 +1,17 @@
 use std::{env::var, path::PathBuf};
 
 use frame_support::{
-    assert_ok,
+    assert_noop, assert_ok,
     traits::{Currency, LockableCurrency, ReservableCurrency, WithdrawReasons},
     weights::Weight,
 };
 use pallet_contracts::{Code, CollectEvents, DebugInfo};
 use pallet_staking::RewardDestination;
-use sp_runtime::traits::Hash;
+use sp_runtime::{
+    testing::UintAuthorityId,
+    traits::{Hash, OpaqueKeys},
+    DispatchError, RuntimeAppPublic,
+};
 
 use super::setup::*;
 use crate::VESTING_ID;
@@ -589,3 +593,182 @@ fn given_nominator_account_with_staking_lock_and_consumer_overflow_when_fixing_c
         assert_eq!(consumers(authority_id), 3);
     });
 }
+
+#[test]
+fn given_unbonded_account_with_next_keys_when_removing_orphaned_keys_then_keys_and_consumer_are_removed(
+) {
+    let authority_id = 1_u64;
+    let non_authority_id = 2_u64;
+    let total_balance_authority = 1000_u128;
+    let total_balance_non_authority = 999_u128;
+    new_test_ext(&[
+        (authority_id, true, total_balance_authority),
+        (non_authority_id, false, total_balance_non_authority),
+    ])
+    .execute_with(|| {
+        // mimic keys left behind by an account that has unbonded since setting them
+        let keys = TestSessionKeys {
+            other: non_authority_id.into(),
+        };
+        let key_owner = (
+            UintAuthorityId::ID,
+            keys.get_raw(UintAuthorityId::ID).to_vec(),
+        );
+        pallet_session::NextKeys::<TestRuntime>::insert(non_authority_id, keys);
+        pallet_session::KeyOwner::<TestRuntime>::insert(&key_owner, non_authority_id);
+        frame_system::Pallet::<TestRuntime>::inc_consumers_without_limit(&non_authority_id)
+            .unwrap();
+        assert_eq!(consumers(non_authority_id), 1);
+        frame_system::Pallet::<TestRuntime>::reset_events();
+
+        assert_ok!(crate::Pallet::<TestRuntime>::remove_orphaned_next_keys(
+            RuntimeOrigin::signed(authority_id),
+            non_authority_id
+        ));
+        assert_eq!(
+            pallet_operations_events(),
+            [
+                crate::Event::OrphanedNextKeysRemoved {
+                    who: non_authority_id
+                },
+                crate::Event::ConsumersCounterDecremented {
+                    who: non_authority_id
+                }
+            ]
+        );
+        assert!(pallet_session::NextKeys::<TestRuntime>::get(non_authority_id).is_none());
+        assert!(pallet_session::KeyOwner::<TestRuntime>::get(&key_owner).is_none());
+        assert_eq!(consumers(non_authority_id), 0);
+    });
+}
+
+#[test]
+fn given_bonded_account_when_removing_orphaned_keys_then_it_fails() {
+    let authority_id = 1_u64;
+    let non_authority_id = 2_u64;
+    let total_balance_authority = 1000_u128;
+    let total_balance_non_authority = 999_u128;
+    new_test_ext(&[
+        (authority_id, true, total_balance_authority),
+        (non_authority_id, false, total_balance_non_authority),
+    ])
+    .execute_with(|| {
+        assert_noop!(
+            crate::Pallet::<TestRuntime>::remove_orphaned_next_keys(
+                RuntimeOrigin::signed(non_authority_id),
+                authority_id
+            ),
+            crate::Error::<TestRuntime>::NotOrphanedNextKeys
+        );
+        assert_noop!(
+            crate::Pallet::<TestRuntime>::remove_orphaned_next_keys(
+                RuntimeOrigin::signed(authority_id),
+                non_authority_id
+            ),
+            crate::Error::<TestRuntime>::NotOrphanedNextKeys
+        );
+        assert_eq!(consumers(authority_id), 3);
+    });
+}
+
+#[test]
+fn given_account_with_consumers_overflow_when_fixing_overflow_then_consumers_decrease_to_expected()
+{
+    let authority_id = 1_u64;
+    let non_authority_id = 2_u64;
+    let total_balance_authority = 1000_u128;
+    let total_balance_non_authority = 999_u128;
+    new_test_ext(&[
+        (authority_id, true, total_balance_authority),
+        (non_authority_id, false, total_balance_non_authority),
+    ])
+    .execute_with(|| {
+        // +1 from bond
+        // +1 from frozen > 0
+        // +1 from session keys
+        assert_eq!(consumers(authority_id), 3);
+        for _ in 0..3 {
+            frame_system::Pallet::<TestRuntime>::inc_consumers_without_limit(&authority_id)
+                .unwrap();
+        }
+        assert_eq!(consumers(authority_id), 6);
+        frame_system::Pallet::<TestRuntime>::reset_events();
+
+        assert_ok!(
+            crate::Pallet::<TestRuntime>::fix_accounts_consumers_overflow(
+                RuntimeOrigin::signed(non_authority_id),
+                authority_id
+            )
+        );
+        assert_eq!(
+            pallet_operations_events(),
+            [crate::Event::ConsumersCounterOverflowFixed {
+                who: authority_id,
+                delta: 3
+            }]
+        );
+        assert_eq!(consumers(authority_id), 3);
+
+        assert_noop!(
+            crate::Pallet::<TestRuntime>::fix_accounts_consumers_overflow(
+                RuntimeOrigin::signed(non_authority_id),
+                authority_id
+            ),
+            crate::Error::<TestRuntime>::NoConsumersOverflow
+        );
+    });
+}
+
+#[test]
+fn given_batch_of_accounts_when_fixing_consumers_then_each_account_is_fixed_independently() {
+    let authority_id = 1_u64;
+    let non_authority_id = 2_u64;
+    let non_existing_id = 3_u64;
+    let total_balance_authority = 1000_u128;
+    let total_balance_non_authority = 999_u128;
+    new_test_ext(&[
+        (authority_id, true, total_balance_authority),
+        (non_authority_id, false, total_balance_non_authority),
+    ])
+    .execute_with(|| {
+        frame_system::Pallet::<TestRuntime>::dec_consumers(&authority_id);
+        assert_eq!(consumers(authority_id), 2);
+        // bonded account without providers cannot have its consumers incremented
+        pallet_staking::Bonded::<TestRuntime>::insert(non_existing_id, non_existing_id);
+        frame_system::Pallet::<TestRuntime>::reset_events();
+
+        assert_ok!(
+            crate::Pallet::<TestRuntime>::fix_accounts_consumers_counter_batch(
+                RuntimeOrigin::signed(non_authority_id),
+                vec![non_existing_id, authority_id, non_authority_id]
+            )
+        );
+        assert_eq!(
+            pallet_operations_events(),
+            [
+                crate::Event::ConsumersCounterFixFailed {
+                    who: non_existing_id,
+                    error: DispatchError::NoProviders
+                },
+                crate::Event::ConsumersCounterIncremented { who: authority_id }
+            ]
+        );
+        assert_eq!(consumers(authority_id), 3);
+        assert_eq!(consumers(non_authority_id), 0);
+        assert_eq!(consumers(non_existing_id), 0);
+    });
+}
+
+#[test]
+fn given_too_many_accounts_when_fixing_consumers_in_batch_then_it_fails() {
+    let authority_id = 1_u64;
+    new_test_ext(&[(authority_id, true, 1000_u128)]).execute_with(|| {
+        assert_noop!(
+            crate::Pallet::<TestRuntime>::fix_accounts_consumers_counter_batch(
+                RuntimeOrigin::signed(authority_id),
+                (0..crate::MAX_ACCOUNTS_IN_BATCH as u64 + 1).collect()
+            ),
+            crate::Error::<TestRuntime>::TooManyAccounts
+        );
+    });
+}
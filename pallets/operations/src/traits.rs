@@ -1,5 +1,5 @@
 use frame_support::traits::StoredMap;
-use sp_runtime::traits::Zero;
+use sp_runtime::traits::{OpaqueKeys, Zero};
 use sp_staking::StakingAccount;
 
 pub trait AccountInfoProvider {
@@ -56,6 +56,10 @@ pub trait NextKeysSessionProvider {
 
     /// Retrieves information whether given account is in the next session keys
     fn has_next_session_keys(who: &Self::AccountId) -> bool;
+
+    /// Removes given account's next session keys together with their ownership entries,
+    /// returns false if there were no keys
+    fn remove_next_session_keys(who: &Self::AccountId) -> bool;
 }
 
 impl<T> NextKeysSessionProvider for pallet_session::Pallet<T>
@@ -67,6 +71,21 @@ where
     fn has_next_session_keys(who: &Self::AccountId) -> bool {
         pallet_session::NextKeys::<T>::get(who).is_some()
     }
+
+    fn remove_next_session_keys(who: &Self::AccountId) -> bool {
+        let keys = match pallet_session::NextKeys::<T>::take(who) {
+            Some(keys) => keys,
+            None => return false,
+        };
+        for id in T::Keys::key_ids() {
+            let key_owner = (*id, keys.get_raw(*id).to_vec());
+            if pallet_session::KeyOwner::<T>::get(&key_owner).as_ref() == Some(who) {
+                pallet_session::KeyOwner::<T>::remove(&key_owner);
+            }
+        }
+
+        true
+    }
 }
 
 pub trait BondedStashProvider {
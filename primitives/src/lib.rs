@@ -1,6 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -13,10 +13,10 @@ pub use sp_runtime::{
 use sp_runtime::{
     impl_opaque_keys,
     traits::{IdentifyAccount, Verify},
-    MultiSignature, Perquintill,
+    MultiSignature, Perquintill, RuntimeAppPublic,
 };
 pub use sp_staking::{EraIndex, SessionIndex};
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
 
 pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"alp0");
 
@@ -172,14 +172,35 @@ pub struct SessionCommittee<T> {
 }
 
 /// Openness of the process of the elections
-#[derive(Decode, Encode, TypeInfo, Debug, Clone, PartialEq, Eq)]
+#[derive(Decode, Encode, TypeInfo, MaxEncodedLen, Debug, Clone, PartialEq, Eq)]
 pub enum ElectionOpenness {
     Permissioned,
     Permissionless,
 }
 
+/// Way of choosing non reserved validators from the eligible candidates
+#[derive(Decode, Encode, TypeInfo, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElectionMode {
+    /// All eligible candidates are elected.
+    ProofOfAuthority,
+    /// Only the eligible candidates with the highest total backing are elected.
+    DelegatedProofOfStake,
+}
+
 /// Represent desirable size of a committee in a session
-#[derive(Decode, Encode, TypeInfo, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(
+    Decode,
+    Encode,
+    TypeInfo,
+    MaxEncodedLen,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
 pub struct CommitteeSeats {
     /// Size of reserved validators in a session
     pub reserved_seats: u32,
@@ -217,6 +238,31 @@ pub trait AbftScoresProvider {
     fn clear_nonce();
 }
 
+/// Handles authorities proven to have equivocated in AlephBFT. Loose pallet coupling via traits.
+pub trait EquivocationHandler<T> {
+    /// `offender` created two different units for the same round of the session `session`.
+    fn on_equivocation(offender: &T, session: SessionIndex);
+}
+
+impl<T> EquivocationHandler<T> for () {
+    fn on_equivocation(_offender: &T, _session: SessionIndex) {}
+}
+
+/// Summary of a validator's block production in an era
+#[derive(
+    Decode, Encode, TypeInfo, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct ValidatorEraPerformance {
+    /// Blocks produced in the sessions the validator was a block producer in
+    pub blocks_produced: BlockCount,
+    /// Blocks expected from the validator in those sessions
+    pub blocks_expected: BlockCount,
+    /// Number of sessions in which the validator underperformed in block production
+    pub underperformed_sessions: SessionCount,
+    /// Reward points the validator got, after scaling them by its performance
+    pub reward_points: u32,
+}
+
 /// Configurable parameters for ban validator mechanism
 #[derive(Decode, Encode, TypeInfo, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FinalityBanConfig {
@@ -259,6 +305,7 @@ pub const DEFAULT_BAN_MINIMAL_EXPECTED_PERFORMANCE: Perbill = Perbill::from_perc
 pub const DEFAULT_BAN_SESSION_COUNT_THRESHOLD: SessionCount = 3;
 pub const DEFAULT_BAN_REASON_LENGTH: u32 = 300;
 pub const DEFAULT_MAX_WINNERS: u32 = u32::MAX;
+pub const DEFAULT_MAX_VALIDATORS: u32 = 1_000;
 
 impl Default for ProductionBanConfig {
     fn default() -> Self {
@@ -280,6 +327,10 @@ pub enum BanReason {
 
     /// Any arbitrary reason
     OtherReason(BoundedVec<u8, ConstU32<DEFAULT_BAN_REASON_LENGTH>>),
+
+    /// Validator has been proven to create two different AlephBFT units for the same round in
+    /// the given session
+    Equivocation(SessionIndex),
 }
 
 /// Details of why and for how long a validator is removed from the committee
@@ -292,7 +343,7 @@ pub struct BanInfo {
 }
 
 /// Represent committee, ie set of nodes that produce and finalize blocks in the session
-#[derive(Eq, Clone, PartialEq, Decode, Encode, TypeInfo)]
+#[derive(Eq, Clone, PartialEq, Decode, Encode, TypeInfo, Debug, Serialize, Deserialize)]
 pub struct EraValidators<AccountId> {
     /// Validators that are chosen to be in committee every single session.
     pub reserved: Vec<AccountId>,
@@ -309,6 +360,18 @@ impl<AccountId> Default for EraValidators<AccountId> {
     }
 }
 
+/// Validators and committee sizes of the current and the next era, together with the thresholds
+/// of the ban mechanism
+#[derive(Eq, Clone, PartialEq, Decode, Encode, TypeInfo, Debug, Serialize, Deserialize)]
+pub struct EraCommitteeInfo<AccountId> {
+    pub current_validators: EraValidators<AccountId>,
+    pub current_committee_size: CommitteeSeats,
+    pub next_validators: EraValidators<AccountId>,
+    pub next_committee_size: CommitteeSeats,
+    pub production_ban_config: ProductionBanConfig,
+    pub finality_ban_config: FinalityBanConfig,
+}
+
 #[derive(Encode, Decode, TypeInfo, PartialEq, Eq, Debug)]
 pub enum ApiError {
     DecodeKey,
@@ -323,18 +386,81 @@ pub enum SessionValidatorError {
     Other(Vec<u8>),
 }
 
+/// Maximal number of keys in an emergency finalizer set.
+pub const MAX_EMERGENCY_FINALIZERS: u32 = 32;
+
+/// Keys allowed to finalize blocks in an emergency, at least `threshold` of which have to sign
+/// a block to finalize it.
+#[derive(Clone, Debug, TypeInfo, Encode, Decode, PartialEq, Eq)]
+pub struct EmergencyFinalizerSet<AID> {
+    keys: BoundedVec<AID, ConstU32<MAX_EMERGENCY_FINALIZERS>>,
+    threshold: u32,
+}
+
+impl<AID> EmergencyFinalizerSet<AID> {
+    /// Returns `None` unless the threshold is between one and the number of keys, and there are
+    /// at most `MAX_EMERGENCY_FINALIZERS` keys.
+    pub fn new(keys: Vec<AID>, threshold: u32) -> Option<Self> {
+        if threshold == 0 || threshold as usize > keys.len() {
+            return None;
+        }
+        let keys = BoundedVec::try_from(keys).ok()?;
+        Some(EmergencyFinalizerSet { keys, threshold })
+    }
+
+    /// A set consisting of a single key, which is enough to finalize blocks on its own.
+    pub fn single(key: AID) -> Self {
+        EmergencyFinalizerSet {
+            keys: BoundedVec::truncate_from(Vec::from([key])),
+            threshold: 1,
+        }
+    }
+
+    pub fn keys(&self) -> &Vec<AID> {
+        &self.keys
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+}
+
+impl<AID: RuntimeAppPublic> EmergencyFinalizerSet<AID> {
+    /// Verifies whether at least `threshold` different keys of the set correctly signed the
+    /// message. Signatures are indexed by the position of the key in the set.
+    pub fn is_complete(
+        &self,
+        msg: &[u8],
+        signatures: &crypto::SignatureSet<AID::Signature>,
+    ) -> bool {
+        let signers: BTreeSet<_> = signatures
+            .0
+            .iter()
+            .filter(|indexed| match self.keys.get(indexed.index as usize) {
+                Some(key) => key.verify(&msg, &indexed.signature),
+                None => false,
+            })
+            .map(|indexed| indexed.index)
+            .collect();
+        signers.len() >= self.threshold as usize
+    }
+}
+
 /// All the data needed to verify block finality justifications.
 #[derive(Clone, Debug, TypeInfo, Encode, Decode, PartialEq, Eq)]
 pub struct SessionAuthorityData {
     authorities: Vec<AuthorityId>,
-    emergency_finalizer: Option<AuthorityId>,
+    emergency_finalizers: Option<EmergencyFinalizerSet<AuthorityId>>,
 }
 
 impl SessionAuthorityData {
-    pub fn new(authorities: Vec<AuthorityId>, emergency_finalizer: Option<AuthorityId>) -> Self {
+    pub fn new(
+        authorities: Vec<AuthorityId>,
+        emergency_finalizers: Option<EmergencyFinalizerSet<AuthorityId>>,
+    ) -> Self {
         SessionAuthorityData {
             authorities,
-            emergency_finalizer,
+            emergency_finalizers,
         }
     }
 
@@ -342,14 +468,48 @@ impl SessionAuthorityData {
         &self.authorities
     }
 
-    pub fn emergency_finalizer(&self) -> &Option<AuthorityId> {
-        &self.emergency_finalizer
+    pub fn emergency_finalizers(&self) -> &Option<EmergencyFinalizerSet<AuthorityId>> {
+        &self.emergency_finalizers
     }
 }
 
-pub type Version = u32;
+/// Authority data in the format returned by `AlephSessionApi` before version 2, with at most a
+/// single emergency finalizer.
+#[derive(Clone, Debug, TypeInfo, Encode, Decode, PartialEq, Eq)]
+pub struct LegacySessionAuthorityData {
+    authorities: Vec<AuthorityId>,
+    emergency_finalizer: Option<AuthorityId>,
+}
 
+impl LegacySessionAuthorityData {
+    pub fn new(authorities: Vec<AuthorityId>, emergency_finalizer: Option<AuthorityId>) -> Self {
+        LegacySessionAuthorityData {
+            authorities,
+            emergency_finalizer,
+        }
+    }
+}
+
+impl From<LegacySessionAuthorityData> for SessionAuthorityData {
+    fn from(data: LegacySessionAuthorityData) -> Self {
+        SessionAuthorityData::new(
+            data.authorities,
+            data.emergency_finalizer.map(EmergencyFinalizerSet::single),
+        )
+    }
+}
+
+/// A block finalized by the emergency finalizers.
 #[derive(Clone, Debug, Decode, Encode, PartialEq, Eq, TypeInfo)]
+pub struct EmergencyFinalization<BlockNumber, AuthorityId> {
+    pub block_number: BlockNumber,
+    pub session: SessionIndex,
+    pub finalizers: EmergencyFinalizerSet<AuthorityId>,
+}
+
+pub type Version = u32;
+
+#[derive(Clone, Debug, Decode, Encode, PartialEq, Eq, TypeInfo, Serialize, Deserialize)]
 pub struct VersionChange {
     pub version_incoming: Version,
     pub session: SessionIndex,
@@ -390,6 +550,8 @@ impl<T> Default for SessionValidators<T> {
 pub trait SessionInfoProvider<T> {
     fn current_session() -> SessionIndex;
     fn next_session_block_number(current_block: T) -> Option<T>;
+    /// Session the block with the given number belongs to.
+    fn session_of_block(block: T) -> SessionIndex;
 }
 
 pub trait BannedValidators {
@@ -457,6 +619,44 @@ pub struct Score {
     pub points: RawScore,
 }
 
+/// Position of an AlephBFT unit, read directly from its encoding: the creator index (`u64`) and
+/// round (`u16`) open the encoded unit, while the session (`u64`) closes it.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct UnitCoord {
+    pub creator: u64,
+    pub round: u16,
+    pub session: u64,
+}
+
+impl UnitCoord {
+    const SESSION_LENGTH: usize = 8;
+
+    pub fn from_encoded_unit(unit: &[u8]) -> Option<Self> {
+        let mut input = unit;
+        let (creator, round) = <(u64, u16)>::decode(&mut input).ok()?;
+        let session_start = unit
+            .len()
+            .checked_sub(Self::SESSION_LENGTH)
+            .filter(|start| *start >= unit.len() - input.len())?;
+        let session = u64::decode(&mut &unit[session_start..]).ok()?;
+        Some(UnitCoord {
+            creator,
+            round,
+            session,
+        })
+    }
+}
+
+/// Two different AlephBFT units created by the same authority for the same round of a session,
+/// each with the signature of its creator over the hash of the encoded unit.
+#[derive(PartialEq, Eq, Decode, Encode, TypeInfo, Debug, Clone)]
+pub struct EquivocationProof<S> {
+    pub first_unit: Vec<u8>,
+    pub first_signature: S,
+    pub second_unit: Vec<u8>,
+    pub second_signature: S,
+}
+
 pub mod crypto {
     use core::marker::PhantomData;
 
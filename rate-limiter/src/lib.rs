@@ -1,3 +1,4 @@
+mod metrics;
 mod rate_limiter;
 mod token_bucket;
 
@@ -5,13 +6,17 @@ use std::num::{NonZeroU64, TryFromIntError};
 
 use futures::{future::BoxFuture, ready, FutureExt};
 use rate_limiter::RateLimiterFacade;
-use tokio::io::AsyncRead;
+use tokio::io::{AsyncRead, AsyncWrite};
 
-pub use crate::{rate_limiter::SharedRateLimiter, token_bucket::SharedTokenBucket};
+pub use crate::{
+    metrics::RateLimiterMetrics,
+    rate_limiter::SharedRateLimiter,
+    token_bucket::{RateScaler, SharedTokenBucket},
+};
 
 const LOG_TARGET: &str = "rate-limiter";
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 pub struct NonZeroRatePerSecond(NonZeroU64);
 
 pub const MIN: NonZeroRatePerSecond = NonZeroRatePerSecond(NonZeroU64::MIN);
@@ -128,15 +133,99 @@ where
     }
 }
 
+pub struct RateLimitedAsyncWrite<Write> {
+    rate_limiter: BoxFuture<'static, RateLimiterFacade>,
+    inner: Write,
+}
+
+impl<Write> RateLimitedAsyncWrite<Write> {
+    pub fn new(write: Write, rate_limiter: RateLimiterFacade) -> Self {
+        Self {
+            rate_limiter: Box::pin(rate_limiter.rate_limit(0)),
+            inner: write,
+        }
+    }
+
+    pub fn inner(&self) -> &Write {
+        &self.inner
+    }
+
+    fn get_inner(self: std::pin::Pin<&mut Self>) -> std::pin::Pin<&mut Write>
+    where
+        Write: Unpin,
+    {
+        let this = self.get_mut();
+        std::pin::Pin::new(&mut this.inner)
+    }
+
+    /// Helper method for the use of the [AsyncWrite](tokio::io::AsyncWrite) implementation.
+    fn rate_limit(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>>
+    where
+        Write: AsyncWrite + Unpin,
+    {
+        let this = self.get_mut();
+        let write = std::pin::Pin::new(&mut this.inner);
+
+        let sleeping_rate_limiter = ready!(this.rate_limiter.poll_unpin(cx));
+
+        let result = write.poll_write(cx, buf);
+        let last_write_size = match &result {
+            std::task::Poll::Ready(Ok(write_size)) => 8 * *write_size,
+            _ => 0,
+        };
+
+        this.rate_limiter = sleeping_rate_limiter.rate_limit(last_write_size).boxed();
+
+        result
+    }
+}
+
+impl<Write> AsyncWrite for RateLimitedAsyncWrite<Write>
+where
+    Write: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        self.rate_limit(cx, buf)
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.get_inner().poll_flush(cx)
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.get_inner().poll_shutdown(cx)
+    }
+}
+
 pub struct FuturesRateLimitedAsyncReadWrite<ReadWrite> {
     rate_limiter: BoxFuture<'static, RateLimiterFacade>,
+    write_rate_limiter: BoxFuture<'static, RateLimiterFacade>,
     inner: ReadWrite,
 }
 
 impl<ReadWrite> FuturesRateLimitedAsyncReadWrite<ReadWrite> {
-    pub fn new(wrapped: ReadWrite, rate_limiter: RateLimiterFacade) -> Self {
+    pub fn new(
+        wrapped: ReadWrite,
+        rate_limiter: RateLimiterFacade,
+        write_rate_limiter: RateLimiterFacade,
+    ) -> Self {
         Self {
             rate_limiter: Box::pin(rate_limiter.rate_limit(0)),
+            write_rate_limiter: Box::pin(write_rate_limiter.rate_limit(0)),
             inner: wrapped,
         }
     }
@@ -173,6 +262,31 @@ impl<ReadWrite> FuturesRateLimitedAsyncReadWrite<ReadWrite> {
 
         result
     }
+
+    /// Helper method for the use of the [AsyncWrite](futures::AsyncWrite) implementation.
+    fn write_rate_limit(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>>
+    where
+        ReadWrite: futures::AsyncWrite + Unpin,
+    {
+        let this = self.get_mut();
+        let write = std::pin::Pin::new(&mut this.inner);
+
+        let sleeping_rate_limiter = ready!(this.write_rate_limiter.poll_unpin(cx));
+
+        let result = write.poll_write(cx, buf);
+        let last_write_size = match &result {
+            std::task::Poll::Ready(Ok(write_size)) => 8 * *write_size,
+            _ => 0,
+        };
+
+        this.write_rate_limiter = sleeping_rate_limiter.rate_limit(last_write_size).boxed();
+
+        result
+    }
 }
 
 impl<Read> futures::AsyncRead for FuturesRateLimitedAsyncReadWrite<Read>
@@ -197,7 +311,7 @@ where
         cx: &mut std::task::Context<'_>,
         buf: &[u8],
     ) -> std::task::Poll<std::io::Result<usize>> {
-        self.get_inner().poll_write(cx, buf)
+        self.write_rate_limit(cx, buf)
     }
 
     fn poll_flush(
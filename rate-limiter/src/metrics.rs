@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use substrate_prometheus_endpoint::{
+    register, Counter, Gauge, Opts, PrometheusError, Registry, U64,
+};
+
+use crate::NonZeroRatePerSecond;
+
+/// Metrics of a single rate-limiter, i.e. of all the connections sharing its bandwidth. Different rate-limiters are
+/// distinguished by the `protocol` and `direction` labels.
+#[derive(Clone)]
+pub enum RateLimiterMetrics {
+    Prometheus {
+        rate: Gauge<U64>,
+        active_connections: Gauge<U64>,
+        consumed_bits: Counter<U64>,
+        throttled_millis: Counter<U64>,
+    },
+    Noop,
+}
+
+impl RateLimiterMetrics {
+    pub fn new(
+        registry: Option<&Registry>,
+        protocol: &str,
+        direction: &str,
+    ) -> Result<Self, PrometheusError> {
+        let registry = match registry {
+            Some(registry) => registry,
+            None => return Ok(RateLimiterMetrics::Noop),
+        };
+        let opts = |name: &str, help: &str| {
+            Opts::new(name, help)
+                .const_label("protocol", protocol)
+                .const_label("direction", direction)
+        };
+        Ok(RateLimiterMetrics::Prometheus {
+            rate: register(
+                Gauge::with_opts(opts(
+                    "aleph_rate_limiter_rate",
+                    "current total bit-rate allowed by the rate-limiter",
+                ))?,
+                registry,
+            )?,
+            active_connections: register(
+                Gauge::with_opts(opts(
+                    "aleph_rate_limiter_active_connections",
+                    "number of connections currently sharing the bit-rate",
+                ))?,
+                registry,
+            )?,
+            consumed_bits: register(
+                Counter::with_opts(opts(
+                    "aleph_rate_limiter_consumed_bits",
+                    "number of bits accounted by the rate-limiter",
+                ))?,
+                registry,
+            )?,
+            throttled_millis: register(
+                Counter::with_opts(opts(
+                    "aleph_rate_limiter_throttled_millis",
+                    "total time in milliseconds connections spent waiting for the rate-limiter",
+                ))?,
+                registry,
+            )?,
+        })
+    }
+
+    pub fn noop() -> Self {
+        RateLimiterMetrics::Noop
+    }
+
+    pub fn report_bandwidth(&self, total_rate: NonZeroRatePerSecond, connections: u64) {
+        if let RateLimiterMetrics::Prometheus {
+            rate,
+            active_connections,
+            ..
+        } = self
+        {
+            rate.set(total_rate.into());
+            active_connections.set(connections);
+        }
+    }
+
+    pub fn report_consumed(&self, bits: u64) {
+        if let RateLimiterMetrics::Prometheus { consumed_bits, .. } = self {
+            consumed_bits.inc_by(bits);
+        }
+    }
+
+    pub fn report_throttled(&self, duration: Duration) {
+        if let RateLimiterMetrics::Prometheus {
+            throttled_millis, ..
+        } = self
+        {
+            throttled_millis.inc_by(duration.as_millis().try_into().unwrap_or(u64::MAX));
+        }
+    }
+}
@@ -1,8 +1,12 @@
-use std::time::Instant;
+use std::{num::NonZeroU64, time::Instant};
 
 use futures::future::pending;
 
-use crate::{token_bucket::SharedTokenBucket, RatePerSecond};
+use crate::{
+    metrics::RateLimiterMetrics,
+    token_bucket::{RateScaler, SharedTokenBucket},
+    RatePerSecond,
+};
 
 pub type SharedRateLimiter = RateLimiterFacade;
 
@@ -23,6 +27,7 @@ impl From<Deadline> for Option<Instant> {
 
 pub enum RateLimiterFacade {
     NoTraffic,
+    Unlimited,
     RateLimiter(SharedTokenBucket),
 }
 
@@ -34,9 +39,49 @@ impl RateLimiterFacade {
         }
     }
 
+    /// Constructs a rate limiter that does not limit the traffic at all if `rate` is not provided.
+    pub fn new_optional(rate: Option<RatePerSecond>) -> Self {
+        match rate {
+            Some(rate) => Self::new(rate),
+            None => Self::Unlimited,
+        }
+    }
+
+    /// Additionally limits every shared instance to `per_peer_rate`. Blocking the per-peer rate blocks all traffic.
+    pub fn with_per_peer_rate(self, per_peer_rate: RatePerSecond) -> Self {
+        match (self, per_peer_rate) {
+            (RateLimiterFacade::NoTraffic, _) | (_, RatePerSecond::Block) => Self::NoTraffic,
+            (RateLimiterFacade::Unlimited, RatePerSecond::Rate(rate)) => Self::RateLimiter(
+                SharedTokenBucket::new(NonZeroU64::MAX.into()).with_per_peer_rate(rate),
+            ),
+            (RateLimiterFacade::RateLimiter(rate_limiter), RatePerSecond::Rate(rate)) => {
+                Self::RateLimiter(rate_limiter.with_per_peer_rate(rate))
+            }
+        }
+    }
+
+    /// Reports the state of all shared instances using the given `metrics`.
+    pub fn with_metrics(self, metrics: RateLimiterMetrics) -> Self {
+        match self {
+            RateLimiterFacade::RateLimiter(rate_limiter) => {
+                Self::RateLimiter(rate_limiter.with_metrics(metrics))
+            }
+            rate_limiter => rate_limiter,
+        }
+    }
+
+    /// Returns a handle allowing to scale the rates of all shared instances, if there are any rates to scale.
+    pub fn rate_scaler(&self) -> Option<RateScaler> {
+        match self {
+            RateLimiterFacade::NoTraffic | RateLimiterFacade::Unlimited => None,
+            RateLimiterFacade::RateLimiter(rate_limiter) => Some(rate_limiter.rate_scaler()),
+        }
+    }
+
     pub async fn rate_limit(self, read_size: usize) -> Self {
         match self {
             RateLimiterFacade::NoTraffic => pending().await,
+            RateLimiterFacade::Unlimited => RateLimiterFacade::Unlimited,
             RateLimiterFacade::RateLimiter(rate_limiter) => RateLimiterFacade::RateLimiter(
                 rate_limiter
                     .rate_limit(read_size.try_into().unwrap_or(u64::MAX))
@@ -48,6 +93,7 @@ impl RateLimiterFacade {
     pub fn share(&self) -> Self {
         match self {
             RateLimiterFacade::NoTraffic => RateLimiterFacade::NoTraffic,
+            RateLimiterFacade::Unlimited => RateLimiterFacade::Unlimited,
             RateLimiterFacade::RateLimiter(shared_token_bucket) => {
                 RateLimiterFacade::RateLimiter(shared_token_bucket.share())
             }
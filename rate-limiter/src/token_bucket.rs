@@ -12,7 +12,7 @@ use futures::{future::pending, Future, FutureExt};
 use log::trace;
 use tokio::time::sleep;
 
-use crate::{NonZeroRatePerSecond, LOG_TARGET, MIN};
+use crate::{metrics::RateLimiterMetrics, NonZeroRatePerSecond, LOG_TARGET, MIN};
 
 /// Returns a non-decreasing values of type [std::time::Instant].
 pub trait TimeProvider {
@@ -177,6 +177,41 @@ where
     }
 }
 
+/// Percentage by which rates are scaled when no adjustment was requested.
+const DEFAULT_SCALE_PERCENT: u64 = 100;
+
+/// Allows to scale, at runtime, the configured rates of a [SharedTokenBucket] and all of its shared instances, e.g. to loosen
+/// the rate-limit when it might be hurting the node.
+#[derive(Clone)]
+pub struct RateScaler {
+    percent: Arc<AtomicU64>,
+}
+
+impl RateScaler {
+    fn new() -> Self {
+        Self {
+            percent: Arc::new(AtomicU64::new(DEFAULT_SCALE_PERCENT)),
+        }
+    }
+
+    /// Gets the current scale of the rates, in percents of the configured ones.
+    pub fn percent(&self) -> u64 {
+        self.percent.load(Ordering::SeqCst)
+    }
+
+    /// Sets the scale of the rates, in percents of the configured ones.
+    pub fn set_percent(&self, percent: u64) {
+        self.percent.store(percent, Ordering::SeqCst);
+    }
+
+    fn scale(&self, rate: NonZeroRatePerSecond) -> NonZeroRatePerSecond {
+        let rate = u64::from(rate).saturating_mul(self.percent()) / DEFAULT_SCALE_PERCENT;
+        NonZeroU64::try_from(rate)
+            .map(NonZeroRatePerSecond::from)
+            .unwrap_or(MIN)
+    }
+}
+
 /// Determines how often instances of [SharedBandwidthManager] should check if their allocated bandwidth has changed.
 const BANDWIDTH_CHECK_INTERVAL: Duration = Duration::from_millis(250);
 
@@ -184,6 +219,7 @@ const BANDWIDTH_CHECK_INTERVAL: Duration = Duration::from_millis(250);
 /// consumers of that bandwidth.
 pub struct SharedBandwidthManager {
     max_rate: NonZeroRatePerSecond,
+    scaler: RateScaler,
     peers_count: Arc<AtomicU64>,
     already_requested: Option<NonZeroRatePerSecond>,
 }
@@ -194,6 +230,7 @@ impl SharedBandwidthManager {
     pub fn new(max_rate: NonZeroRatePerSecond) -> Self {
         Self {
             max_rate,
+            scaler: RateScaler::new(),
             peers_count: Arc::new(AtomicU64::new(0)),
             already_requested: None,
         }
@@ -202,15 +239,24 @@ impl SharedBandwidthManager {
     pub fn share(&self) -> Self {
         Self {
             max_rate: self.max_rate,
+            scaler: self.scaler.clone(),
             peers_count: self.peers_count.clone(),
             already_requested: None,
         }
     }
 
+    fn total_rate(&self) -> NonZeroRatePerSecond {
+        self.scaler.scale(self.max_rate)
+    }
+
+    fn active_count(&self) -> u64 {
+        self.peers_count.load(Ordering::SeqCst)
+    }
+
     fn calculate_bandwidth(&mut self, active_children: Option<u64>) -> NonZeroRatePerSecond {
         let active_children =
             active_children.unwrap_or_else(|| self.peers_count.load(Ordering::SeqCst));
-        let rate = u64::from(self.max_rate) / active_children;
+        let rate = u64::from(self.total_rate()) / active_children;
         NonZeroU64::try_from(rate)
             .map(NonZeroRatePerSecond::from)
             .unwrap_or(MIN)
@@ -275,6 +321,10 @@ where
         }
     }
 
+    fn now(&self) -> Instant {
+        self.token_bucket.time_provider.now()
+    }
+
     /// Accounts `requested` units. A next call to [AsyncTokenBucket::wait] will
     /// account these units while calculating necessary delay.
     pub fn rate_limit(&mut self, requested: u64) {
@@ -313,10 +363,15 @@ where
 /// 1/n) ≈ bandwidth * (ln n + O(1))`. This can happen when each instance of [TokenBucket] tries to spend slightly more data
 /// than its initially acquired bandwidth, but small enough so none of them other instances receives a notification about
 /// ongoing bandwidth change.
+///
+/// Optionally, the share of every instance can be additionally capped by a per-peer rate, so a single peer cannot consume the
+/// whole bandwidth even if it is the only active one.
 pub struct SharedTokenBucket<TP = TokioTimeProvider, SU = TokioSleepUntil> {
     shared_bandwidth: SharedBandwidthManager,
     rate_limiter: AsyncTokenBucket<TP, SU>,
     need_to_notify_parent: bool,
+    per_peer_rate: Option<NonZeroRatePerSecond>,
+    metrics: RateLimiterMetrics,
 }
 
 impl SharedTokenBucket {
@@ -336,9 +391,24 @@ impl<TP, SU> SharedTokenBucket<TP, SU> {
             shared_bandwidth: SharedBandwidthManager::new(rate),
             rate_limiter,
             need_to_notify_parent: false,
+            per_peer_rate: None,
+            metrics: RateLimiterMetrics::noop(),
         }
     }
 
+    /// Reports the state of this instance and all instances shared from it using the given `metrics`.
+    pub fn with_metrics(mut self, metrics: RateLimiterMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Caps the bandwidth available to every instance shared from this one at `per_peer_rate`, regardless of its share of
+    /// the total bandwidth.
+    pub fn with_per_peer_rate(mut self, per_peer_rate: NonZeroRatePerSecond) -> Self {
+        self.per_peer_rate = Some(per_peer_rate);
+        self
+    }
+
     pub fn share(&self) -> Self
     where
         TP: Clone,
@@ -348,12 +418,27 @@ impl<TP, SU> SharedTokenBucket<TP, SU> {
             shared_bandwidth: self.shared_bandwidth.share(),
             rate_limiter: self.rate_limiter.clone(),
             need_to_notify_parent: false,
+            per_peer_rate: self.per_peer_rate,
+            metrics: self.metrics.clone(),
+        }
+    }
+
+    /// Returns a handle allowing to scale the rates of this instance and all instances shared with it.
+    pub fn rate_scaler(&self) -> RateScaler {
+        self.shared_bandwidth.scaler.clone()
+    }
+
+    fn cap_rate(&self, rate: NonZeroRatePerSecond) -> NonZeroRatePerSecond {
+        match self.per_peer_rate {
+            Some(per_peer_rate) => min(rate, self.shared_bandwidth.scaler.scale(per_peer_rate)),
+            None => rate,
         }
     }
 
     fn request_bandwidth(&mut self) -> NonZeroRatePerSecond {
         self.need_to_notify_parent = true;
-        self.shared_bandwidth.request_bandwidth()
+        let rate = self.shared_bandwidth.request_bandwidth();
+        self.cap_rate(rate)
     }
 
     fn notify_idle(&mut self) {
@@ -373,14 +458,23 @@ impl<TP, SU> SharedTokenBucket<TP, SU> {
         self.rate_limiter.set_rate(rate);
 
         self.rate_limiter.rate_limit(requested);
+        self.metrics.report_consumed(requested);
+        self.metrics.report_bandwidth(
+            self.shared_bandwidth.total_rate(),
+            self.shared_bandwidth.active_count(),
+        );
+        let throttled_since = self.rate_limiter.now();
 
         loop {
             futures::select! {
                 _ = self.rate_limiter.wait().fuse() => {
                     self.notify_idle();
+                    self.metrics
+                        .report_throttled(self.rate_limiter.now().saturating_duration_since(throttled_since));
                     return self;
                 },
                 rate = self.shared_bandwidth.bandwidth_changed().fuse() => {
+                    let rate = self.cap_rate(rate);
                     self.rate_limiter.set_rate(rate);
                 },
             }
@@ -966,6 +1060,60 @@ mod tests {
         assert_eq!(deadline, None);
     }
 
+    #[tokio::test]
+    async fn single_peer_is_capped_by_per_peer_rate() {
+        let limit_per_second = 10.try_into().expect("10 > 0 qed");
+        let per_peer_limit = 5.try_into().expect("5 > 0 qed");
+        let now = Instant::now();
+        let time_to_return = Arc::new(parking_lot::RwLock::new(now));
+        let time_provider = time_to_return.clone();
+        let time_provider: Arc<Box<dyn TimeProvider + Send + Sync>> =
+            Arc::new(Box::new(move || *time_provider.read()));
+
+        let rate_limiter = TracingRateLimiter::<_>::from((
+            limit_per_second,
+            time_provider,
+            SharedTracingSleepUntil::new(),
+        ))
+        .with_per_peer_rate(per_peer_limit);
+
+        let rate_limiter_cloned = rate_limiter.share();
+
+        let (_, deadline) = RateLimiter::rate_limit(rate_limiter, 5).await;
+        assert_eq!(deadline, Some(now + Duration::from_millis(1000)));
+
+        *time_to_return.write() = now + Duration::from_millis(2000);
+
+        let (_, deadline) = RateLimiter::rate_limit(rate_limiter_cloned, 10).await;
+        assert_eq!(deadline, Some(now + Duration::from_millis(3000)));
+    }
+
+    #[tokio::test]
+    async fn scaled_rate_applies_to_shared_instances() {
+        let limit_per_second = 10.try_into().expect("10 > 0 qed");
+        let per_peer_limit = 5.try_into().expect("5 > 0 qed");
+        let now = Instant::now();
+        let time_to_return = Arc::new(parking_lot::RwLock::new(now));
+        let time_provider = time_to_return.clone();
+        let time_provider: Arc<Box<dyn TimeProvider + Send + Sync>> =
+            Arc::new(Box::new(move || *time_provider.read()));
+
+        let rate_limiter = TracingRateLimiter::<_>::from((
+            limit_per_second,
+            time_provider,
+            SharedTracingSleepUntil::new(),
+        ))
+        .with_per_peer_rate(per_peer_limit);
+        let rate_scaler = rate_limiter.rate_scaler();
+
+        let rate_limiter_cloned = rate_limiter.share();
+        rate_scaler.set_percent(200);
+        assert_eq!(rate_scaler.percent(), 200);
+
+        let (_, deadline) = RateLimiter::rate_limit(rate_limiter_cloned, 10).await;
+        assert_eq!(deadline, Some(now + Duration::from_millis(1000)));
+    }
+
     #[tokio::test]
     async fn peers_receive_at_least_one_token_per_second() {
         let limit_per_second = 1.try_into().expect("1 > 0 qed");